@@ -0,0 +1,125 @@
+use codespan_reporting::{diagnostic::Diagnostic, files::Files};
+use duck::{FileId, GmlLibrary, RunSummary, cache::fnv1a};
+use hashbrown::HashMap;
+use std::path::Path;
+
+/// A record of the diagnostics found in a project, used to suppress them on later runs so that
+/// only new problems are reported. Diagnostics are identified by their lint, their file, and a
+/// hash of the code they point at, so that they survive unrelated edits moving them around.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct Baseline {
+    entries: Vec<BaselineEntry>,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+struct BaselineEntry {
+    lint: String,
+    file: String,
+    hash: String,
+    /// The number of identical diagnostics, such as the same mistake repeated in one file.
+    count: usize,
+}
+
+/// The lint, file and hash that identify a diagnostic.
+type Key = (String, String, String);
+
+impl Baseline {
+    /// Creates a baseline containing every diagnostic in the run.
+    pub fn new(run_summary: &RunSummary, project_directory: &Path) -> Self {
+        let mut counts: HashMap<Key, usize> = HashMap::new();
+        for report in run_summary.diagnostics() {
+            *counts
+                .entry(key(&report.diagnostic, run_summary.files(), project_directory))
+                .or_default() += 1;
+        }
+        let mut entries: Vec<BaselineEntry> = counts
+            .into_iter()
+            .map(|((lint, file, hash), count)| BaselineEntry {
+                lint,
+                file,
+                hash,
+                count,
+            })
+            .collect();
+        entries.sort();
+        Self { entries }
+    }
+
+    /// Reads a baseline from a json file.
+    ///
+    /// ### Errors
+    /// Returns an error if the file cannot be read, or is not a valid baseline.
+    pub fn read(path: &Path) -> Result<Self, std::io::Error> {
+        let text = std::fs::read_to_string(path)?;
+        serde_json::from_str(&text).map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))
+    }
+
+    /// Writes the baseline to a json file.
+    ///
+    /// ### Errors
+    /// Returns an error if the file cannot be written.
+    pub fn write(&self, path: &Path) -> Result<(), std::io::Error> {
+        std::fs::write(path, serde_json::to_string_pretty(self).unwrap())
+    }
+
+    /// Returns the number of diagnostics recorded in the baseline.
+    pub fn len(&self) -> usize {
+        self.entries.iter().map(|entry| entry.count).sum()
+    }
+
+    /// Removes every diagnostic recorded in the baseline from the run, returning how many were
+    /// removed. Each entry suppresses at most as many diagnostics as it recorded, so repeating an
+    /// existing mistake is still reported.
+    pub fn suppress(&self, run_summary: &mut RunSummary, project_directory: &Path) -> usize {
+        let mut remaining: HashMap<Key, usize> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                (
+                    (entry.lint.clone(), entry.file.clone(), entry.hash.clone()),
+                    entry.count,
+                )
+            })
+            .collect();
+        let keys: Vec<Key> = run_summary
+            .diagnostics()
+            .iter()
+            .map(|report| key(&report.diagnostic, run_summary.files(), project_directory))
+            .collect();
+        let mut keys = keys.into_iter();
+        let mut suppressed = 0;
+        run_summary.retain_diagnostics(|_| match remaining.get_mut(&keys.next().unwrap()) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                suppressed += 1;
+                false
+            }
+            _ => true,
+        });
+        suppressed
+    }
+}
+
+/// Returns the key identifying a diagnostic within a baseline.
+fn key(diagnostic: &Diagnostic<FileId>, library: &GmlLibrary, project_directory: &Path) -> Key {
+    // Lints always tag their diagnostics, so the only ones without a code are from parsing
+    let lint = diagnostic.code.clone().unwrap_or_else(|| "parse_error".into());
+    let Some(label) = diagnostic.labels.first() else {
+        return (lint, String::new(), String::new());
+    };
+    let name = library.name(label.file_id).unwrap_or_default();
+    let file = Path::new(&name)
+        .strip_prefix(project_directory)
+        .map_or(name.clone(), |path| path.to_string_lossy().replace('\\', "/"));
+
+    // The hash covers every line the label touches, ignoring indentation and line endings
+    let source = library.source(label.file_id).unwrap_or_default();
+    let start = source[..label.range.start.min(source.len())]
+        .rfind('\n')
+        .map_or(0, |i| i + 1);
+    let end = source[label.range.end.min(source.len())..]
+        .find('\n')
+        .map_or(source.len(), |i| label.range.end + i);
+    let code: Vec<&str> = source[start..end].lines().map(str::trim).collect();
+    (lint, file, format!("{:016x}", fnv1a(code.join("\n").as_bytes())))
+}
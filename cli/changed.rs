@@ -0,0 +1,79 @@
+use crate::RunOptions;
+use hashbrown::HashSet;
+use std::{
+    io::BufRead,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Returns the files a run should report diagnostics for, or None if it should report on the
+/// entire project, alongside a warning for each listed file that does not exist. Files can be
+/// listed directly, read from stdin by passing `-`, or found by asking git which files have
+/// changed since a given ref. Relative paths are resolved against the project directory, and every
+/// path is canonicalized, so that they can be compared against the names in a
+/// [duck::GmlLibrary]. Files git reports that no longer exist are skipped without a warning, as
+/// they were deleted and cannot contain any diagnostics.
+///
+/// ### Errors
+/// Returns an error if stdin cannot be read, or if git fails.
+pub fn selected_files(
+    options: &RunOptions,
+    project_directory: &Path,
+) -> Result<(Option<HashSet<PathBuf>>, Vec<String>), String> {
+    if options.files.is_empty() && options.changed_since.is_none() {
+        return Ok((None, vec![]));
+    }
+    let mut paths = vec![];
+    for path in &options.files {
+        if path == Path::new("-") {
+            for line in std::io::stdin().lock().lines() {
+                let line = line.map_err(|error| format!("Failed to read the list of files from stdin: {error}"))?;
+                if !line.trim().is_empty() {
+                    paths.push(PathBuf::from(line.trim()));
+                }
+            }
+        } else {
+            paths.push(path.clone());
+        }
+    }
+    let mut warnings = vec![];
+    let mut selected_files: HashSet<PathBuf> = paths
+        .into_iter()
+        .filter_map(|path| match project_directory.join(&path).canonicalize() {
+            Ok(path) => Some(path),
+            Err(_) => {
+                warnings.push(format!("{} does not exist", path.display()));
+                None
+            }
+        })
+        .collect();
+    if let Some(git_ref) = &options.changed_since {
+        // New files count as changed too, but git diff only knows about the files it tracks
+        let changed = git(project_directory, &["diff", "--name-only", "--relative", git_ref])?;
+        let untracked = git(project_directory, &["ls-files", "--others", "--exclude-standard"])?;
+        selected_files.extend(
+            changed
+                .lines()
+                .chain(untracked.lines())
+                .filter_map(|line| project_directory.join(line).canonicalize().ok()),
+        );
+    }
+    Ok((Some(selected_files), warnings))
+}
+
+/// Runs a git command within the directory, returning what it printed.
+fn git(directory: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(directory)
+        .output()
+        .map_err(|error| format!("Failed to run git: {error}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -1,4 +1,4 @@
-use clap::{ArgEnum, Parser, Subcommand};
+use clap::{ArgEnum, Args, Parser, Subcommand};
 use duck::Config;
 use std::path::PathBuf;
 
@@ -13,36 +13,30 @@ pub struct Cli {
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// Runs the primary linting process.
-    Run {
-        /// The path to the project directory to run on. Uses the current directory if not provided.
+    Run(RunOptions),
+    /// Runs duck as a language server, communicating over stdio.
+    Lsp,
+    /// Applies the suggested fixes of every lint in place.
+    Fix {
+        /// The path to the project directory to fix. Uses the current directory if not provided.
         #[clap(long, short, parse(from_os_str))]
         path: Option<PathBuf>,
 
-        /// Prevents duck from returning a non-zero status due to lint warnings.
+        /// Instead of writing any files, prints a unified diff of the changes that would be made.
         #[clap(long)]
-        allow_warnings: bool,
-
-        /// Prevents duck from returning a non-zero status due to lint denials.
-        #[clap(long)]
-        allow_errors: bool,
-
-        /// Prevents duck from returning a non-zero status due to gml parsing errors, or errors
-        /// accessing the projects files.
-        #[clap(long)]
-        allow_duck_errors: bool,
-
-        /// If provided, will force color output instead of deferring.
-        #[clap(long)]
-        color: bool,
+        dry_run: bool,
+    },
+    /// Formats gml files in place.
+    Fmt {
+        /// The path to the project directory to format. Uses the current directory if not
+        /// provided. Can alternatively pass the path to a singular gml file.
+        #[clap(long, short, parse(from_os_str))]
+        path: Option<PathBuf>,
 
-        /// Shortens summary message and omits it if no warnings or errors were found.
+        /// Instead of writing any files, lists the files that would be changed and returns a
+        /// non-zero status if there are any.
         #[clap(long)]
-        brief: bool,
-
-        /// File paths to ignore relative to the project directory. These are combined with any
-        /// that are listed in your config.
-        #[clap(long, multiple_values = true)]
-        ignored_file_paths: Vec<String>,
+        check: bool,
     },
     /// Creates a new configuration file in the current directory.
     NewConfig {
@@ -71,6 +65,78 @@ pub enum Commands {
     },
 }
 
+#[derive(Args, Debug)]
+pub struct RunOptions {
+    /// The path to the project directory to run on. Uses the current directory if not provided.
+    #[clap(long, short, parse(from_os_str))]
+    pub path: Option<PathBuf>,
+
+    /// Prevents duck from returning a non-zero status due to lint warnings.
+    #[clap(long)]
+    pub allow_warnings: bool,
+
+    /// Prevents duck from returning a non-zero status due to lint denials.
+    #[clap(long)]
+    pub allow_errors: bool,
+
+    /// Prevents duck from returning a non-zero status due to gml parsing errors, or errors
+    /// accessing the projects files.
+    #[clap(long)]
+    pub allow_duck_errors: bool,
+
+    /// If provided, will force color output instead of deferring.
+    #[clap(long)]
+    pub color: bool,
+
+    /// Shortens summary message and omits it if no warnings or errors were found.
+    #[clap(long)]
+    pub brief: bool,
+
+    /// File paths or gitignore-style globs to ignore relative to the project directory. These are
+    /// combined with any that are listed in your config or `.duckignore`.
+    #[clap(long, multiple_values = true)]
+    pub ignored_file_paths: Vec<String>,
+
+    /// The format to report diagnostics in. Every format other than `human` is printed to
+    /// stdout without any summary, for use by code scanning tools and CI annotations.
+    #[clap(long, arg_enum, default_value = "human")]
+    pub format: OutputFormat,
+
+    /// Suppresses every diagnostic recorded in the given baseline file, so that only new problems
+    /// are reported. Diagnostics are matched by their lint, file, and the code they point at, so
+    /// they stay suppressed when unrelated edits move them to another line.
+    #[clap(long, parse(from_os_str))]
+    pub baseline: Option<PathBuf>,
+
+    /// Records every diagnostic found in this run to the given baseline file, for use with
+    /// `--baseline` in later runs.
+    #[clap(long, parse(from_os_str))]
+    pub write_baseline: Option<PathBuf>,
+
+    /// Remembers the results of each run in `.duck/cache`, so that later runs only parse and lint
+    /// the files that have changed.
+    #[clap(long)]
+    pub cache: bool,
+
+    /// Keeps duck running, linting the project again whenever one of its gml files or its
+    /// configuration changes.
+    #[clap(long)]
+    pub watch: bool,
+
+    /// Only reports the diagnostics found within these files, such as those staged in a
+    /// pre-commit hook. Pass `-` to read the list from stdin, one path per line. The rest of the
+    /// project is still read, so that lints relying on its functions and other declarations stay
+    /// accurate.
+    #[clap(parse(from_os_str))]
+    pub files: Vec<PathBuf>,
+
+    /// Only reports the diagnostics found within files that have changed since the given git ref
+    /// (such as `main` or `HEAD~1`), including files git is not yet tracking. Combines with any
+    /// files listed directly.
+    #[clap(long, value_name = "GIT_REF")]
+    pub changed_since: Option<String>,
+}
+
 #[derive(Parser, Debug, Copy, Clone, ArgEnum)]
 pub enum ConfigTemplate {
     /// A standard config for duck, which includes the basics that every project should have.
@@ -87,6 +153,22 @@ impl From<ConfigTemplate> for Config {
     }
 }
 
+#[derive(Parser, Debug, Copy, Clone, PartialEq, Eq, ArgEnum)]
+pub enum OutputFormat {
+    /// Colored, human-readable diagnostics followed by a summary.
+    Human,
+    /// A JSON array containing every diagnostic.
+    Json,
+    /// A SARIF 2.1.0 log, as accepted by code scanning dashboards.
+    Sarif,
+    /// A checkstyle XML report.
+    Checkstyle,
+    /// A JUnit XML report, where each diagnostic is a failed test case.
+    Junit,
+    /// GitHub Actions workflow commands, which annotate pull requests.
+    Github,
+}
+
 #[derive(Parser, Debug, Copy, Clone, ArgEnum)]
 pub enum EmitFormat {
     Json,
@@ -0,0 +1,375 @@
+use codespan_reporting::diagnostic::{Diagnostic, LabelStyle, Severity};
+use duck::{
+    Config, FileId, IgnoreList,
+    driver::{self, Ctx},
+    lint::Report,
+    project,
+};
+use hashbrown::HashMap;
+use lsp_server::{Connection, Message, Notification, Request, Response};
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams, CodeActionProviderCapability,
+    DiagnosticRelatedInformation, DiagnosticSeverity, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, InitializeParams, Location, NumberOrString, Position, PublishDiagnosticsParams, Range,
+    ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind, TextEdit, Url, WorkspaceEdit,
+    notification::{DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, PublishDiagnostics},
+    request::CodeActionRequest,
+};
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+
+type LspResult = Result<(), Box<dyn std::error::Error + Sync + Send>>;
+
+/// Runs duck as a language server over stdio until the client asks it to exit.
+pub async fn run() -> i32 {
+    let (connection, io_threads) = Connection::stdio();
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+        ..Default::default()
+    };
+    let params = match connection.initialize(serde_json::to_value(capabilities).unwrap()) {
+        Ok(params) => params,
+        Err(error) => {
+            eprintln!("duck lsp failed to initialize: {error}");
+            return 1;
+        }
+    };
+    let params: InitializeParams = serde_json::from_value(params).unwrap_or_default();
+
+    // Load the project's config and collect what we can about it before any documents arrive
+    let project_directory = project_directory(&params);
+    let mut server = Server {
+        connection,
+        config: Config::default(),
+        ctx: Ctx::default(),
+        documents: HashMap::new(),
+        file_ids: HashMap::new(),
+        next_file_id: 0,
+        files: HashMap::new(),
+        references: HashMap::new(),
+        project_directory: project_directory.clone(),
+    };
+    if let Some(project_directory) = project_directory {
+        let (duck, _) = super::create_duck(&project_directory);
+        server.config = duck.config().clone();
+        server.scan_project(&project_directory).await;
+    }
+
+    let result = tokio::task::block_in_place(|| server.main_loop());
+    drop(server);
+    io_threads.join().ok();
+    i32::from(result.is_err())
+}
+
+/// Returns the root directory of the workspace the client opened, if any.
+fn project_directory(params: &InitializeParams) -> Option<PathBuf> {
+    #[allow(deprecated)]
+    let root_uri = params
+        .workspace_folders
+        .as_ref()
+        .and_then(|folders| folders.first())
+        .map(|folder| &folder.uri)
+        .or(params.root_uri.as_ref());
+    root_uri.and_then(|uri| uri.to_file_path().ok())
+}
+
+/// An open document, alongside the diagnostics last found within it.
+struct Document {
+    source: &'static str,
+    diagnostics: Vec<Report>,
+}
+
+struct Server {
+    connection: Connection,
+    config: Config,
+    ctx: Ctx,
+    documents: HashMap<Url, Document>,
+    /// The id each file's declarations are recorded under in the [Ctx]. See [Server::file_id].
+    file_ids: HashMap<PathBuf, FileId>,
+    /// The id the next document outside of the scan will be given.
+    next_file_id: FileId,
+    /// The uri and latest source of every file with an id, so that labels pointing into other
+    /// files can be located.
+    files: HashMap<FileId, (Url, &'static str)>,
+    /// The names each file references, from which the [Ctx]'s references are rebuilt whenever a
+    /// document changes, as a document may stop referencing a name.
+    references: HashMap<FileId, BTreeSet<String>>,
+    project_directory: Option<PathBuf>,
+}
+impl Server {
+    fn main_loop(&mut self) -> LspResult {
+        while let Ok(message) = self.connection.receiver.recv() {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    self.handle_request(request)?;
+                }
+                Message::Notification(notification) => self.handle_notification(notification)?,
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&mut self, request: Request) -> LspResult {
+        match request.extract::<CodeActionParams>(<CodeActionRequest as lsp_types::request::Request>::METHOD) {
+            Ok((id, params)) => {
+                let actions = self.code_actions(&params);
+                self.respond(Response::new_ok(id, actions))
+            }
+            Err(lsp_server::ExtractError::MethodMismatch(request)) => self.respond(Response::new_err(
+                request.id,
+                lsp_server::ErrorCode::MethodNotFound as i32,
+                format!("duck does not support `{}`", request.method),
+            )),
+            Err(lsp_server::ExtractError::JsonError { error, .. }) => Err(error.into()),
+        }
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> LspResult {
+        use lsp_types::notification::Notification as _;
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams = serde_json::from_value(notification.params)?;
+                self.update_document(params.text_document.uri, params.text_document.text)
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams = serde_json::from_value(notification.params)?;
+                // We only advertise full syncs, so the last change contains the entire document
+                match params.content_changes.into_iter().last() {
+                    Some(change) => self.update_document(params.text_document.uri, change.text),
+                    None => Ok(()),
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams = serde_json::from_value(notification.params)?;
+                self.documents.remove(&params.text_document.uri);
+                self.publish_diagnostics(params.text_document.uri, vec![])
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Collects the global declarations made throughout the project, which the late pass uses,
+    /// alongside the id each file was loaded with and the names each file references.
+    async fn scan_project(&mut self, project_directory: &Path) {
+        let (ignore_list, _) = IgnoreList::load(project_directory, &self.config.ignored_file_paths);
+        let (path_receiver, _) = driver::start_gml_discovery(project_directory, ignore_list);
+        let (file_receiver, file_handle) = driver::start_file_load(path_receiver);
+        let (mut ast_receiver, _) = driver::start_parse(file_receiver);
+        while let Some(ast) = ast_receiver.recv().await {
+            let mut file_ctx = Ctx::default();
+            file_ctx.collect_ast(&ast);
+            self.references.insert(ast.file_id(), file_ctx.references.clone());
+            self.ctx.extend(file_ctx);
+        }
+        self.ctx.collect_assets(project_directory);
+
+        // Files are named by their canonical paths when loaded
+        let Ok((_, library, _)) = file_handle.await else {
+            return;
+        };
+        for file_id in 0..library.len() {
+            let file = library.get(file_id).unwrap();
+            let path = PathBuf::from(file.name());
+            if let Ok(uri) = Url::from_file_path(&path) {
+                self.files.insert(file_id, (uri, *file.source()));
+            }
+            self.file_ids.insert(path, file_id);
+        }
+        self.next_file_id = library.len();
+    }
+
+    /// Re-lints a document with its new source and publishes the results.
+    fn update_document(&mut self, uri: Url, text: String) -> LspResult {
+        // The parser requires static sources, so each version of the document is leaked, just as
+        // the files loaded during a run are
+        let source: &'static str = Box::leak(text.into_boxed_str());
+        let diagnostics = self.lint_document(&uri, source);
+        let lsp_diagnostics = diagnostics
+            .iter()
+            .map(|report| self.lsp_diagnostic(&report.diagnostic))
+            .collect();
+        self.documents.insert(uri.clone(), Document { source, diagnostics });
+        self.publish_diagnostics(uri, lsp_diagnostics)
+    }
+
+    /// Parses and lints a document's source, updating the [Ctx] with its declarations.
+    fn lint_document(&mut self, uri: &Url, source: &'static str) -> Vec<Report> {
+        let file_id = self.file_id(uri);
+        self.files.insert(file_id, (uri.clone(), source));
+        let (mut ast, parse_errors) = driver::parse_gml_partial(source, &file_id);
+        let mut diagnostics: Vec<Report> = parse_errors.into_iter().map(Report::from).collect();
+        let mut config = None;
+        if let Ok(path) = uri.to_file_path() {
+            ast.set_origin(project::ast_origin(&path));
+            config = self
+                .project_directory
+                .as_ref()
+                .and_then(|project_directory| path.strip_prefix(project_directory).ok())
+                .and_then(|path| self.config.for_file(path));
+        }
+        // The document's declarations replace what the scan found, as they may have changed
+        let mut declarations = Ctx::default();
+        declarations.collect_ast(&ast);
+        self.ctx.symbols.replace_file(file_id, declarations.symbols);
+        self.ctx.instance_variables.extend(declarations.instance_variables);
+        self.references.insert(file_id, declarations.references);
+        self.ctx.references = self.references.values().flatten().cloned().collect();
+        driver::lint_ast(
+            &ast,
+            &mut diagnostics,
+            config.as_ref().unwrap_or(&self.config),
+            &self.ctx,
+        );
+        diagnostics
+    }
+
+    /// Returns the id a document's declarations are recorded under. A document loaded by the scan
+    /// shares the id it was loaded with, so that its declarations replace those the scan found,
+    /// while any other document is given an id of its own.
+    fn file_id(&mut self, uri: &Url) -> FileId {
+        let path = uri
+            .to_file_path()
+            .ok()
+            .and_then(|path| path.canonicalize().ok())
+            .unwrap_or_else(|| PathBuf::from(uri.as_str()));
+        if let Some(file_id) = self.file_ids.get(&path) {
+            return *file_id;
+        }
+        let file_id = self.next_file_id;
+        self.next_file_id += 1;
+        self.file_ids.insert(path, file_id);
+        file_id
+    }
+
+    /// Returns a quick fix for every diagnostic with suggestions within the requested range.
+    fn code_actions(&self, params: &CodeActionParams) -> Vec<CodeActionOrCommand> {
+        let uri = &params.text_document.uri;
+        let Some(document) = self.documents.get(uri) else {
+            return vec![];
+        };
+        document
+            .diagnostics
+            .iter()
+            .filter_map(|report| {
+                if report.suggestions.is_empty() {
+                    return None;
+                }
+                let lsp_diagnostic = self.lsp_diagnostic(&report.diagnostic);
+                if !ranges_overlap(&lsp_diagnostic.range, &params.range) {
+                    return None;
+                }
+                let edits = report
+                    .suggestions
+                    .iter()
+                    .map(|suggestion| TextEdit {
+                        range: lsp_range(document.source, suggestion.span.start(), suggestion.span.end()),
+                        new_text: suggestion.replacement.clone(),
+                    })
+                    .collect();
+                Some(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: match &report.diagnostic.code {
+                        Some(code) => format!("Apply the suggested fix for `{code}`"),
+                        None => "Apply the suggested fix".into(),
+                    },
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![lsp_diagnostic]),
+                    edit: Some(WorkspaceEdit {
+                        changes: Some([(uri.clone(), edits)].into_iter().collect()),
+                        ..Default::default()
+                    }),
+                    is_preferred: Some(true),
+                    ..Default::default()
+                }))
+            })
+            .collect()
+    }
+
+    /// Converts one of duck's diagnostics into one the client can display. Secondary labels are
+    /// sent as related information, as they may point into other files.
+    fn lsp_diagnostic(&self, diagnostic: &Diagnostic<FileId>) -> lsp_types::Diagnostic {
+        let label = diagnostic
+            .labels
+            .iter()
+            .find(|label| label.style == LabelStyle::Primary)
+            .or_else(|| diagnostic.labels.first());
+        let message = match label {
+            Some(label) if !label.message.is_empty() => format!("{}\n{}", diagnostic.message, label.message),
+            _ => diagnostic.message.clone(),
+        };
+        let related_information = diagnostic
+            .labels
+            .iter()
+            .filter(|label| label.style == LabelStyle::Secondary)
+            .filter_map(|label| {
+                let (uri, source) = self.files.get(&label.file_id)?;
+                Some(DiagnosticRelatedInformation {
+                    location: Location::new(uri.clone(), lsp_range(source, label.range.start, label.range.end)),
+                    message: label.message.clone(),
+                })
+            })
+            .collect::<Vec<_>>();
+        lsp_types::Diagnostic {
+            range: label
+                .and_then(|label| {
+                    let (_, source) = self.files.get(&label.file_id)?;
+                    Some(lsp_range(source, label.range.start, label.range.end))
+                })
+                .unwrap_or_default(),
+            severity: Some(match diagnostic.severity {
+                Severity::Bug | Severity::Error => DiagnosticSeverity::ERROR,
+                Severity::Warning => DiagnosticSeverity::WARNING,
+                Severity::Note => DiagnosticSeverity::INFORMATION,
+                Severity::Help => DiagnosticSeverity::HINT,
+            }),
+            code: diagnostic.code.clone().map(NumberOrString::String),
+            source: Some("duck".into()),
+            message,
+            related_information: (!related_information.is_empty()).then_some(related_information),
+            ..Default::default()
+        }
+    }
+
+    fn publish_diagnostics(&self, uri: Url, diagnostics: Vec<lsp_types::Diagnostic>) -> LspResult {
+        use lsp_types::notification::Notification as _;
+        let params = PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version: None,
+        };
+        self.connection.sender.send(Message::Notification(Notification::new(
+            PublishDiagnostics::METHOD.into(),
+            params,
+        )))?;
+        Ok(())
+    }
+
+    fn respond(&self, response: Response) -> LspResult {
+        self.connection.sender.send(Message::Response(response))?;
+        Ok(())
+    }
+}
+
+fn lsp_range(source: &str, start: usize, end: usize) -> Range {
+    Range::new(lsp_position(source, start), lsp_position(source, end))
+}
+
+/// Converts a byte offset into a line and utf-16 column, which is what clients expect by default.
+fn lsp_position(source: &str, offset: usize) -> Position {
+    let offset = offset.min(source.len());
+    let before = &source[..offset];
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let character = source[line_start..offset].encode_utf16().count();
+    Position::new(line as u32, character as u32)
+}
+
+fn ranges_overlap(a: &Range, b: &Range) -> bool {
+    a.start <= b.end && b.start <= a.end
+}
@@ -1,42 +1,38 @@
+use baseline::Baseline;
 use clap::Parser;
-use codespan_reporting::term::termcolor::{ColorChoice, StandardStream};
+use codespan_reporting::{
+    files::Files,
+    term::termcolor::{ColorChoice, StandardStream},
+};
 use colored::Colorize;
 use duck::{
-    Config, Duck, driver,
-    lint::{Lint, LintLevelSetting, collection::*},
+    Config, ConfigWarning, Duck, FileId, GmlLibrary, IgnoreList, RunSummary, driver,
+    lint::{Lint, LintLevelSetting, Suggestion, collection::*},
     parse::Ast,
 };
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use num_format::{Locale, ToFormattedString};
-use std::path::{Path, PathBuf};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 
+mod baseline;
+mod changed;
 mod input;
+mod lsp;
+mod output;
+mod watch;
 pub use input::*;
 
 #[tokio::main]
 async fn main() {
     let input = Cli::parse();
     let status_code = match input.command {
-        Commands::Run {
-            path,
-            allow_warnings,
-            allow_errors,
-            allow_duck_errors,
-            color,
-            brief,
-            ignored_file_paths,
-        } => {
-            run(
-                path,
-                allow_warnings,
-                allow_errors,
-                allow_duck_errors,
-                color,
-                brief,
-                ignored_file_paths,
-            )
-            .await
-        }
+        Commands::Run(options) => run(options).await,
+        Commands::Lsp => lsp::run().await,
+        Commands::Fix { path, dry_run } => fix(path, dry_run).await,
+        Commands::Fmt { path, check } => fmt(path, check).await,
         Commands::NewConfig { template } => new_config(template.unwrap_or(ConfigTemplate::Default)),
         Commands::Explain { lint_name } => explain(lint_name),
         Commands::Emit {
@@ -48,34 +44,161 @@ async fn main() {
     std::process::exit(status_code);
 }
 
-async fn run(
-    path: Option<PathBuf>,
-    allow_warnings: bool,
-    allow_denials: bool,
-    allow_errors: bool,
-    color: bool,
-    brief: bool,
-    mut ignored_file_paths: Vec<String>,
-) -> i32 {
+async fn run(options: RunOptions) -> i32 {
     // Force colors?
-    if color {
+    if options.color {
         std::env::set_var("CLICOLOR_FORCE", "1");
     }
 
+    let current_directory = options
+        .path
+        .clone()
+        .unwrap_or_else(|| std::env::current_dir().expect("Cannot access the current directory!"));
+    let selected_files = match changed::selected_files(&options, &current_directory) {
+        Ok((selected_files, warnings)) => {
+            warnings.iter().for_each(|warning| {
+                eprintln!("{}: {warning}", "warning".yellow().bold());
+            });
+            selected_files
+        }
+        Err(error) => {
+            eprintln!("{}: {error}", "error".bright_red().bold());
+            return 1;
+        }
+    };
+    if options.watch {
+        return watch::watch(&options, &current_directory, selected_files.as_ref()).await;
+    }
+
     // Run duck
     let timer = std::time::Instant::now();
-    let current_directory =
-        path.unwrap_or_else(|| std::env::current_dir().expect("Cannot access the current directory!"));
     let (mut duck, config_usage) = create_duck(&current_directory);
-    duck.config_mut().ignored_file_paths.append(&mut ignored_file_paths);
-    let run_summary = duck.run(&current_directory).await.unwrap();
-    let total_duration = timer.elapsed();
+    duck.config_mut()
+        .ignored_file_paths
+        .extend(options.ignored_file_paths.iter().cloned());
+    duck.set_caching(options.cache);
+    let mut run_summary = duck.run(&current_directory).await.unwrap();
+    report(
+        &options,
+        &current_directory,
+        &mut run_summary,
+        selected_files.as_ref(),
+        &config_usage,
+        timer.elapsed(),
+    )
+}
+
+/// Prints the results of a run, returning the status code duck should exit with.
+fn report(
+    options: &RunOptions,
+    current_directory: &Path,
+    run_summary: &mut RunSummary,
+    selected_files: Option<&HashSet<PathBuf>>,
+    config_usage: &ConfigUsage,
+    total_duration: std::time::Duration,
+) -> i32 {
+    let &RunOptions {
+        allow_warnings,
+        allow_errors: allow_denials,
+        allow_duck_errors: allow_errors,
+        color,
+        brief,
+        format,
+        ..
+    } = options;
+
+    // Only report on the files we were asked about
+    if let Some(selected_files) = selected_files {
+        let selected: Vec<bool> = run_summary
+            .diagnostics()
+            .iter()
+            .map(|report| {
+                report.diagnostic.labels.first().is_none_or(|label| {
+                    let name = run_summary.files().name(label.file_id).unwrap_or_default();
+                    selected_files.contains(Path::new(&name))
+                })
+            })
+            .collect();
+        let mut selected = selected.into_iter();
+        run_summary.retain_diagnostics(|_| selected.next().unwrap());
+    }
+
+    // Suppress the diagnostics that were already present when the baseline was written
+    let project_directory = current_directory
+        .canonicalize()
+        .unwrap_or_else(|_| current_directory.to_path_buf());
+    let mut baseline_notes = vec![];
+    if let Some(path) = &options.write_baseline {
+        let baseline = Baseline::new(run_summary, &project_directory);
+        if let Err(error) = baseline.write(path) {
+            eprintln!("error: Failed to write the baseline to {}: {error}", path.display());
+            return 1;
+        }
+        baseline.suppress(run_summary, &project_directory);
+        baseline_notes.push(format!(
+            "Wrote a baseline of {} diagnostic{} to {}.",
+            baseline.len(),
+            if baseline.len() == 1 { "" } else { "s" },
+            path.display()
+        ));
+    }
+    if let Some(path) = &options.baseline {
+        let baseline = match Baseline::read(path) {
+            Ok(baseline) => baseline,
+            Err(error) => {
+                eprintln!("error: Failed to read the baseline at {}: {error}", path.display());
+                return 1;
+            }
+        };
+        let suppressed = baseline.suppress(run_summary, &project_directory);
+        if suppressed > 0 {
+            baseline_notes.push(format!(
+                "{suppressed} diagnostic{} recorded in {} {} suppressed.",
+                if suppressed == 1 { "" } else { "s" },
+                path.display(),
+                if suppressed == 1 { "was" } else { "were" },
+            ));
+        }
+    }
+
+    // Machine-readable formats only print the diagnostics, leaving any problems to stderr
+    if format != OutputFormat::Human {
+        print!(
+            "{}",
+            output::render(
+                format,
+                run_summary.diagnostics(),
+                run_summary.files(),
+                current_directory
+            )
+        );
+        match config_usage {
+            ConfigUsage::Failed(error) => eprintln!(
+                "error: Your config was not used in this run, as duck encountered the following error while being parsed: {:?}",
+                error
+            ),
+            ConfigUsage::Some(warnings) => warnings.iter().for_each(|warning| {
+                eprintln!("warning: {warning} in .duck.toml");
+            }),
+            ConfigUsage::None => {}
+        }
+        run_summary.warnings().iter().for_each(|warning| {
+            eprintln!("warning: {warning}");
+        });
+        run_summary.io_errors().iter().for_each(|error| {
+            eprintln!("error: {error}");
+        });
+        baseline_notes.iter().for_each(|note| {
+            eprintln!("note: {note}");
+        });
+        return exit_code(run_summary, allow_warnings, allow_denials, allow_errors);
+    }
 
     // Output the results
     let writer = StandardStream::stderr(if color { ColorChoice::Always } else { ColorChoice::Auto });
     let config = codespan_reporting::term::Config::default();
     for report in run_summary.diagnostics() {
-        codespan_reporting::term::emit(&mut writer.lock(), &config, run_summary.files(), report).unwrap();
+        codespan_reporting::term::emit(&mut writer.lock(), &config, run_summary.files(), &report.diagnostic).unwrap();
     }
 
     let seperation_string = String::from_utf8(vec![b'-'; 50]).unwrap();
@@ -109,6 +232,9 @@ async fn run(
             .bright_black()
         );
     }
+    run_summary.warnings().iter().for_each(|warning| {
+        println!("{}: {warning}", "warning".yellow().bold());
+    });
     if !run_summary.io_errors().is_empty() {
         println!(
             "{}: The following errors occured while trying to read your project's files...\n",
@@ -118,16 +244,25 @@ async fn run(
             println!("{error}");
         })
     }
+    baseline_notes.iter().for_each(|note| {
+        println!("{}: {note}", "note".bright_black().bold());
+    });
     if !brief {
         println!("{seperation_string}");
     }
     match config_usage {
         ConfigUsage::None => println!("{}", "note: You are not using a configuration file, which is highly recommended! Use `duck new-config` to generate one.\n".bright_black().bold()),
         ConfigUsage::Failed(error) => println!("{}: Your config was not used in this run, as duck encountered the following error while being parsed: {:?}\n", "error".bright_red().bold(), error),
-        ConfigUsage::Some => {}
+        ConfigUsage::Some(warnings) => warnings.iter().for_each(|warning| {
+            println!("{}: {warning} in .duck.toml", "warning".yellow().bold());
+        }),
     }
 
-    // Return the status code
+    exit_code(run_summary, allow_warnings, allow_denials, allow_errors)
+}
+
+/// Returns the status code a run should exit with.
+fn exit_code(run_summary: &RunSummary, allow_warnings: bool, allow_denials: bool, allow_errors: bool) -> i32 {
     i32::from(
         (!allow_warnings && run_summary.warning_count() != 0)
             || (!allow_denials && run_summary.denial_count() != 0)
@@ -171,8 +306,16 @@ fn explain(name: String) -> i32 {
             ConditionWrapper::default_level(),
         ),
         "deprecated" => (Deprecated::explanation().to_string(), Deprecated::default_level()),
+        "draw_outside_draw_event" => (
+            DrawOutsideDrawEvent::explanation().to_string(),
+            DrawOutsideDrawEvent::default_level(),
+        ),
         "draw_sprite" => (DrawSprite::explanation().to_string(), DrawSprite::default_level()),
         "draw_text" => (DrawText::explanation().to_string(), DrawText::default_level()),
+        "duplicate_declaration" => (
+            DuplicateDeclaration::explanation().to_string(),
+            DuplicateDeclaration::default_level(),
+        ),
         "english_flavor_violation" => (
             EnglishFlavorViolation::explanation().to_string(),
             EnglishFlavorViolation::default_level(),
@@ -253,10 +396,19 @@ fn explain(name: String) -> i32 {
             UnassignedConstructor::explanation().to_string(),
             UnassignedConstructor::default_level(),
         ),
+        "undefined_identifier" => (
+            UndefinedIdentifier::explanation().to_string(),
+            UndefinedIdentifier::default_level(),
+        ),
         "unnecessary_grouping" => (
             UnnecessaryGrouping::explanation().to_string(),
             UnnecessaryGrouping::default_level(),
         ),
+        "unused_allow" => (UnusedAllow::explanation().to_string(), UnusedAllow::default_level()),
+        "unused_function" => (
+            UnusedFunction::explanation().to_string(),
+            UnusedFunction::default_level(),
+        ),
         "unused_local_variable" => (
             UnusedLocalVariable::explanation().to_string(),
             UnusedLocalVariable::default_level(),
@@ -311,10 +463,11 @@ async fn emit(path: Option<PathBuf>, output_path: PathBuf, format: Option<EmitFo
     } else {
         let current_directory =
             path.unwrap_or_else(|| std::env::current_dir().expect("Cannot access the current directory!"));
-        let (path_receiver, _) = driver::start_gml_discovery(&current_directory, vec![]);
+        let (ignore_list, _) = IgnoreList::load(&current_directory, &[]);
+        let (path_receiver, _) = driver::start_gml_discovery(&current_directory, ignore_list);
         let (mut file_receiver, file_handle) = driver::start_file_load(path_receiver);
         let (_, library, _) = file_handle.await.unwrap();
-        while let Some((file_id, data)) = file_receiver.recv().await {
+        while let Some((file_id, data, _)) = file_receiver.recv().await {
             let file = library.get(file_id).expect("Failed to find a file in the library!");
             if let Ok(ast) = driver::parse_gml(data, &file_id) {
                 emit.insert(file.name().clone(), ast);
@@ -333,10 +486,154 @@ async fn emit(path: Option<PathBuf>, output_path: PathBuf, format: Option<EmitFo
     Ok(())
 }
 
+async fn fix(path: Option<PathBuf>, dry_run: bool) -> i32 {
+    let current_directory =
+        path.unwrap_or_else(|| std::env::current_dir().expect("Cannot access the current directory!"));
+    let (duck, _) = create_duck(&current_directory);
+    let run_summary = duck.run(&current_directory).await.unwrap();
+
+    // Group the fixes by the file they apply to
+    let mut fixes: BTreeMap<FileId, Vec<Vec<Suggestion>>> = BTreeMap::new();
+    for report in run_summary.diagnostics() {
+        if let Some(suggestion) = report.suggestions.first() {
+            fixes
+                .entry(suggestion.file_id)
+                .or_default()
+                .push(report.suggestions.clone());
+        }
+    }
+
+    let total_fixes: usize = fixes.values().map(Vec::len).sum();
+    let mut applied_fixes = 0;
+    let mut io_errors = vec![];
+    for (file_id, file_fixes) in fixes {
+        let file = run_summary.files().get(file_id).unwrap();
+        let (fixed, applied) = duck::lint::apply_suggestions(file.source(), &file_fixes);
+        applied_fixes += applied;
+        if dry_run {
+            let diff = similar::TextDiff::from_lines(*file.source(), fixed.as_str());
+            print!("{}", diff.unified_diff().header(file.name(), file.name()));
+        } else if let Err(error) = std::fs::write(file.name(), fixed) {
+            io_errors.push(error);
+        }
+    }
+
+    println!(
+        "  {}",
+        format!(
+            "🦆 <( {} {} fix{}! )",
+            if dry_run { "Would apply" } else { "Applied" },
+            applied_fixes.to_string().green().bold(),
+            if applied_fixes == 1 { "" } else { "es" }
+        )
+        .bold()
+    );
+    let skipped_fixes = total_fixes - applied_fixes;
+    if skipped_fixes != 0 {
+        println!(
+            "{}",
+            format!(
+                "note: Skipped {skipped_fixes} fix{} that overlapped with others. Run `duck fix` again to apply {}.",
+                if skipped_fixes == 1 { "" } else { "es" },
+                if skipped_fixes == 1 { "it" } else { "them" },
+            )
+            .bright_black()
+            .bold()
+        );
+    }
+    if !io_errors.is_empty() {
+        println!(
+            "{}: The following errors occured while trying to write your project's files...\n",
+            "error".bright_red().bold()
+        );
+        io_errors.iter().for_each(|error| {
+            println!("{error}");
+        })
+    }
+    i32::from(!io_errors.is_empty())
+}
+
+async fn fmt(path: Option<PathBuf>, check: bool) -> i32 {
+    let mut library = GmlLibrary::new();
+    let mut io_errors = vec![];
+    let project_directory = if let Some(path) = path.as_ref().filter(|v| v.extension().is_some_and(|v| v == "gml")) {
+        match std::fs::read_to_string(path) {
+            Ok(data) => {
+                library.add(path.to_str().unwrap().into(), Box::leak(Box::from(data)));
+            }
+            Err(error) => io_errors.push(error),
+        }
+        path.parent().map(Path::to_path_buf).unwrap_or_default()
+    } else {
+        let current_directory =
+            path.unwrap_or_else(|| std::env::current_dir().expect("Cannot access the current directory!"));
+        let (duck, _) = create_duck(&current_directory);
+        let (ignore_list, _) = IgnoreList::load(&current_directory, &duck.config().ignored_file_paths);
+        let (path_receiver, _) = driver::start_gml_discovery(&current_directory, ignore_list);
+        let (mut file_receiver, file_handle) = driver::start_file_load(path_receiver);
+        while file_receiver.recv().await.is_some() {}
+        let (_, loaded_library, mut loading_errors) = file_handle.await.unwrap();
+        library = loaded_library;
+        io_errors.append(&mut loading_errors);
+        current_directory
+    };
+    let (duck, _) = create_duck(&project_directory);
+
+    let writer = StandardStream::stderr(ColorChoice::Auto);
+    let mut changed_files = 0;
+    let mut file_id = 0;
+    while let Ok(file) = library.get(file_id) {
+        let ast = match driver::parse_gml_lossless(file.source(), &file_id) {
+            Ok(ast) => ast,
+            Err(error) => {
+                let config = codespan_reporting::term::Config::default();
+                codespan_reporting::term::emit(&mut writer.lock(), &config, &library, &error).unwrap();
+                io_errors.push(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("{} could not be formatted as it contains errors", file.name()),
+                ));
+                file_id += 1;
+                continue;
+            }
+        };
+        let formatted = duck::format::format_ast(&ast, file.source(), duck.config());
+        if formatted != *file.source() {
+            changed_files += 1;
+            if check {
+                println!("Would reformat {}", file.name());
+            } else if let Err(error) = std::fs::write(file.name(), formatted) {
+                io_errors.push(error);
+            }
+        }
+        file_id += 1;
+    }
+
+    println!(
+        "  {}",
+        format!(
+            "🦆 <( {} {} file{}! )",
+            if check { "Would reformat" } else { "Reformatted" },
+            changed_files.to_string().yellow().bold(),
+            if changed_files == 1 { "" } else { "s" }
+        )
+        .bold()
+    );
+    if !io_errors.is_empty() {
+        println!(
+            "{}: The following errors occured while trying to format your project's files...\n",
+            "error".bright_red().bold()
+        );
+        io_errors.iter().for_each(|error| {
+            println!("{error}");
+        })
+    }
+    i32::from(!io_errors.is_empty() || (check && changed_files != 0))
+}
+
 fn create_duck(current_directory: &Path) -> (Duck, ConfigUsage) {
     if let Ok(text) = std::fs::read_to_string(current_directory.join(".duck.toml")) {
-        match toml::from_str::<Config>(&text) {
-            Ok(config) => (Duck::new(config), ConfigUsage::Some),
+        match Config::from_toml(&text) {
+            Ok((config, warnings)) => (Duck::new(config), ConfigUsage::Some(warnings)),
             Err(e) => (Duck::default(), ConfigUsage::Failed(e)),
         }
     } else {
@@ -347,6 +644,6 @@ fn create_duck(current_directory: &Path) -> (Duck, ConfigUsage) {
 #[derive(Debug)]
 enum ConfigUsage {
     None,
-    Some,
+    Some(Vec<ConfigWarning>),
     Failed(toml::de::Error),
 }
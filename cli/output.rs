@@ -0,0 +1,298 @@
+use crate::OutputFormat;
+use codespan_reporting::{
+    diagnostic::{Diagnostic, Label, LabelStyle, Severity},
+    files::Files,
+};
+use duck::{FileId, GmlLibrary, lint::Report};
+use itertools::Itertools;
+use serde_json::json;
+use std::path::Path;
+
+/// Renders every diagnostic in a machine-readable format.
+pub fn render(format: OutputFormat, reports: &[Report], library: &GmlLibrary, project_directory: &Path) -> String {
+    let records: Vec<Record> = reports
+        .iter()
+        .map(|report| Record::new(&report.diagnostic, library, project_directory))
+        .collect();
+    match format {
+        OutputFormat::Human => unreachable!("human output is rendered by codespan"),
+        OutputFormat::Json => serde_json::to_string_pretty(&records).unwrap(),
+        OutputFormat::Sarif => sarif(&records),
+        OutputFormat::Checkstyle => checkstyle(&records),
+        OutputFormat::Junit => junit(&records),
+        OutputFormat::Github => github(&records),
+    }
+}
+
+/// A diagnostic resolved against the files of the project.
+#[derive(Debug, serde::Serialize)]
+struct Record {
+    /// The tag of the lint that created the diagnostic, or `parse_error`.
+    lint: String,
+    severity: &'static str,
+    message: String,
+    file: String,
+    start: Position,
+    end: Position,
+    labels: Vec<LabelRecord>,
+    notes: Vec<String>,
+}
+impl Record {
+    fn new(diagnostic: &Diagnostic<FileId>, library: &GmlLibrary, project_directory: &Path) -> Self {
+        let labels: Vec<LabelRecord> = diagnostic
+            .labels
+            .iter()
+            .map(|label| LabelRecord::new(label, library, project_directory))
+            .collect();
+        let primary = labels
+            .iter()
+            .find(|label| label.style == "primary")
+            .or_else(|| labels.first());
+        Self {
+            // Lints always tag their diagnostics, so the only ones without a code are from parsing
+            lint: diagnostic.code.clone().unwrap_or_else(|| "parse_error".into()),
+            severity: match diagnostic.severity {
+                Severity::Bug | Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Note => "note",
+                Severity::Help => "help",
+            },
+            message: diagnostic.message.clone(),
+            file: primary.map(|label| label.file.clone()).unwrap_or_default(),
+            start: primary.map(|label| label.start).unwrap_or_default(),
+            end: primary.map(|label| label.end).unwrap_or_default(),
+            notes: diagnostic.notes.clone(),
+            labels,
+        }
+    }
+
+    /// The message of the diagnostic, followed by the message of its primary label.
+    fn full_message(&self) -> String {
+        match self.labels.iter().find(|label| label.style == "primary") {
+            Some(label) if !label.message.is_empty() => format!("{}: {}", self.message, label.message),
+            _ => self.message.clone(),
+        }
+    }
+}
+
+#[derive(Debug, serde::Serialize)]
+struct LabelRecord {
+    style: &'static str,
+    message: String,
+    file: String,
+    start: Position,
+    end: Position,
+}
+impl LabelRecord {
+    fn new(label: &Label<FileId>, library: &GmlLibrary, project_directory: &Path) -> Self {
+        let file = library.name(label.file_id).unwrap_or_default();
+        let file = Path::new(&file)
+            .strip_prefix(project_directory)
+            .map_or(file.clone(), |path| path.to_string_lossy().replace('\\', "/"));
+        Self {
+            style: match label.style {
+                LabelStyle::Primary => "primary",
+                LabelStyle::Secondary => "secondary",
+            },
+            message: label.message.clone(),
+            file,
+            start: Position::new(library, label.file_id, label.range.start),
+            end: Position::new(library, label.file_id, label.range.end),
+        }
+    }
+}
+
+/// A one-based line and column within a file, alongside the byte offset it came from.
+#[derive(Debug, Default, Copy, Clone, serde::Serialize)]
+struct Position {
+    line: usize,
+    column: usize,
+    offset: usize,
+}
+impl Position {
+    fn new(library: &GmlLibrary, file_id: FileId, offset: usize) -> Self {
+        library.location(file_id, offset).map_or(
+            Self {
+                line: 1,
+                column: 1,
+                offset,
+            },
+            |location| Self {
+                line: location.line_number,
+                column: location.column_number,
+                offset,
+            },
+        )
+    }
+}
+
+/// Renders the diagnostics as a SARIF 2.1.0 log, which code scanning dashboards accept.
+fn sarif(records: &[Record]) -> String {
+    let rules: Vec<_> = records
+        .iter()
+        .map(|record| record.lint.as_str())
+        .unique()
+        .sorted()
+        .map(|lint| json!({ "id": lint, "helpUri": "https://github.com/imlazyeye/duck/blob/main/LINTS.md" }))
+        .collect();
+    let region = |start: &Position, end: &Position| {
+        json!({
+            "startLine": start.line,
+            "startColumn": start.column,
+            "endLine": end.line,
+            "endColumn": end.column,
+        })
+    };
+    let results: Vec<_> = records
+        .iter()
+        .map(|record| {
+            let related_locations: Vec<_> = record
+                .labels
+                .iter()
+                .filter(|label| label.style == "secondary")
+                .map(|label| {
+                    json!({
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": label.file },
+                            "region": region(&label.start, &label.end),
+                        },
+                        "message": { "text": label.message },
+                    })
+                })
+                .collect();
+            json!({
+                "ruleId": record.lint,
+                "level": match record.severity {
+                    "error" => "error",
+                    "warning" => "warning",
+                    _ => "note",
+                },
+                "message": { "text": record.full_message() },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": record.file },
+                        "region": region(&record.start, &record.end),
+                    }
+                }],
+                "relatedLocations": related_locations,
+            })
+        })
+        .collect();
+    serde_json::to_string_pretty(&json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "duck",
+                    "informationUri": "https://github.com/imlazyeye/duck",
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    }))
+    .unwrap()
+}
+
+/// Renders the diagnostics as a checkstyle report, grouped by file.
+fn checkstyle(records: &[Record]) -> String {
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<checkstyle version=\"4.3\">\n");
+    for (file, records) in &records
+        .iter()
+        .sorted_by(|a, b| a.file.cmp(&b.file))
+        .group_by(|record| &record.file)
+    {
+        output += &format!("  <file name=\"{}\">\n", escape_xml(file));
+        for record in records {
+            output += &format!(
+                "    <error line=\"{}\" column=\"{}\" severity=\"{}\" message=\"{}\" source=\"duck.{}\"/>\n",
+                record.start.line,
+                record.start.column,
+                if record.severity == "error" { "error" } else { "warning" },
+                escape_xml(&record.full_message()),
+                escape_xml(&record.lint),
+            );
+        }
+        output += "  </file>\n";
+    }
+    output += "</checkstyle>\n";
+    output
+}
+
+/// Renders the diagnostics as a JUnit report, where each file is a test suite and each diagnostic
+/// is a failed test case.
+fn junit(records: &[Record]) -> String {
+    let mut output = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"duck\" tests=\"{0}\" failures=\"{0}\">\n",
+        records.len()
+    );
+    for (file, records) in &records
+        .iter()
+        .sorted_by(|a, b| a.file.cmp(&b.file))
+        .group_by(|record| &record.file)
+    {
+        let records: Vec<&Record> = records.collect();
+        output += &format!(
+            "  <testsuite name=\"{}\" tests=\"{1}\" failures=\"{1}\">\n",
+            escape_xml(file),
+            records.len()
+        );
+        for record in records {
+            output += &format!(
+                "    <testcase name=\"{}\" classname=\"{}\">\n      <failure type=\"{}\" message=\"{}\">{}:{}:{}: {}</failure>\n    </testcase>\n",
+                escape_xml(&format!(
+                    "{} ({}:{})",
+                    record.lint, record.start.line, record.start.column
+                )),
+                escape_xml(file),
+                record.severity,
+                escape_xml(&record.message),
+                escape_xml(file),
+                record.start.line,
+                record.start.column,
+                escape_xml(&record.full_message()),
+            );
+        }
+        output += "  </testsuite>\n";
+    }
+    output += "</testsuites>\n";
+    output
+}
+
+/// Renders the diagnostics as GitHub Actions workflow commands, which annotate pull requests.
+fn github(records: &[Record]) -> String {
+    records
+        .iter()
+        .map(|record| {
+            format!(
+                "::{} file={},line={},col={},endLine={},endColumn={},title={}::{}\n",
+                if record.severity == "error" { "error" } else { "warning" },
+                escape_github_property(&record.file),
+                record.start.line,
+                record.start.column,
+                record.end.line,
+                record.end.column,
+                escape_github_property(&record.lint),
+                escape_github_data(&record.full_message()),
+            )
+        })
+        .collect()
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+        .replace('\n', "&#10;")
+}
+
+fn escape_github_data(text: &str) -> String {
+    text.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+fn escape_github_property(text: &str) -> String {
+    escape_github_data(text).replace(':', "%3A").replace(',', "%2C")
+}
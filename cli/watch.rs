@@ -0,0 +1,130 @@
+use crate::{ConfigUsage, OutputFormat, RunOptions, create_duck, report};
+use colored::Colorize;
+use duck::Workspace;
+use hashbrown::HashSet;
+use notify::{EventHandler, PollWatcher, RecommendedWatcher, RecursiveMode, Watcher};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tokio::sync::mpsc::{UnboundedReceiver, unbounded_channel};
+
+/// How long to wait for further changes after the first one, as editors often write a file (or
+/// several) in more than one step.
+const SETTLE_DURATION: Duration = Duration::from_millis(100);
+
+/// Lints the project, and then lints it again every time one of its files changes, printing a
+/// fresh report each time. Only the files that changed are parsed and linted again, unless the
+/// config itself changes.
+pub async fn watch(options: &RunOptions, current_directory: &Path, selected_files: Option<&HashSet<PathBuf>>) -> i32 {
+    let project_directory = current_directory
+        .canonicalize()
+        .unwrap_or_else(|_| current_directory.to_path_buf());
+    let (path_sender, mut path_receiver) = unbounded_channel();
+    let handler = move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event {
+            event.paths.into_iter().for_each(|path| {
+                let _ = path_sender.send(path);
+            });
+        }
+    };
+    let _watcher = match start_watcher(&project_directory, handler) {
+        Ok(watcher) => watcher,
+        Err(error) => {
+            eprintln!(
+                "{}: Failed to watch {}: {error}",
+                "error".bright_red().bold(),
+                project_directory.display()
+            );
+            return 1;
+        }
+    };
+
+    let (mut workspace, mut config_usage) = create_workspace(options, &project_directory);
+    let mut changed_paths = vec![];
+    loop {
+        let timer = std::time::Instant::now();
+        let mut run_summary = workspace.run(&changed_paths).await.unwrap();
+        if options.format == OutputFormat::Human {
+            // Clears the terminal, so that only the latest report is visible
+            print!("\x1B[2J\x1B[1;1H");
+            let _ = std::io::stdout().flush();
+        }
+        report(
+            options,
+            &project_directory,
+            &mut run_summary,
+            selected_files,
+            &config_usage,
+            timer.elapsed(),
+        );
+        eprintln!("{}", "Watching for changes...".bright_black().italic());
+
+        changed_paths = match next_changes(&mut path_receiver).await {
+            Some(changed_paths) => changed_paths,
+            None => return 0,
+        };
+        if changed_paths.iter().any(|path| path.ends_with(".duck.toml")) {
+            (workspace, config_usage) = create_workspace(options, &project_directory);
+        }
+    }
+}
+
+/// Starts watching the project's directory for changes, preferring the platform's file system
+/// notifications but falling back to polling where they are not available (such as on some network
+/// drives).
+fn start_watcher(directory: &Path, handler: impl EventHandler + Clone) -> notify::Result<Box<dyn Watcher>> {
+    let native = RecommendedWatcher::new(handler.clone(), notify::Config::default()).and_then(|mut watcher| {
+        watcher.watch(directory, RecursiveMode::Recursive)?;
+        Ok(watcher)
+    });
+    match native {
+        Ok(watcher) => Ok(Box::new(watcher)),
+        Err(_) => {
+            let config = notify::Config::default().with_poll_interval(Duration::from_secs(1));
+            let mut watcher = PollWatcher::new(handler, config)?;
+            watcher.watch(directory, RecursiveMode::Recursive)?;
+            Ok(Box::new(watcher))
+        }
+    }
+}
+
+/// Creates a workspace using the project's current config.
+fn create_workspace(options: &RunOptions, project_directory: &Path) -> (Workspace, ConfigUsage) {
+    let (mut duck, config_usage) = create_duck(project_directory);
+    duck.config_mut()
+        .ignored_file_paths
+        .extend(options.ignored_file_paths.iter().cloned());
+    let workspace = Workspace::new(project_directory, duck.config().clone(), options.cache);
+    (workspace, config_usage)
+}
+
+/// Waits for a change to one of the files that affects a run, returning every path that changed
+/// before things settled. Returns None if the watcher has stopped.
+async fn next_changes(path_receiver: &mut UnboundedReceiver<PathBuf>) -> Option<Vec<PathBuf>> {
+    let mut changed_paths = vec![];
+    while changed_paths.is_empty() {
+        let path = path_receiver.recv().await?;
+        if is_relevant(&path) {
+            changed_paths.push(path);
+        }
+    }
+    while let Ok(Some(path)) = tokio::time::timeout(SETTLE_DURATION, path_receiver.recv()).await {
+        if is_relevant(&path) {
+            changed_paths.push(path);
+        }
+    }
+    Some(changed_paths)
+}
+
+/// Returns whether a change to the path could change the results of a run. Besides gml files,
+/// this includes the config and the files that decide which gml belongs to the project.
+fn is_relevant(path: &Path) -> bool {
+    let file_name = path.file_name().unwrap_or_default();
+    file_name == ".duck.toml"
+        || file_name == ".duckignore"
+        || path
+            .extension()
+            .is_some_and(|extension| extension == "gml" || extension == "yy" || extension == "yyp")
+}
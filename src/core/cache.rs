@@ -0,0 +1,303 @@
+use crate::{
+    Config, FileId, SymbolKind,
+    driver::{self, Ctx, TagScopes},
+    lint::Report,
+    parse::Ast,
+};
+use hashbrown::{HashMap, HashSet};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tokio::{
+    sync::mpsc::{Receiver, channel},
+    task::JoinError,
+};
+
+/// The results of a previous run, stored in a project's `.duck/cache` so that files which have not
+/// changed since are not parsed and linted again.
+///
+/// Each file's diagnostics are stored alongside a hash of its contents and the [Ctx] it
+/// contributed. The diagnostics of the early pass only depend on the file itself, so they are
+/// reused whenever it is unchanged. The late pass also relies on the names declared and referenced
+/// throughout the project, so when those change, the late pass is run again over every file. A
+/// file's late pass is also run again when one of its diagnostics points into a file that changed.
+/// Changing the config or the version of duck invalidates the entire cache.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct Cache {
+    version: String,
+    config_hash: u64,
+    ctx_hash: u64,
+    files: HashMap<String, CachedFile>,
+}
+
+/// The results of linting a single file.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct CachedFile {
+    hash: u64,
+    /// The id the file had when it was linted, which diagnostics refer to it by.
+    file_id: FileId,
+    ctx: Ctx,
+    /// The file's parse errors, along with the diagnostics of the early pass.
+    early_diagnostics: Vec<Report>,
+    /// The tags that suppressed a diagnostic during the early pass. See [TagScopes::used].
+    used_tags: Vec<usize>,
+    late_diagnostics: Vec<Report>,
+}
+
+impl Cache {
+    /// Creates an empty cache for runs using the given config.
+    pub fn new(config: &Config) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            config_hash: config_hash(config),
+            ..Default::default()
+        }
+    }
+
+    /// Loads the cache within the project's directory. Returns an empty cache if there is none, if
+    /// it cannot be read, or if it was created by a different version of duck or with a different
+    /// config.
+    pub fn load(project_directory: &Path, config: &Config) -> Self {
+        let empty = Self::new(config);
+        match std::fs::read_to_string(Self::path(project_directory))
+            .ok()
+            .and_then(|text| serde_json::from_str::<Cache>(&text).ok())
+        {
+            Some(cache) if cache.version == empty.version && cache.config_hash == empty.config_hash => cache,
+            _ => empty,
+        }
+    }
+
+    /// Writes the cache into the project's directory.
+    ///
+    /// ### Errors
+    /// Returns an error if the cache's directory or file cannot be written.
+    pub fn save(&self, project_directory: &Path) -> Result<(), std::io::Error> {
+        let path = Self::path(project_directory);
+        let duck_directory = path.parent().unwrap();
+        std::fs::create_dir_all(duck_directory)?;
+
+        // Nothing in here belongs in version control
+        let gitignore = duck_directory.join(".gitignore");
+        if !gitignore.exists() {
+            std::fs::write(gitignore, "*\n")?;
+        }
+        std::fs::write(path, serde_json::to_string(self).unwrap())
+    }
+
+    /// Returns the path to the cache within a project's directory.
+    pub fn path(project_directory: &Path) -> PathBuf {
+        project_directory.join(".duck").join("cache")
+    }
+
+    /// Parses and lints every file that has changed since the cache was last updated by running
+    /// them through the [driver]'s passes, reusing the diagnostics of the rest. Files that are no
+    /// longer in the project are removed from the cache. Returns the diagnostics of every file, in
+    /// the order the files were provided.
+    ///
+    /// ### Errors
+    /// Returns an error if we fail to join any of the tokio tasks.
+    pub async fn update(
+        &mut self,
+        files: Vec<(FileId, &'static str, PathBuf)>,
+        config: Arc<Config>,
+        project_directory: &Path,
+    ) -> Result<Vec<Report>, JoinError> {
+        // Reuse what we can, and set everything else aside for the early pass
+        let mut previous_files = std::mem::take(&mut self.files);
+        let mut file_ids = HashMap::new();
+        let mut keys = vec![];
+        let mut cached_files = HashMap::new();
+        let mut file_ctxs = HashMap::new();
+        let mut unchanged_files = vec![];
+        let mut changed_files = vec![];
+        for (file_id, gml, path) in files {
+            let key = path
+                .strip_prefix(project_directory)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            // File ids are assigned as files are loaded, so they can differ between runs
+            let previous_file = previous_files.remove(&key);
+            if let Some(previous_file) = &previous_file {
+                file_ids.insert(previous_file.file_id, file_id);
+            }
+            let hash = fnv1a(gml.as_bytes());
+            match previous_file.filter(|file| file.hash == hash) {
+                Some(mut file) => {
+                    file.ctx.set_file_id(file_id);
+                    file_ctxs.insert(file_id, file.ctx.clone());
+                    cached_files.insert(file_id, file);
+                    unchanged_files.push((file_id, gml, path));
+                }
+                None => changed_files.push((file_id, gml, path)),
+            }
+            keys.push((key, file_id, hash));
+        }
+        let changed_file_ids: HashSet<FileId> = changed_files.iter().map(|(file_id, ..)| *file_id).collect();
+
+        // Run the files that changed through the early pass, holding onto what each contributes
+        let (ast_receiver, parse_handle) = driver::start_parse(send_all(changed_files));
+        let (mut late_receiver, _, mut report_receiver, mut ctx_receiver, _) =
+            driver::start_early_pass(config.clone(), project_directory, ast_receiver);
+        // The early pass only collects the Ctx of the files that changed, so the project's Ctx is
+        // built from each file's below
+        ctx_receiver.recv().await;
+        let mut late_files = vec![];
+        let mut used_tags = HashMap::new();
+        while let Some((ast, file_config, scopes)) = late_receiver.recv().await {
+            file_ctxs.insert(ast.file_id(), collect_ctx(&ast));
+            used_tags.insert(ast.file_id(), scopes.used());
+            late_files.push((ast, file_config, scopes));
+        }
+        let mut early_diagnostics = group_by_file(parse_handle.await?);
+        while let Some(reports) = report_receiver.recv().await {
+            for (file_id, mut reports) in group_by_file(reports) {
+                early_diagnostics.entry(file_id).or_default().append(&mut reports);
+            }
+        }
+
+        // If the names declared or referenced throughout the project have changed, the late pass
+        // needs to see every file again. Otherwise, it only needs to see the files whose
+        // diagnostics point into a file that changed. Files are added to the Ctx in the order of
+        // their paths, so that its hash does not depend on the order they were found in.
+        let mut ctx = Ctx::default();
+        let mut sorted_keys: Vec<&(String, FileId, u64)> = keys.iter().collect();
+        sorted_keys.sort();
+        for (_, file_id, _) in sorted_keys {
+            if let Some(file_ctx) = file_ctxs.get(file_id) {
+                ctx.extend(file_ctx.clone());
+            }
+        }
+        ctx.collect_assets(project_directory);
+        let ctx_hash = ctx_hash(&ctx);
+        let ctx_changed = ctx_hash != self.ctx_hash;
+        self.ctx_hash = ctx_hash;
+        let relinted_files: Vec<_> = unchanged_files
+            .into_iter()
+            .filter(|(file_id, ..)| {
+                let file = &cached_files[file_id];
+                ctx_changed
+                    || file
+                        .late_diagnostics
+                        .iter()
+                        .flat_map(|report| report.diagnostic.labels.iter())
+                        .any(|label| {
+                            label.file_id != file.file_id
+                                && file_ids
+                                    .get(&label.file_id)
+                                    .is_none_or(|file_id| changed_file_ids.contains(file_id))
+                        })
+            })
+            .collect();
+        let relinted_file_ids: HashSet<FileId> = relinted_files.iter().map(|(file_id, ..)| *file_id).collect();
+
+        // Files that did not change pick the late pass up from where their cached early pass left
+        // off. Their parse errors were cached alongside the early pass, so they are not needed.
+        let (mut ast_receiver, parse_handle) = driver::start_parse(send_all(relinted_files));
+        while let Some(ast) = ast_receiver.recv().await {
+            let scopes = TagScopes::new(&ast);
+            scopes.restore_used(&cached_files[&ast.file_id()].used_tags);
+            let file_config = driver::file_config(&config, project_directory, &ast);
+            late_files.push((ast, file_config, scopes));
+        }
+        parse_handle.await?;
+        let (report_sender, report_receiver) = channel::<Vec<Report>>(1000);
+        let mut late_diagnostics =
+            group_by_file(driver::start_late_pass(ctx, send_all(late_files), report_sender, report_receiver).await?);
+
+        let mut diagnostics = vec![];
+        for (key, file_id, hash) in keys {
+            let file = match cached_files.remove(&file_id) {
+                Some(mut file) => {
+                    remap_file_ids(&mut file.early_diagnostics, &file_ids, file_id);
+                    if relinted_file_ids.contains(&file_id) {
+                        file.late_diagnostics = late_diagnostics.remove(&file_id).unwrap_or_default();
+                    } else {
+                        remap_file_ids(&mut file.late_diagnostics, &file_ids, file_id);
+                    }
+                    file.file_id = file_id;
+                    file
+                }
+                None => CachedFile {
+                    hash,
+                    file_id,
+                    ctx: file_ctxs.remove(&file_id).unwrap_or_default(),
+                    early_diagnostics: early_diagnostics.remove(&file_id).unwrap_or_default(),
+                    used_tags: used_tags.remove(&file_id).unwrap_or_default(),
+                    late_diagnostics: late_diagnostics.remove(&file_id).unwrap_or_default(),
+                },
+            };
+            diagnostics.extend(file.early_diagnostics.iter().cloned());
+            diagnostics.extend(file.late_diagnostics.iter().cloned());
+            self.files.insert(key, file);
+        }
+        Ok(diagnostics)
+    }
+}
+
+/// Sends each item through a new channel, as the [driver]'s passes expect.
+fn send_all<T: Send + 'static>(items: Vec<T>) -> Receiver<T> {
+    let (sender, receiver) = channel::<T>(1000);
+    tokio::task::spawn(async move {
+        for item in items {
+            sender.send(item).await.unwrap();
+        }
+    });
+    receiver
+}
+
+/// Sorts reports by the file they were found in, which is the file of their first label.
+fn group_by_file(reports: Vec<Report>) -> HashMap<FileId, Vec<Report>> {
+    let mut files: HashMap<FileId, Vec<Report>> = HashMap::new();
+    for report in reports {
+        if let Some(file_id) = report.diagnostic.labels.first().map(|label| label.file_id) {
+            files.entry(file_id).or_default().push(report);
+        }
+    }
+    files
+}
+
+/// Moves reports from a previous run over to the ids their files have in this one. Labels that
+/// point into files which are no longer part of the project are moved to the file itself.
+fn remap_file_ids(reports: &mut [Report], file_ids: &HashMap<FileId, FileId>, file_id: FileId) {
+    for report in reports {
+        for label in report.diagnostic.labels.iter_mut() {
+            label.file_id = file_ids.get(&label.file_id).copied().unwrap_or(file_id);
+        }
+        for suggestion in report.suggestions.iter_mut() {
+            suggestion.file_id = file_ids.get(&suggestion.file_id).copied().unwrap_or(file_id);
+        }
+    }
+}
+
+/// Returns the declarations a file contributes to the [Ctx].
+fn collect_ctx(ast: &Ast) -> Ctx {
+    let mut ctx = Ctx::default();
+    ctx.collect_ast(ast);
+    ctx
+}
+
+/// Returns a hash of the names within the project's [Ctx], which the late pass relies on. Where
+/// each symbol was declared is left out, so that edits which only move declarations around do not
+/// cause every file to be linted again.
+fn ctx_hash(ctx: &Ctx) -> u64 {
+    let symbols: Vec<(&String, &SymbolKind)> = ctx.symbols.iter().map(|symbol| (&symbol.name, &symbol.kind)).collect();
+    let names = (symbols, &ctx.assets, &ctx.instance_variables, &ctx.references);
+    fnv1a(&serde_json::to_vec(&names).unwrap())
+}
+
+/// Returns a hash of the config, which invalidates the cache when it changes.
+fn config_hash(config: &Config) -> u64 {
+    // Unlike json, toml sorts the config's maps, so equal configs always hash the same
+    toml::Value::try_from(config).map_or(0, |value| fnv1a(value.to_string().as_bytes()))
+}
+
+/// A 64-bit FNV-1a hash, which (unlike the standard library's hasher) is stable across versions of
+/// Rust, and so can be stored in files.
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
@@ -1,7 +1,11 @@
-use crate::lint::{LintLevel, LintLevelSetting};
+use crate::{
+    Glob,
+    lint::{LintLevel, LintLevelSetting},
+};
 use hashbrown::HashMap;
 use heck::{ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
 use itertools::Itertools;
+use std::path::Path;
 
 /// A series of various settings shared by the lints to customize their
 /// behavior.
@@ -54,12 +58,14 @@ pub struct Config {
     /// math.
     #[serde(default)]
     pub simplification_rules: SimplificationRules,
-    /// Manual definitions for any lint's lint level. The key is the lint's tag.
-    ///
-    /// FIXME: We do not currently validate that all entries are valid lint
-    /// tags!
+    /// The preferences used by `duck fmt` when formatting code. The formatter additionally
+    /// respects `statement_parentheticals` and the keyword preferences above.
+    #[serde(default)]
+    pub formatting_rules: FormattingRules,
+    /// Manual definitions for any lint's lint level. The key is the lint's tag. Unknown tags are
+    /// reported as a [ConfigWarning] by [Config::from_toml].
     ///
-    /// Additionally, in the future, we *could* default this field to the actual default
+    /// In the future, we *could* default this field to the actual default
     /// levels of the lint, and then we'd never have to fall back to calling
     /// Lint::default_level()...
     #[serde(default)]
@@ -67,6 +73,10 @@ pub struct Config {
     /// File paths that duck should ignore.
     #[serde(default)]
     pub ignored_file_paths: Vec<String>,
+    /// Settings that only apply to some of the project's files, such as vendored libraries. Each
+    /// override replaces the settings above for the files matching its globs.
+    #[serde(default)]
+    pub overrides: Vec<ConfigOverride>,
 }
 impl Default for Config {
     fn default() -> Self {
@@ -84,7 +94,9 @@ impl Default for Config {
             casing_rules: Default::default(),
             lint_levels: Default::default(),
             simplification_rules: Default::default(),
+            formatting_rules: Default::default(),
             ignored_file_paths: Default::default(),
+            overrides: Default::default(),
         }
     }
 }
@@ -102,8 +114,10 @@ impl Config {
                 ("collapsable_if".into(), LintLevel::Warn),
                 ("condition_wrapper".into(), LintLevel::Allow),
                 ("deprecated".into(), LintLevel::Warn),
+                ("draw_outside_draw_event".into(), LintLevel::Warn),
                 ("draw_sprite".into(), LintLevel::Allow),
                 ("draw_text".into(), LintLevel::Allow),
+                ("duplicate_declaration".into(), LintLevel::Deny),
                 ("english_flavor_violation".into(), LintLevel::Allow),
                 ("exit".into(), LintLevel::Allow),
                 ("fucntion_name_as_parameter".into(), LintLevel::Deny),
@@ -130,7 +144,10 @@ impl Config {
                 ("too_many_arguments".into(), LintLevel::Warn),
                 ("try_catch".into(), LintLevel::Allow),
                 ("unassigned_constructor".into(), LintLevel::Warn),
+                ("undefined_identifier".into(), LintLevel::Allow),
                 ("unnecessary_grouping".into(), LintLevel::Warn),
+                ("unused_allow".into(), LintLevel::Warn),
+                ("unused_function".into(), LintLevel::Allow),
                 ("unused_local_variable".into(), LintLevel::Warn),
                 ("unused_parameter".into(), LintLevel::Warn),
                 ("useless_function".into(), LintLevel::Deny),
@@ -141,6 +158,63 @@ impl Config {
             ..Default::default()
         }
     }
+    /// Parses a config from the contents of a `.duck.toml` file. Rather than failing the entire
+    /// config, unknown fields, unknown lint tags and invalid values are skipped and returned as
+    /// warnings.
+    ///
+    /// ### Errors
+    /// Returns an error if the text is not valid toml.
+    pub fn from_toml(text: &str) -> Result<(Self, Vec<ConfigWarning>), toml::de::Error> {
+        let mut table: toml::value::Table = toml::from_str(text)?;
+        let mut warnings = vec![];
+        let overrides = table.remove("overrides");
+        let mut table = validate_table(table, "", &mut warnings);
+
+        // Each override is checked just like the rest of the config, apart from its globs
+        if let Some(overrides) = overrides {
+            let overrides = match overrides {
+                toml::Value::Array(overrides) => overrides
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(|(i, value)| validate_override(value, &format!("overrides[{i}]"), &mut warnings))
+                    .collect(),
+                _ => {
+                    warnings.push(ConfigWarning::InvalidValue {
+                        field: "overrides".into(),
+                        message: "expected an array of tables".into(),
+                    });
+                    vec![]
+                }
+            };
+            table.insert("overrides".into(), toml::Value::Array(overrides));
+        }
+        let config = toml::Value::Table(table).try_into()?;
+        Ok((config, warnings))
+    }
+
+    /// Returns the config that applies to a file, with the settings of every override matching its
+    /// path (relative to the project's directory) applied in order. Returns `None` if no overrides
+    /// apply to the file.
+    pub fn for_file(&self, relative_path: &Path) -> Option<Config> {
+        let mut matching_overrides = self
+            .overrides
+            .iter()
+            .filter(|config_override| config_override.matches(relative_path))
+            .peekable();
+        matching_overrides.peek()?;
+        let base = Self {
+            overrides: vec![],
+            ..self.clone()
+        };
+        let toml::Value::Table(mut table) = toml::Value::try_from(base).unwrap() else {
+            unreachable!("configs always serialize into a table")
+        };
+        for config_override in matching_overrides {
+            merge_tables(&mut table, &config_override.settings);
+        }
+        toml::Value::Table(table).try_into().ok()
+    }
+
     /// Gets the level needed for a lint based on its tag, taking a default
     /// if the user hasn't specifide anything.
     pub fn get_lint_level_setting(&self, tag: &str, default: LintLevel) -> LintLevelSetting {
@@ -173,6 +247,223 @@ impl Config {
     }
 }
 
+/// A problem found in a config that did not prevent it from being used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigWarning {
+    /// A field that duck does not recognize, such as a misspelled setting.
+    UnknownField {
+        /// The path to the field, such as `formatting_rules.indent_size`.
+        field: String,
+        /// The name of a known field that is similar, if any.
+        suggestion: Option<String>,
+    },
+    /// An entry in `lint_levels` that does not match any lint's tag.
+    UnknownLint {
+        /// The tag as written in the config.
+        tag: String,
+        /// The tag of a known lint that is similar, if any.
+        suggestion: Option<String>,
+    },
+    /// A field whose value could not be parsed, which was replaced by its default.
+    InvalidValue {
+        /// The path to the field, such as `lint_levels.todo`.
+        field: String,
+        /// The reason the value could not be parsed.
+        message: String,
+    },
+    /// A field that only applies to the entire project, written within an override.
+    ProjectFieldInOverride {
+        /// The path to the field, such as `overrides[0].ignored_file_paths`.
+        field: String,
+    },
+}
+impl std::fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigWarning::UnknownField { field, suggestion } => {
+                write!(f, "unknown field `{field}`")?;
+                if let Some(suggestion) = suggestion {
+                    write!(f, " (did you mean `{suggestion}`?)")?;
+                }
+                Ok(())
+            }
+            ConfigWarning::UnknownLint { tag, suggestion } => {
+                write!(f, "unknown lint `{tag}` in `lint_levels`")?;
+                if let Some(suggestion) = suggestion {
+                    write!(f, " (did you mean `{suggestion}`?)")?;
+                }
+                Ok(())
+            }
+            ConfigWarning::InvalidValue { field, message } => {
+                write!(
+                    f,
+                    "invalid value for `{field}`, so its default was used instead: {message}"
+                )
+            }
+            ConfigWarning::ProjectFieldInOverride { field } => {
+                write!(
+                    f,
+                    "`{field}` has no effect within an override, as it applies to the entire project"
+                )
+            }
+        }
+    }
+}
+
+/// Settings that replace those in the rest of the config for a subset of the project's files.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ConfigOverride {
+    /// Globs matched against paths relative to the project's directory, such as `scripts/lib_*`.
+    pub files: Vec<String>,
+    /// The settings to apply, written just like the rest of the config.
+    #[serde(flatten)]
+    pub settings: toml::value::Table,
+}
+impl ConfigOverride {
+    /// Returns whether the override applies to the file at the given path, relative to the
+    /// project's directory.
+    pub fn matches(&self, relative_path: &Path) -> bool {
+        self.files
+            .iter()
+            .filter_map(|pattern| Glob::new(pattern).ok())
+            .any(|glob| glob.is_match(relative_path))
+    }
+}
+
+/// Validates the fields of a config (or an override within one), returning only the ones that can
+/// be used and recording a warning for everything else.
+fn validate_table(mut table: toml::value::Table, path: &str, warnings: &mut Vec<ConfigWarning>) -> toml::value::Table {
+    let known_lints: Vec<String> = Config::full().lint_levels.into_keys().collect();
+    let toml::Value::Table(known_fields) = toml::Value::try_from(Config::full()).unwrap() else {
+        unreachable!("configs always serialize into a table")
+    };
+
+    // Lint levels are checked one at a time, so a single typo does not discard the rest
+    if let Some(toml::Value::Table(lint_levels)) = table.get_mut("lint_levels") {
+        *lint_levels = std::mem::take(lint_levels)
+            .into_iter()
+            .filter(|(tag, level)| {
+                if !known_lints.contains(tag) {
+                    warnings.push(ConfigWarning::UnknownLint {
+                        tag: tag.clone(),
+                        suggestion: did_you_mean(tag, known_lints.iter()),
+                    });
+                    false
+                } else if let Err(error) = level.clone().try_into::<LintLevel>() {
+                    warnings.push(ConfigWarning::InvalidValue {
+                        field: format!("{path}lint_levels.{tag}"),
+                        message: error.to_string(),
+                    });
+                    false
+                } else {
+                    true
+                }
+            })
+            .collect();
+    }
+    remove_unknown_fields(&mut table, &known_fields, path, warnings);
+
+    // Any field that fails to parse on its own falls back to its default value
+    table
+        .into_iter()
+        .filter(|(field, value)| {
+            let single_field = toml::Value::Table(toml::value::Table::from_iter([(field.clone(), value.clone())]));
+            match single_field.try_into::<Config>() {
+                Ok(_) => true,
+                Err(error) => {
+                    warnings.push(ConfigWarning::InvalidValue {
+                        field: format!("{path}{field}"),
+                        message: error.to_string(),
+                    });
+                    false
+                }
+            }
+        })
+        .collect()
+}
+
+/// Validates a single entry of `overrides`, returning `None` if its globs are unusable.
+fn validate_override(value: toml::Value, path: &str, warnings: &mut Vec<ConfigWarning>) -> Option<toml::Value> {
+    let mut invalid = |message: String| {
+        warnings.push(ConfigWarning::InvalidValue {
+            field: path.into(),
+            message,
+        });
+        None
+    };
+    let toml::Value::Table(mut table) = value else {
+        return invalid("expected a table".into());
+    };
+    let files = match table.remove("files").map(|files| files.try_into::<Vec<String>>()) {
+        Some(Ok(files)) => files,
+        Some(Err(error)) => return invalid(format!("`files` must be a list of globs: {error}")),
+        None => return invalid("missing the `files` it applies to".into()),
+    };
+    if let Some(error) = files.iter().find_map(|pattern| Glob::new(pattern).err()) {
+        return invalid(format!("invalid glob in `files`: {error}"));
+    }
+    for field in ["overrides", "ignored_file_paths"] {
+        if table.remove(field).is_some() {
+            warnings.push(ConfigWarning::ProjectFieldInOverride {
+                field: format!("{path}.{field}"),
+            });
+        }
+    }
+    let mut table = validate_table(table, &format!("{path}."), warnings);
+    table.insert("files".into(), toml::Value::try_from(files).unwrap());
+    Some(toml::Value::Table(table))
+}
+
+/// Writes every value in `settings` into `table`, merging the tables found in both.
+fn merge_tables(table: &mut toml::value::Table, settings: &toml::value::Table) {
+    for (key, value) in settings {
+        match (table.get_mut(key), value) {
+            (Some(toml::Value::Table(table)), toml::Value::Table(settings)) => merge_tables(table, settings),
+            _ => {
+                table.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// Removes every field in the table (and the tables within it) that is not present in the known
+/// fields, recording a warning for each.
+fn remove_unknown_fields(
+    table: &mut toml::value::Table,
+    known_fields: &toml::value::Table,
+    path: &str,
+    warnings: &mut Vec<ConfigWarning>,
+) {
+    *table = std::mem::take(table)
+        .into_iter()
+        .filter_map(|(field, mut value)| match known_fields.get(&field) {
+            Some(toml::Value::Table(known_fields)) if field != "lint_levels" => {
+                if let toml::Value::Table(table) = &mut value {
+                    remove_unknown_fields(table, known_fields, &format!("{path}{field}."), warnings);
+                }
+                Some((field, value))
+            }
+            Some(_) => Some((field, value)),
+            None => {
+                warnings.push(ConfigWarning::UnknownField {
+                    suggestion: did_you_mean(&field, known_fields.keys()),
+                    field: format!("{path}{field}"),
+                });
+                None
+            }
+        })
+        .collect();
+}
+
+/// Returns the candidate most similar to the name, if any are close enough to be a likely typo.
+fn did_you_mean<'a>(name: &str, candidates: impl Iterator<Item = &'a String>) -> Option<String> {
+    candidates
+        .map(|candidate| (strsim::jaro_winkler(name, candidate), candidate))
+        .filter(|(similarity, _)| *similarity > 0.8)
+        .max_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, candidate)| candidate.clone())
+}
+
 // Default values used by serde. No, I don't love this, I just don't think there's a better
 // way for me to allow all of the config values to be optional to the user, but still always have
 // default values for the lints...
@@ -267,6 +558,50 @@ impl Default for SimplificationRules {
     }
 }
 
+/// Contains preferences for `duck fmt`.
+#[derive(Debug, PartialEq, Copy, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct FormattingRules {
+    /// The number of spaces used for each level of indentation.
+    pub indent_width: usize,
+    /// Whether or not to indent with tabs instead of spaces.
+    pub use_tabs: bool,
+    /// Where opening braces are placed.
+    pub brace_style: BraceStyle,
+    /// Whether or not to place spaces around binary and assignment operators (`a + b` vs `a+b`).
+    pub spaces_around_operators: bool,
+}
+impl FormattingRules {
+    /// Returns the string used for a single level of indentation.
+    pub fn indentation(&self) -> String {
+        if self.use_tabs {
+            "\t".into()
+        } else {
+            " ".repeat(self.indent_width)
+        }
+    }
+}
+impl Default for FormattingRules {
+    fn default() -> Self {
+        Self {
+            indent_width: 4,
+            use_tabs: false,
+            brace_style: BraceStyle::SameLine,
+            spaces_around_operators: true,
+        }
+    }
+}
+
+/// The brace placement options supported by `duck fmt`.
+#[derive(Debug, PartialEq, Copy, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BraceStyle {
+    /// Opening braces stay on the same line as the statement (ie: `if foo {`).
+    SameLine,
+    /// Opening braces are placed on their own line.
+    NextLine,
+}
+
 /// The various casing options supported by duck for the `casing_rules` lint.
 #[derive(Debug, PartialEq, Copy, Clone, enum_map::Enum, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
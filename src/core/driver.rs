@@ -1,14 +1,22 @@
 use crate::{
-    Config, FileId, GmlLibrary,
+    Config, FileId, GmlLibrary, IgnoreList, Symbol, SymbolKind, SymbolTable,
+    core::project,
     lint::{collection::*, *},
-    parse::{Ast, Expr, ExprKind, Function, ParseVisitor, Parser, Stmt, StmtKind},
+    parse::{
+        Access, Assignment, Ast, AstOrigin, Call, Constructor, Expr, ExprKind, Field, Function, Literal, Location,
+        ParseVisitor, Parser, Span, Stmt, StmtKind, Tag,
+    },
 };
 use async_walkdir::{DirEntry, Filtering, WalkDir};
 use codespan_reporting::diagnostic::Diagnostic;
 use futures::StreamExt;
 use std::{
+    collections::BTreeSet,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
 };
 use tokio::{
     sync::mpsc::{Receiver, Sender, channel},
@@ -25,49 +33,92 @@ pub fn parse_gml(source_code: &'static str, file_id: &FileId) -> Result<Ast, Dia
     Parser::new(source_code, *file_id).into_ast()
 }
 
+/// Parses the given String of GML, recovering from any errors along the way.
+/// Returns the (potentially partial) [Ast] alongside every error that was
+/// encountered.
+pub fn parse_gml_partial(source_code: &'static str, file_id: &FileId) -> (Ast, Vec<Diagnostic<FileId>>) {
+    Parser::new(source_code, *file_id).into_partial_ast()
+}
+
+/// Parses the given String of GML, collecting all whitespace, comments, tags and regions onto
+/// the nodes they surround. See [Parser::new_lossless].
+pub fn parse_gml_lossless(source_code: &'static str, file_id: &FileId) -> Result<Ast, Diagnostic<FileId>> {
+    Parser::new_lossless(source_code, *file_id).into_ast()
+}
+
+/// Runs an [Ast] through every pass, the late pass being run with the provided [Ctx]. This is
+/// useful when linting a single file, such as one open in an editor, rather than a project.
+pub fn lint_ast(ast: &Ast, reports: &mut Vec<Report>, config: &Config, ctx: &Ctx) {
+    let scopes = TagScopes::new(ast);
+    process_ast(ast, reports, config, &scopes);
+    for stmt in ast.stmts() {
+        process_stmt_early(stmt, reports, config, &scopes);
+    }
+    process_ast_late(ast, reports, config, ctx, &scopes);
+    scopes.report_unused(config, reports);
+}
+
 /// Runs an [Ast] through its pass, running any lint that implements [AstPass].
 ///
 /// NOTE: This function is largely auto-generated! See `CONTRIBUTING.md` for
 /// more information.
-pub fn process_ast(ast: &Ast, reports: &mut Vec<Diagnostic<FileId>>, config: &Config) {
+pub fn process_ast(ast: &Ast, reports: &mut Vec<Report>, config: &Config, scopes: &TagScopes) {
     // @ast calls. Do not remove this comment!
-    run_lint_on_ast::<UnusedLocalVariable>(ast, config, reports);
+    run_lint_on_ast::<DrawOutsideDrawEvent>(ast, config, scopes, reports);
+    run_lint_on_ast::<UnusedLocalVariable>(ast, config, scopes, reports);
     // @end ast calls. Do not remove this comment!
 }
 
+/// Runs an [Ast] through the late pass, running any lint that implements [LateAstPass] before
+/// running each of its statements through [process_stmt_late].
+///
+/// NOTE: This function is largely auto-generated! See `CONTRIBUTING.md` for
+/// more information.
+pub fn process_ast_late(ast: &Ast, reports: &mut Vec<Report>, config: &Config, ctx: &Ctx, scopes: &TagScopes) {
+    // @late ast calls. Do not remove this comment!
+    run_late_lint_on_ast::<DuplicateDeclaration>(ast, config, ctx, scopes, reports);
+    run_late_lint_on_ast::<UndefinedIdentifier>(ast, config, ctx, scopes, reports);
+    run_late_lint_on_ast::<UnusedFunction>(ast, config, ctx, scopes, reports);
+    // @end late ast calls. Do not remove this comment!
+
+    for stmt in ast.stmts() {
+        process_stmt_late(stmt, reports, config, ctx, scopes);
+    }
+}
+
 /// Runs a [Stmt] through the early pass, running any lint that
 /// implements [EarlyStmtPass], as well as collecting information
 /// into the provided [GlobalScopeBuilder].
 ///
 /// NOTE: This function is largely auto-generated! See `CONTRIBUTING.md` for
 /// more information.
-pub fn process_stmt_early(stmt: &Stmt, reports: &mut Vec<Diagnostic<FileId>>, config: &Config) {
+pub fn process_stmt_early(stmt: &Stmt, reports: &mut Vec<Report>, config: &Config, scopes: &TagScopes) {
     // @early stmt calls. Do not remove this comment!
-    run_early_lint_on_stmt::<CasingRules>(stmt, config, reports);
-    run_early_lint_on_stmt::<CollapsableIf>(stmt, config, reports);
-    run_early_lint_on_stmt::<ConditionWrapper>(stmt, config, reports);
-    run_early_lint_on_stmt::<Deprecated>(stmt, config, reports);
-    run_early_lint_on_stmt::<Exit>(stmt, config, reports);
-    run_early_lint_on_stmt::<Global>(stmt, config, reports);
-    run_early_lint_on_stmt::<InvalidAssignment>(stmt, config, reports);
-    run_early_lint_on_stmt::<MissingDefaultCase>(stmt, config, reports);
-    run_early_lint_on_stmt::<MultiVarDeclaration>(stmt, config, reports);
-    run_early_lint_on_stmt::<NullCoalescenceEqual>(stmt, config, reports);
-    run_early_lint_on_stmt::<SingleSwitchCase>(stmt, config, reports);
-    run_early_lint_on_stmt::<SuspicousConstantUsage>(stmt, config, reports);
-    run_early_lint_on_stmt::<SwitchWithoutCase>(stmt, config, reports);
-    run_early_lint_on_stmt::<TryCatch>(stmt, config, reports);
-    run_early_lint_on_stmt::<UnassignedConstructor>(stmt, config, reports);
-    run_early_lint_on_stmt::<UnnecessaryGrouping>(stmt, config, reports);
-    run_early_lint_on_stmt::<UselessFunction>(stmt, config, reports);
-    run_early_lint_on_stmt::<VarPrefixViolation>(stmt, config, reports);
-    run_early_lint_on_stmt::<WithLoop>(stmt, config, reports);
+    run_early_lint_on_stmt::<CasingRules>(stmt, config, scopes, reports);
+    run_early_lint_on_stmt::<CollapsableIf>(stmt, config, scopes, reports);
+    run_early_lint_on_stmt::<ConditionWrapper>(stmt, config, scopes, reports);
+    run_early_lint_on_stmt::<Deprecated>(stmt, config, scopes, reports);
+    run_early_lint_on_stmt::<Exit>(stmt, config, scopes, reports);
+    run_early_lint_on_stmt::<Global>(stmt, config, scopes, reports);
+    run_early_lint_on_stmt::<InvalidAssignment>(stmt, config, scopes, reports);
+    run_early_lint_on_stmt::<MissingDefaultCase>(stmt, config, scopes, reports);
+    run_early_lint_on_stmt::<MultiVarDeclaration>(stmt, config, scopes, reports);
+    run_early_lint_on_stmt::<NullCoalescenceEqual>(stmt, config, scopes, reports);
+    run_early_lint_on_stmt::<SingleSwitchCase>(stmt, config, scopes, reports);
+    run_early_lint_on_stmt::<SuspicousConstantUsage>(stmt, config, scopes, reports);
+    run_early_lint_on_stmt::<SwitchWithoutCase>(stmt, config, scopes, reports);
+    run_early_lint_on_stmt::<TryCatch>(stmt, config, scopes, reports);
+    run_early_lint_on_stmt::<UnassignedConstructor>(stmt, config, scopes, reports);
+    run_early_lint_on_stmt::<UnnecessaryGrouping>(stmt, config, scopes, reports);
+    run_early_lint_on_stmt::<UselessFunction>(stmt, config, scopes, reports);
+    run_early_lint_on_stmt::<VarPrefixViolation>(stmt, config, scopes, reports);
+    run_early_lint_on_stmt::<WithLoop>(stmt, config, scopes, reports);
     // @end early stmt calls. Do not remove this comment!
 
     // Recurse...
     let stmt = stmt.kind();
-    stmt.visit_child_stmts(|stmt| process_stmt_early(stmt, reports, config));
-    stmt.visit_child_exprs(|expr| process_expr_early(expr, reports, config));
+    stmt.visit_child_stmts(|stmt| process_stmt_early(stmt, reports, config, scopes));
+    stmt.visit_child_exprs(|expr| process_expr_early(expr, reports, config, scopes));
 }
 
 /// Runs an expression through the early pass, running any lint that
@@ -76,37 +127,37 @@ pub fn process_stmt_early(stmt: &Stmt, reports: &mut Vec<Diagnostic<FileId>>, co
 ///
 /// NOTE: This function is largely auto-generated! See `CONTRIBUTING.md` for
 /// more information.
-pub fn process_expr_early(expr: &Expr, reports: &mut Vec<Diagnostic<FileId>>, config: &Config) {
+pub fn process_expr_early(expr: &Expr, reports: &mut Vec<Report>, config: &Config, scopes: &TagScopes) {
     // @early expr calls. Do not remove this comment!
-    run_early_lint_on_expr::<AccessorAlternative>(expr, config, reports);
-    run_early_lint_on_expr::<AndPreference>(expr, config, reports);
-    run_early_lint_on_expr::<AnonymousConstructor>(expr, config, reports);
-    run_early_lint_on_expr::<BoolEquality>(expr, config, reports);
-    run_early_lint_on_expr::<CasingRules>(expr, config, reports);
-    run_early_lint_on_expr::<ConditionWrapper>(expr, config, reports);
-    run_early_lint_on_expr::<Deprecated>(expr, config, reports);
-    run_early_lint_on_expr::<DrawSprite>(expr, config, reports);
-    run_early_lint_on_expr::<DrawText>(expr, config, reports);
-    run_early_lint_on_expr::<EnglishFlavorViolation>(expr, config, reports);
-    run_early_lint_on_expr::<InvalidComparison>(expr, config, reports);
-    run_early_lint_on_expr::<InvalidEquality>(expr, config, reports);
-    run_early_lint_on_expr::<ModPreference>(expr, config, reports);
-    run_early_lint_on_expr::<NonSimplifiedExpression>(expr, config, reports);
-    run_early_lint_on_expr::<NotPreference>(expr, config, reports);
-    run_early_lint_on_expr::<OrPreference>(expr, config, reports);
-    run_early_lint_on_expr::<RoomGoto>(expr, config, reports);
-    run_early_lint_on_expr::<ShowDebugMessage>(expr, config, reports);
-    run_early_lint_on_expr::<SingleEqualsComparison>(expr, config, reports);
-    run_early_lint_on_expr::<SuspicousConstantUsage>(expr, config, reports);
-    run_early_lint_on_expr::<Todo>(expr, config, reports);
-    run_early_lint_on_expr::<TooManyArguments>(expr, config, reports);
-    run_early_lint_on_expr::<UnnecessaryGrouping>(expr, config, reports);
-    run_early_lint_on_expr::<UnusedParameter>(expr, config, reports);
+    run_early_lint_on_expr::<AccessorAlternative>(expr, config, scopes, reports);
+    run_early_lint_on_expr::<AndPreference>(expr, config, scopes, reports);
+    run_early_lint_on_expr::<AnonymousConstructor>(expr, config, scopes, reports);
+    run_early_lint_on_expr::<BoolEquality>(expr, config, scopes, reports);
+    run_early_lint_on_expr::<CasingRules>(expr, config, scopes, reports);
+    run_early_lint_on_expr::<ConditionWrapper>(expr, config, scopes, reports);
+    run_early_lint_on_expr::<Deprecated>(expr, config, scopes, reports);
+    run_early_lint_on_expr::<DrawSprite>(expr, config, scopes, reports);
+    run_early_lint_on_expr::<DrawText>(expr, config, scopes, reports);
+    run_early_lint_on_expr::<EnglishFlavorViolation>(expr, config, scopes, reports);
+    run_early_lint_on_expr::<InvalidComparison>(expr, config, scopes, reports);
+    run_early_lint_on_expr::<InvalidEquality>(expr, config, scopes, reports);
+    run_early_lint_on_expr::<ModPreference>(expr, config, scopes, reports);
+    run_early_lint_on_expr::<NonSimplifiedExpression>(expr, config, scopes, reports);
+    run_early_lint_on_expr::<NotPreference>(expr, config, scopes, reports);
+    run_early_lint_on_expr::<OrPreference>(expr, config, scopes, reports);
+    run_early_lint_on_expr::<RoomGoto>(expr, config, scopes, reports);
+    run_early_lint_on_expr::<ShowDebugMessage>(expr, config, scopes, reports);
+    run_early_lint_on_expr::<SingleEqualsComparison>(expr, config, scopes, reports);
+    run_early_lint_on_expr::<SuspicousConstantUsage>(expr, config, scopes, reports);
+    run_early_lint_on_expr::<Todo>(expr, config, scopes, reports);
+    run_early_lint_on_expr::<TooManyArguments>(expr, config, scopes, reports);
+    run_early_lint_on_expr::<UnnecessaryGrouping>(expr, config, scopes, reports);
+    run_early_lint_on_expr::<UnusedParameter>(expr, config, scopes, reports);
     // @end early expr calls. Do not remove this comment!
 
     // Recurse...
-    expr.visit_child_stmts(|stmt| process_stmt_early(stmt, reports, config));
-    expr.visit_child_exprs(|expr| process_expr_early(expr, reports, config));
+    expr.visit_child_stmts(|stmt| process_stmt_early(stmt, reports, config, scopes));
+    expr.visit_child_exprs(|expr| process_expr_early(expr, reports, config, scopes));
 }
 
 /// Runs a [Stmt] through the late pass, running any lint that
@@ -114,15 +165,15 @@ pub fn process_expr_early(expr: &Expr, reports: &mut Vec<Diagnostic<FileId>>, co
 ///
 /// NOTE: This function is largely auto-generated! See `CONTRIBUTING.md` for
 /// more information.
-pub fn process_stmt_late(stmt: &Stmt, reports: &mut Vec<Diagnostic<FileId>>, config: &Config, ctx: &Ctx) {
+pub fn process_stmt_late(stmt: &Stmt, reports: &mut Vec<Report>, config: &Config, ctx: &Ctx, scopes: &TagScopes) {
     // @late stmt calls. Do not remove this comment!
-    run_late_lint_on_stmt::<MissingCaseMember>(stmt, config, ctx, reports);
+    run_late_lint_on_stmt::<MissingCaseMember>(stmt, config, ctx, scopes, reports);
     // @end late stmt calls. Do not remove this comment!
 
     // Recurse...
     let stmt = stmt.kind();
-    stmt.visit_child_stmts(|stmt| process_stmt_late(stmt, reports, config, ctx));
-    stmt.visit_child_exprs(|expr| process_expr_late(expr, reports, config, ctx));
+    stmt.visit_child_stmts(|stmt| process_stmt_late(stmt, reports, config, ctx, scopes));
+    stmt.visit_child_exprs(|expr| process_expr_late(expr, reports, config, ctx, scopes));
 }
 
 /// Runs an expression through the late pass, running any lint that
@@ -130,21 +181,62 @@ pub fn process_stmt_late(stmt: &Stmt, reports: &mut Vec<Diagnostic<FileId>>, con
 ///
 ///  NOTE: This function is largely auto-generated! See `CONTRIBUTING.md`
 /// for more information.
-fn process_expr_late(expr: &Expr, reports: &mut Vec<Diagnostic<FileId>>, config: &Config, ctx: &Ctx) {
+fn process_expr_late(expr: &Expr, reports: &mut Vec<Report>, config: &Config, ctx: &Ctx, scopes: &TagScopes) {
     // @late expr calls. Do not remove this comment!
-    run_late_lint_on_expr::<FunctionNameAsParameter>(expr, config, ctx, reports);
-    run_late_lint_on_expr::<NonConstantDefaultParameter>(expr, config, ctx, reports);
+    run_late_lint_on_expr::<FunctionNameAsParameter>(expr, config, ctx, scopes, reports);
+    run_late_lint_on_expr::<NonConstantDefaultParameter>(expr, config, ctx, scopes, reports);
     // @end late expr calls. Do not remove this comment!
 
     // Recurse...
-    expr.visit_child_stmts(|stmt| process_stmt_late(stmt, reports, config, ctx));
-    expr.visit_child_exprs(|expr| process_expr_late(expr, reports, config, ctx));
+    expr.visit_child_stmts(|stmt| process_stmt_late(stmt, reports, config, ctx, scopes));
+    expr.visit_child_exprs(|expr| process_expr_late(expr, reports, config, ctx, scopes));
+}
+
+/// Performs a given [AstPass] on an Ast.
+fn run_lint_on_ast<T: Lint + AstPass>(ast: &Ast, config: &Config, scopes: &TagScopes, reports: &mut Vec<Report>) {
+    run_lint_on_entire_ast::<T>(config, scopes, reports, |reports| T::visit_ast(ast, config, reports));
+}
+
+/// Performs a given [LateAstPass] on an Ast.
+fn run_late_lint_on_ast<T: Lint + LateAstPass>(
+    ast: &Ast,
+    config: &Config,
+    ctx: &Ctx,
+    scopes: &TagScopes,
+    reports: &mut Vec<Report>,
+) {
+    run_lint_on_entire_ast::<T>(config, scopes, reports, |reports| {
+        T::visit_ast_late(ast, config, ctx, reports)
+    });
 }
 
-/// Performs a lint on an Ast
-fn run_lint_on_ast<T: Lint + AstPass>(ast: &Ast, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
-    if *config.get_lint_level_setting(T::tag(), T::default_level()) != LintLevel::Allow {
-        T::visit_ast(ast, config, reports);
+/// Runs a lint on an entire Ast. As the lint may report on any node within the Ast, each
+/// diagnostic it creates follows the innermost tag that contains it, if any.
+fn run_lint_on_entire_ast<T: Lint>(
+    config: &Config,
+    scopes: &TagScopes,
+    reports: &mut Vec<Report>,
+    visit: impl FnOnce(&mut Vec<Report>),
+) {
+    let config_level = *config.get_lint_level_setting(T::tag(), T::default_level());
+    if config_level == LintLevel::Allow && !scopes.mentions::<T>() {
+        return;
+    }
+    let mut ast_reports = vec![];
+    visit(&mut ast_reports);
+    for mut report in ast_reports {
+        let scope = report
+            .diagnostic
+            .labels
+            .first()
+            .and_then(|label| scopes.find::<T>(Span::new(label.range.start, label.range.end)));
+        match scope.map_or(config_level, |scope| scope.level).severity() {
+            Some(severity) => {
+                report.diagnostic.severity = severity;
+                reports.push(report);
+            }
+            None => scope.iter().for_each(|scope| scope.mark_used()),
+        }
     }
 }
 
@@ -152,26 +244,24 @@ fn run_lint_on_ast<T: Lint + AstPass>(ast: &Ast, config: &Config, reports: &mut
 fn run_early_lint_on_stmt<T: Lint + EarlyStmtPass>(
     stmt: &Stmt,
     config: &Config,
-    reports: &mut Vec<Diagnostic<FileId>>,
+    scopes: &TagScopes,
+    reports: &mut Vec<Report>,
 ) {
-    if stmt.tag().is_none_or(|tag| !tag.eq(&("allow", Some(T::tag()))))
-        && *config.get_lint_level_setting(T::tag(), T::default_level()) != LintLevel::Allow
-    {
-        T::visit_stmt_early(stmt, config, reports);
-    }
+    run_lint_on_node::<T>(stmt.span(), config, scopes, reports, |reports| {
+        T::visit_stmt_early(stmt, config, reports)
+    });
 }
 
 /// Performs a given [EarlyExprPass] on a statement.
 fn run_early_lint_on_expr<T: Lint + EarlyExprPass>(
     expr: &Expr,
     config: &Config,
-    reports: &mut Vec<Diagnostic<FileId>>,
+    scopes: &TagScopes,
+    reports: &mut Vec<Report>,
 ) {
-    if expr.tag().is_none_or(|tag| !tag.eq(&("allow", Some(T::tag()))))
-        && *config.get_lint_level_setting(T::tag(), T::default_level()) != LintLevel::Allow
-    {
-        T::visit_expr_early(expr, config, reports);
-    }
+    run_lint_on_node::<T>(expr.span(), config, scopes, reports, |reports| {
+        T::visit_expr_early(expr, config, reports)
+    });
 }
 
 /// Performs a given [LateStmtPass] on a statement.
@@ -179,13 +269,12 @@ fn run_late_lint_on_stmt<T: Lint + LateStmtPass>(
     stmt: &Stmt,
     config: &Config,
     ctx: &Ctx,
-    reports: &mut Vec<Diagnostic<FileId>>,
+    scopes: &TagScopes,
+    reports: &mut Vec<Report>,
 ) {
-    if stmt.tag().is_none_or(|tag| !tag.eq(&("allow", Some(T::tag()))))
-        && *config.get_lint_level_setting(T::tag(), T::default_level()) != LintLevel::Allow
-    {
-        T::visit_stmt_late(stmt, config, ctx, reports);
-    }
+    run_lint_on_node::<T>(stmt.span(), config, scopes, reports, |reports| {
+        T::visit_stmt_late(stmt, config, ctx, reports)
+    });
 }
 
 /// Performs a given [LateExprPass] on a statement.
@@ -193,68 +282,321 @@ fn run_late_lint_on_expr<T: Lint + LateExprPass>(
     expr: &Expr,
     config: &Config,
     ctx: &Ctx,
-    reports: &mut Vec<Diagnostic<FileId>>,
+    scopes: &TagScopes,
+    reports: &mut Vec<Report>,
 ) {
-    if expr.tag().is_none_or(|tag| !tag.eq(&("allow", Some(T::tag()))))
-        && *config.get_lint_level_setting(T::tag(), T::default_level()) != LintLevel::Allow
-    {
-        T::visit_expr_late(expr, config, ctx, reports);
+    run_lint_on_node::<T>(expr.span(), config, scopes, reports, |reports| {
+        T::visit_expr_late(expr, config, ctx, reports)
+    });
+}
+
+/// Runs a lint on a single node at the level set by the innermost tag containing it, or otherwise
+/// the level in the config. Any diagnostics created take on the severity of that level. Lints
+/// allowed by a tag are still run, so that tags which suppress nothing can be reported.
+fn run_lint_on_node<T: Lint>(
+    span: Span,
+    config: &Config,
+    scopes: &TagScopes,
+    reports: &mut Vec<Report>,
+    visit: impl FnOnce(&mut Vec<Report>),
+) {
+    let scope = scopes.find::<T>(span);
+    let level = scope.map_or_else(
+        || *config.get_lint_level_setting(T::tag(), T::default_level()),
+        |scope| scope.level,
+    );
+    match (level.severity(), scope) {
+        (Some(severity), _) => {
+            let first_new_report = reports.len();
+            visit(reports);
+            for report in &mut reports[first_new_report..] {
+                report.diagnostic.severity = severity;
+            }
+        }
+        (None, Some(scope)) => {
+            let mut suppressed = vec![];
+            visit(&mut suppressed);
+            if !suppressed.is_empty() {
+                scope.mark_used();
+            }
+        }
+        (None, None) => {}
+    }
+}
+
+/// The lint levels set by the `allow`, `warn` and `deny` tags throughout an [Ast]. Each tag applies
+/// to a span of the source code: tags on a statement or expression cover it and everything within
+/// it, tags written above a `#region` cover the region, and file tags (`// #![allow(lint)]`) cover
+/// the whole file. Where tags overlap, the innermost one wins.
+///
+/// As lints are run, each allow tag records whether it suppressed anything, so that unused ones
+/// can be reported with [TagScopes::report_unused] once every pass is complete.
+#[derive(Debug, Default)]
+pub struct TagScopes {
+    file_id: FileId,
+    scopes: Vec<TagScope>,
+    misplaced_file_tags: Vec<(Span, Tag)>,
+}
+impl TagScopes {
+    /// Collects every lint level tag within the Ast.
+    pub fn new(ast: &Ast) -> Self {
+        // Without any statements there is nothing for a tag to apply to
+        let Some(file_id) = ast.stmts().first().map(Stmt::file_id) else {
+            return Self::default();
+        };
+
+        // Outer scopes are pushed first, so that inner ones win ties between identical spans
+        let mut scopes = Self {
+            file_id,
+            scopes: vec![],
+            misplaced_file_tags: ast.misplaced_file_tags().to_vec(),
+        };
+        for (span, tag) in ast.file_tags() {
+            scopes.push(Span::new(0, usize::MAX), *span, tag);
+        }
+        for (span, tag) in ast.region_tags() {
+            let region_span = Span::new(span.start(), span.start() + "#region".len());
+            scopes.push(*span, region_span, tag);
+        }
+        for node in ast.tagged_nodes(None) {
+            if let Some(tag) = node.tag() {
+                scopes.push(node.span(), node.span(), tag);
+            }
+        }
+        scopes
+    }
+
+    /// Adds a tag to the scopes. Tags within an identical tag are still kept, as only the innermost
+    /// one is marked as used, so that a redundant one is reported.
+    fn push(&mut self, span: Span, tag_span: Span, tag: &Tag) {
+        if let Tag(level, Some(lint)) = tag {
+            if let Some(level) = LintLevel::from_str(level) {
+                self.scopes.push(TagScope {
+                    span,
+                    tag_span,
+                    tag: tag.clone(),
+                    level,
+                    lint: lint.clone(),
+                    used: AtomicBool::new(false),
+                });
+            }
+        }
+    }
+
+    /// Returns the level set for a lint by the innermost tag containing the span, if any.
+    pub fn level<T: Lint>(&self, span: Span) -> Option<LintLevel> {
+        self.find::<T>(span).map(|scope| scope.level)
+    }
+
+    /// Returns whether any tag sets the level of the lint somewhere within the Ast.
+    pub fn mentions<T: Lint>(&self) -> bool {
+        self.scopes.iter().any(|scope| scope.lint == T::tag())
+    }
+
+    /// Returns the index of every tag that has suppressed a diagnostic so far, so that a later
+    /// run which only repeats the late pass can restore them with [TagScopes::restore_used].
+    pub fn used(&self) -> Vec<usize> {
+        self.scopes
+            .iter()
+            .enumerate()
+            .filter(|(_, scope)| scope.used.load(Ordering::Relaxed))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Marks the tags at each index as having suppressed a diagnostic. See [TagScopes::used].
+    pub fn restore_used(&self, indices: &[usize]) {
+        indices
+            .iter()
+            .filter_map(|index| self.scopes.get(*index))
+            .for_each(TagScope::mark_used);
+    }
+
+    /// Reports every allow tag that has not suppressed a diagnostic, every tag that names a lint
+    /// that does not exist, and every file tag written after the first statement, as
+    /// [UnusedAllow].
+    pub fn report_unused(&self, config: &Config, reports: &mut Vec<Report>) {
+        let known_lints = Config::full().lint_levels;
+        let config_level = *config.get_lint_level_setting(UnusedAllow::tag(), UnusedAllow::default_level());
+        for (span, tag) in &self.misplaced_file_tags {
+            let level = self.level::<UnusedAllow>(*span).unwrap_or(config_level);
+            if let Some(severity) = level.severity() {
+                let mut report = UnusedAllow::misplaced_file_tag(config, self.file_id, *span, tag);
+                report.diagnostic.severity = severity;
+                reports.push(report);
+            }
+        }
+        for scope in &self.scopes {
+            let mut report = if !known_lints.contains_key(&scope.lint) {
+                UnusedAllow::unknown_lint(config, self.file_id, scope.tag_span, &scope.tag)
+            } else if scope.level == LintLevel::Allow
+                && scope.lint != UnusedAllow::tag()
+                && !scope.used.load(Ordering::Relaxed)
+            {
+                UnusedAllow::unused_tag(config, self.file_id, scope.tag_span, &scope.tag)
+            } else {
+                continue;
+            };
+            let level = self.level::<UnusedAllow>(scope.tag_span).unwrap_or(config_level);
+            if let Some(severity) = level.severity() {
+                report.diagnostic.severity = severity;
+                reports.push(report);
+            }
+        }
+    }
+
+    /// Returns the innermost tag setting the level of a lint that contains the span, if any.
+    fn find<T: Lint>(&self, span: Span) -> Option<&TagScope> {
+        self.scopes
+            .iter()
+            .rev()
+            .filter(|scope| scope.lint == T::tag() && scope.span.contains(span))
+            .min_by_key(|scope| scope.span.end() - scope.span.start())
+    }
+}
+
+/// A single tag within [TagScopes].
+#[derive(Debug)]
+struct TagScope {
+    /// The source code the tag applies to.
+    span: Span,
+    /// Where the tag is reported if it goes unused.
+    tag_span: Span,
+    tag: Tag,
+    level: LintLevel,
+    lint: String,
+    used: AtomicBool,
+}
+impl TagScope {
+    fn mark_used(&self) {
+        self.used.store(true, Ordering::Relaxed);
     }
 }
 
-/// Creates a Tokio task which will walk through the provided directory in
-/// search of gml files. Passes each path it finds into the returned
-/// Receiver. Closes when all files have been sent.
+/// Creates a Tokio task which will search the provided directory for gml files. If the directory
+/// contains a `.yyp` file, the gml belonging to each of the project's resources is found through
+/// it, skipping any files on disk that are no longer part of the project. Otherwise, the
+/// `objects`, `scripts` and `rooms` directories are walked instead. Files matched by the
+/// [IgnoreList] are skipped. Passes each path it finds into the returned Receiver. Closes when all
+/// files have been sent.
 ///
 /// ### Panics
 /// Panics if the receiver for the sender closes. This should not be possible!
 pub fn start_gml_discovery(
     directory: &Path,
-    files_to_ignore: Vec<String>,
+    ignore_list: IgnoreList,
 ) -> (Receiver<PathBuf>, JoinHandle<Vec<std::io::Error>>) {
+    let (path_sender, path_receiver) = channel::<PathBuf>(1000);
+    let handle = match project::find_yyp(directory) {
+        Some(yyp_path) => tokio::task::spawn(discover_project_gml(
+            directory.to_path_buf(),
+            yyp_path,
+            ignore_list,
+            path_sender,
+        )),
+        None => tokio::task::spawn(discover_directory_gml(
+            directory.to_path_buf(),
+            ignore_list,
+            path_sender,
+        )),
+    };
+    (path_receiver, handle)
+}
+
+/// Sends the path of every gml file belonging to the resources listed in a `.yyp` file.
+async fn discover_project_gml(
+    directory: PathBuf,
+    yyp_path: PathBuf,
+    ignore_list: IgnoreList,
+    path_sender: Sender<PathBuf>,
+) -> Vec<std::io::Error> {
+    let mut io_errors = vec![];
+    let resources = match tokio::fs::read_to_string(&yyp_path)
+        .await
+        .and_then(|yyp| project::yyp_resources(&yyp))
+    {
+        Ok(resources) => resources,
+        Err(error) => {
+            io_errors.push(std::io::Error::new(
+                error.kind(),
+                format!("failed to read {}: {error}", yyp_path.display()),
+            ));
+            return io_errors;
+        }
+    };
+    for resource_path in resources {
+        let gml_paths = match tokio::fs::read_to_string(directory.join(&resource_path))
+            .await
+            .and_then(|yy| project::resource_gml_paths(&resource_path, &yy))
+        {
+            Ok(gml_paths) => gml_paths,
+            Err(error) => {
+                io_errors.push(std::io::Error::new(
+                    error.kind(),
+                    format!("failed to read {}: {error}", resource_path.display()),
+                ));
+                continue;
+            }
+        };
+        for relative_path in gml_paths {
+            if ignore_list.is_ignored(&relative_path) {
+                continue;
+            }
+            let path = directory.join(relative_path);
+            match path.canonicalize() {
+                Ok(_) => path_sender.send(path).await.unwrap(),
+                Err(error) => io_errors.push(std::io::Error::new(
+                    error.kind(),
+                    format!("{} is part of the project, but could not be found", path.display()),
+                )),
+            }
+        }
+    }
+    io_errors
+}
+
+/// Sends the path of every gml file within the `objects`, `scripts` and `rooms` directories, which
+/// is used for projects without a `.yyp` file.
+async fn discover_directory_gml(
+    directory: PathBuf,
+    ignore_list: IgnoreList,
+    path_sender: Sender<PathBuf>,
+) -> Vec<std::io::Error> {
     /// Filters DirEntry's for gml files.
     async fn filter(entry: DirEntry) -> Filtering {
-        if entry.file_name().to_str().is_some_and(|f| !f.contains(".gml")) {
-            Filtering::Ignore
-        } else {
+        if Path::new(&entry.file_name()).extension().is_some_and(|v| v == "gml") {
             Filtering::Continue
+        } else {
+            Filtering::Ignore
         }
     }
 
-    let files_to_ignore: Vec<PathBuf> = files_to_ignore
-        .iter()
-        .map(PathBuf::from)
-        .map(|v| directory.join(v).canonicalize().unwrap())
-        .collect();
-
     let mut io_errors = vec![];
     let mut walker = WalkDir::new(directory.join("objects"))
         .filter(filter)
         .chain(WalkDir::new(directory.join("scripts")).filter(filter))
         .chain(WalkDir::new(directory.join("rooms")).filter(filter));
-    let (path_sender, path_receiver) = channel::<PathBuf>(1000);
-    let handle = tokio::task::spawn(async move {
-        loop {
-            match walker.next().await {
-                Some(Ok(entry))
-                    if !files_to_ignore
-                        .iter()
-                        .any(|v| v == &entry.path().canonicalize().unwrap()) =>
+    loop {
+        match walker.next().await {
+            Some(Ok(entry)) => {
+                let path = entry.path();
+                if !path
+                    .strip_prefix(&directory)
+                    .is_ok_and(|relative_path| ignore_list.is_ignored(relative_path))
                 {
-                    path_sender.send(entry.path()).await.unwrap()
+                    path_sender.send(path).await.unwrap();
                 }
-                Some(Err(e)) => io_errors.push(e),
-                None => break,
-                _ => {}
             }
+            Some(Err(e)) => io_errors.push(e),
+            None => break,
         }
-        io_errors
-    });
-    (path_receiver, handle)
+    }
+    io_errors
 }
 
 /// Creates a Tokio task which will await paths through `path_receiever` and
-/// subsequently load their data, pumping it to the returned Receiver.
+/// subsequently load their data, pumping it to the returned Receiver alongside where in the
+/// project each file belongs.
 /// Closes when the `path_receiver` channel closes. Additionally returns the total number of
 /// lines that were found.
 ///
@@ -264,10 +606,10 @@ pub fn start_gml_discovery(
 pub fn start_file_load(
     mut path_receiver: Receiver<PathBuf>,
 ) -> (
-    Receiver<(FileId, &'static str)>,
+    Receiver<(FileId, &'static str, PathBuf)>,
     JoinHandle<(usize, GmlLibrary, Vec<std::io::Error>)>,
 ) {
-    let (file_sender, file_receiver) = channel::<(FileId, &'static str)>(1000);
+    let (file_sender, file_receiver) = channel::<(FileId, &'static str, PathBuf)>(1000);
     let handle = tokio::task::spawn(async move {
         let mut files = GmlLibrary::new();
         let mut io_errors = vec![];
@@ -278,7 +620,7 @@ pub fn start_file_load(
                     let gml: &'static str = Box::leak(Box::new(gml));
                     lines += gml.lines().count();
                     let file_id = files.add(path.canonicalize().unwrap().to_str().unwrap().to_string(), gml);
-                    file_sender.send((file_id, gml)).await.unwrap();
+                    file_sender.send((file_id, gml, path)).await.unwrap();
                 }
                 Err(io_error) => io_errors.push(io_error),
             };
@@ -290,21 +632,24 @@ pub fn start_file_load(
 
 /// Creates a Tokio task which will await gml files through `file_receiever`
 /// and subsequently parse them into an [Ast], pumping them into the
-/// returned Receiver. Closes when the `file_receiever` channel closes.
+/// returned Receiver. Files containing errors are still sent along with
+/// whatever could be parsed, so that the rest of the file can be linted.
+/// Closes when the `file_receiever` channel closes.
 ///
 /// ### Panics
 /// Panics if the receiver for the sender closes. This should not be possible!
 pub fn start_parse(
-    mut file_receiver: Receiver<(FileId, &'static str)>,
-) -> (Receiver<Ast>, JoinHandle<Vec<Diagnostic<FileId>>>) {
+    mut file_receiver: Receiver<(FileId, &'static str, PathBuf)>,
+) -> (Receiver<Ast>, JoinHandle<Vec<Report>>) {
     let (ast_sender, ast_receiver) = channel::<Ast>(1000);
     let handle = tokio::task::spawn(async move {
         let mut parse_errors = vec![];
-        while let Some((file_id, gml)) = file_receiver.recv().await {
-            match parse_gml(gml, &file_id) {
-                Ok(ast) => ast_sender.send(ast).await.unwrap(),
-                Err(parse_error) => parse_errors.push(parse_error),
-            }
+        while let Some((file_id, gml, path)) = file_receiver.recv().await {
+            let (mut ast, errors) = parse_gml_partial(gml, &file_id);
+            ast.set_origin(project::ast_origin(&path));
+            ast.set_path(path);
+            parse_errors.extend(errors.into_iter().map(Report::from));
+            ast_sender.send(ast).await.unwrap();
         }
         parse_errors
     });
@@ -315,67 +660,73 @@ pub fn start_parse(
 /// run the early pass lints on them, pumping the results through the
 /// returned Receiver. Closes when the `ast_receiever` channel closes.
 ///
+/// Each Ast is linted with the config returned by [file_config], which is then sent alongside it
+/// to the late pass.
+///
 /// ### Panics
 /// Panics if the receiver for the sender closes. This should not be possible!
 #[allow(clippy::type_complexity)]
 pub fn start_early_pass(
     config: Arc<Config>,
+    project_directory: &Path,
     mut ast_receiever: Receiver<Ast>,
 ) -> (
-    Receiver<Stmt>,
-    Sender<Vec<Diagnostic<FileId>>>,
-    Receiver<Vec<Diagnostic<FileId>>>,
+    Receiver<(Ast, Arc<Config>, TagScopes)>,
+    Sender<Vec<Report>>,
+    Receiver<Vec<Report>>,
     Receiver<Ctx>,
     JoinHandle<()>,
 ) {
-    let (report_sender, report_receiver) = channel::<Vec<Diagnostic<FileId>>>(100000000);
-    let (stmt_sender, stmt_reciever) = channel::<Stmt>(100000000);
+    let (report_sender, report_receiver) = channel::<Vec<Report>>(100000000);
+    let (late_sender, late_reciever) = channel::<(Ast, Arc<Config>, TagScopes)>(100000000);
     let (ctx_sender, ctx_reciever) = channel::<Ctx>(100000000);
     let sender = report_sender.clone();
     let mut ctx = Ctx::default();
+    let project_directory = project_directory.to_path_buf();
     let handle = tokio::task::spawn(async move {
         while let Some(ast) = ast_receiever.recv().await {
-            let config = config.clone();
+            let config = file_config(&config, &project_directory, &ast);
+            let scopes = TagScopes::new(&ast);
             let mut reports = vec![];
-            process_ast(&ast, &mut reports, config.as_ref());
-            sender.send(reports).await.unwrap();
-            for stmt in ast.unpack() {
-                let mut reports = vec![];
-
-                if let StmtKind::Expr(expr) = stmt.kind() {
-                    if let ExprKind::Function(Function { name: Some(name), .. }) = expr.kind() {
-                        ctx.global_function_names.push(name.to_string());
-                    }
-                }
-
-                process_stmt_early(&stmt, &mut reports, config.as_ref());
-                stmt_sender.send(stmt).await.unwrap();
-                sender.send(reports).await.unwrap();
+            process_ast(&ast, &mut reports, config.as_ref(), &scopes);
+            for stmt in ast.stmts() {
+                process_stmt_early(stmt, &mut reports, config.as_ref(), &scopes);
             }
+            sender.send(reports).await.unwrap();
+            ctx.collect_ast(&ast);
+            late_sender.send((ast, config, scopes)).await.unwrap();
         }
         ctx_sender.send(ctx).await.unwrap();
     });
-    (stmt_reciever, report_sender, report_receiver, ctx_reciever, handle)
+    (late_reciever, report_sender, report_receiver, ctx_reciever, handle)
+}
+
+/// Returns the config an [Ast] is linted with, which is the one returned by [Config::for_file] for
+/// its path relative to the project's directory, if there is one.
+pub fn file_config(config: &Arc<Config>, project_directory: &Path, ast: &Ast) -> Arc<Config> {
+    ast.path()
+        .and_then(|path| path.strip_prefix(project_directory).ok())
+        .and_then(|path| config.for_file(path))
+        .map_or_else(|| config.clone(), Arc::new)
 }
 
-/// Creates Tokio tasks for all of the provided `StmtIteration`s,
-/// running the late lint pass on them. Returns a handle to another
-/// Tokio task which will collect their finalized [LatePassReport]s.
+/// Creates Tokio tasks for all of the provided [Ast]s, running the late lint pass on them. Tags
+/// that went unused throughout a file are reported once it has been linted. Returns a handle to
+/// another Tokio task which will collect every diagnostic found.
 ///
 /// ### Panics
 /// Panics if the receiver for the sender closes. This should not be possible!
 pub fn start_late_pass(
-    config: Arc<Config>,
     ctx: Ctx,
-    mut stmt_receiver: Receiver<Stmt>,
-    report_sender: Sender<Vec<Diagnostic<FileId>>>,
-    mut report_receiver: Receiver<Vec<Diagnostic<FileId>>>,
-) -> JoinHandle<Vec<Diagnostic<FileId>>> {
+    mut ast_receiver: Receiver<(Ast, Arc<Config>, TagScopes)>,
+    report_sender: Sender<Vec<Report>>,
+    mut report_receiver: Receiver<Vec<Report>>,
+) -> JoinHandle<Vec<Report>> {
     tokio::task::spawn(async move {
-        while let Some(stmt) = stmt_receiver.recv().await {
-            let config = config.clone();
+        while let Some((ast, config, scopes)) = ast_receiver.recv().await {
             let mut reports = vec![];
-            process_stmt_late(&stmt, &mut reports, config.as_ref(), &ctx);
+            process_ast_late(&ast, &mut reports, config.as_ref(), &ctx, &scopes);
+            scopes.report_unused(config.as_ref(), &mut reports);
             report_sender.send(reports).await.unwrap();
         }
     });
@@ -389,11 +740,236 @@ pub fn start_late_pass(
 }
 
 /// TODO
-pub type Pass = (Stmt, Vec<Diagnostic<FileId>>);
+pub type Pass = (Stmt, Vec<Report>);
 
 /// Information collected throughout the run.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Ctx {
-    /// The string names of every global function found.
-    pub global_function_names: Vec<String>,
+    /// Every global name declared throughout the project.
+    pub symbols: SymbolTable,
+    /// The name of every resource in the project, such as its sprites and sounds, along with the
+    /// names its resources declare outside of gml. See [Ctx::collect_assets].
+    pub assets: BTreeSet<String>,
+    /// Every name assigned to as a variable of an instance or struct throughout the project, such
+    /// as `hp` in `hp = 10`, `other.hp = 10` or `{ hp: 10 }`.
+    pub instance_variables: BTreeSet<String>,
+    /// Every name read or called throughout the project, along with the names passed as strings to
+    /// `asset_get_index`. References a global function makes to itself are not included.
+    pub references: BTreeSet<String>,
+}
+impl Ctx {
+    /// Records the global declarations made within an [Ast], such as its functions, enums and
+    /// macros, along with the object it belongs to, if any.
+    pub fn collect_ast(&mut self, ast: &Ast) {
+        // Functions declared in an object's events are methods on its instances, not globals
+        let functions_are_global = match ast.origin() {
+            AstOrigin::ObjectEvent { object, .. } => {
+                let location = Location(ast.file_id(), Span::default());
+                self.symbols.insert(Symbol::new(object, SymbolKind::Object, location));
+                false
+            }
+            AstOrigin::RoomCreationCode { .. } | AstOrigin::InstanceCreationCode { .. } => false,
+            AstOrigin::Script { .. } | AstOrigin::Unknown => true,
+        };
+        for stmt in ast.stmts() {
+            if let StmtKind::Expr(expr) = stmt.kind() {
+                if let ExprKind::Function(function @ Function { name: Some(name), .. }) = expr.kind() {
+                    if functions_are_global {
+                        let kind = match function.constructor {
+                            Some(_) => SymbolKind::Constructor,
+                            None => SymbolKind::Function,
+                        };
+                        self.symbols
+                            .insert(Symbol::new(&name.lexeme, kind, Location(stmt.file_id(), name.span)));
+                        collect_function(function, self);
+
+                        // A function that only calls itself is still unused
+                        let mut references = BTreeSet::new();
+                        collect_references_in_function(function, &mut references);
+                        references.remove(&name.lexeme);
+                        self.references.extend(references);
+                        continue;
+                    }
+                }
+            }
+
+            // Everything else is global no matter where it is declared
+            collect_declarations(stmt, self);
+            collect_references(stmt, &mut self.references);
+        }
+    }
+
+    /// Records the name of every resource in the project within the directory, along with the
+    /// names its resources declare outside of gml. See [project::asset_names].
+    pub fn collect_assets(&mut self, project_directory: &Path) {
+        // A project whose resources cannot be read has already been reported during discovery
+        if let Ok(names) = project::asset_names(project_directory) {
+            self.assets.extend(names);
+        }
+    }
+
+    /// Adds the information collected in another Ctx, such as that of a single file.
+    pub fn extend(&mut self, other: Ctx) {
+        self.symbols.extend(other.symbols);
+        self.assets.extend(other.assets);
+        self.instance_variables.extend(other.instance_variables);
+        self.references.extend(other.references);
+    }
+
+    /// Moves every declaration in the Ctx to the given file. Used when reusing the Ctx of a single
+    /// file from a previous run, in which the file may have had a different id.
+    pub fn set_file_id(&mut self, file_id: FileId) {
+        self.symbols.set_file_id(file_id);
+    }
+}
+
+/// Records every enum, macro, global variable and instance variable declared within the statement,
+/// including those nested within functions.
+fn collect_declarations(stmt: &Stmt, ctx: &mut Ctx) {
+    match stmt.kind() {
+        StmtKind::Enum(gml_enum) => {
+            let enum_name = &gml_enum.name.lexeme;
+            let location = Location(stmt.file_id(), gml_enum.name.span);
+            ctx.symbols.insert(Symbol::new(enum_name, SymbolKind::Enum, location));
+            for member in gml_enum.members.iter() {
+                let kind = SymbolKind::EnumMember {
+                    enum_name: enum_name.clone(),
+                };
+                let location = Location(stmt.file_id(), member.name_identifier().span);
+                ctx.symbols.insert(Symbol::new(member.name(), kind, location));
+            }
+            return;
+        }
+        StmtKind::Macro(gml_macro) => {
+            let location = Location(stmt.file_id(), gml_macro.name.span);
+            let kind = SymbolKind::Macro {
+                config: gml_macro.config.clone(),
+            };
+            ctx.symbols.insert(Symbol::new(&gml_macro.name.lexeme, kind, location));
+        }
+        StmtKind::Globalvar(globalvar) => {
+            let location = Location(stmt.file_id(), globalvar.name.span);
+            ctx.symbols.insert(Symbol::new(
+                &globalvar.name.lexeme,
+                SymbolKind::GlobalVariable,
+                location,
+            ));
+        }
+        StmtKind::LocalVariables(local_variables) => {
+            // Local variables are not instance variables, but their values may contain functions
+            for value in local_variables.declarations.iter().filter_map(Field::assignment_value) {
+                collect_declarations_in_expr(value, ctx);
+            }
+            return;
+        }
+        StmtKind::Assignment(Assignment { left, .. }) => match left.kind() {
+            ExprKind::Access(Access::Global { right }) => {
+                let location = Location(stmt.file_id(), right.span);
+                ctx.symbols
+                    .insert(Symbol::new(&right.lexeme, SymbolKind::GlobalVariable, location));
+            }
+            ExprKind::Identifier(identifier) => {
+                ctx.instance_variables.insert(identifier.lexeme.clone());
+            }
+            ExprKind::Access(Access::Dot { right, .. } | Access::Identity { right } | Access::Other { right }) => {
+                ctx.instance_variables.insert(right.lexeme.clone());
+            }
+            _ => {}
+        },
+        _ => {}
+    }
+    stmt.kind().visit_child_stmts(|stmt| collect_declarations(stmt, ctx));
+    stmt.kind()
+        .visit_child_exprs(|expr| collect_declarations_in_expr(expr, ctx));
+}
+
+/// Records every declaration made within the expression, such as within a function's body.
+fn collect_declarations_in_expr(expr: &Expr, ctx: &mut Ctx) {
+    match expr.kind() {
+        ExprKind::Function(function) => {
+            // Named functions that are not global become variables of the instance or struct
+            if let Some(name) = &function.name {
+                ctx.instance_variables.insert(name.lexeme.clone());
+            }
+            collect_function(function, ctx);
+            return;
+        }
+        ExprKind::Literal(Literal::Struct(fields)) => {
+            for (name, _) in fields {
+                ctx.instance_variables.insert(name.lexeme.clone());
+            }
+        }
+        _ => {}
+    }
+    expr.visit_child_stmts(|stmt| collect_declarations(stmt, ctx));
+    expr.visit_child_exprs(|expr| collect_declarations_in_expr(expr, ctx));
+}
+
+/// Records every declaration made within a function, skipping its parameters.
+fn collect_function(function: &Function, ctx: &mut Ctx) {
+    for value in function.parameters.iter().filter_map(Field::assignment_value) {
+        collect_declarations_in_expr(value, ctx);
+    }
+    if let Some(Constructor {
+        inheritance: Some(call),
+    }) = &function.constructor
+    {
+        collect_declarations_in_expr(call, ctx);
+    }
+    collect_declarations(&function.body, ctx);
+}
+
+/// Records every name read or called within the statement, including those within functions.
+fn collect_references(stmt: &Stmt, references: &mut BTreeSet<String>) {
+    match stmt.kind() {
+        // Assigning to a bare name does not reference it
+        StmtKind::Assignment(Assignment { left, right, .. }) if left.kind().as_identifier().is_some() => {
+            collect_references_in_expr(right, references);
+        }
+        kind => {
+            kind.visit_child_stmts(|stmt| collect_references(stmt, references));
+            kind.visit_child_exprs(|expr| collect_references_in_expr(expr, references));
+        }
+    }
+}
+
+/// Records every name read or called within the expression.
+fn collect_references_in_expr(expr: &Expr, references: &mut BTreeSet<String>) {
+    match expr.kind() {
+        ExprKind::Identifier(identifier) => {
+            references.insert(identifier.lexeme.clone());
+        }
+        // Functions can also be looked up by their name
+        ExprKind::Call(Call { left, arguments, .. })
+            if left
+                .kind()
+                .as_identifier()
+                .is_some_and(|identifier| identifier.lexeme == "asset_get_index") =>
+        {
+            if let Some(Literal::String(name)) = arguments.first().and_then(|argument| argument.kind().as_literal()) {
+                references.insert(name.clone());
+            }
+        }
+        ExprKind::Function(function) => {
+            collect_references_in_function(function, references);
+            return;
+        }
+        _ => {}
+    }
+    expr.visit_child_stmts(|stmt| collect_references(stmt, references));
+    expr.visit_child_exprs(|expr| collect_references_in_expr(expr, references));
+}
+
+/// Records every name read or called within a function, skipping its parameters.
+fn collect_references_in_function(function: &Function, references: &mut BTreeSet<String>) {
+    for value in function.parameters.iter().filter_map(Field::assignment_value) {
+        collect_references_in_expr(value, references);
+    }
+    if let Some(Constructor {
+        inheritance: Some(call),
+    }) = &function.constructor
+    {
+        collect_references_in_expr(call, references);
+    }
+    collect_references(&function.body, references);
 }
@@ -1,8 +1,8 @@
-use crate::{Config, lint::LintLevel};
-use codespan_reporting::{
-    diagnostic::Diagnostic,
-    files::{Error, Files, SimpleFile},
+use crate::{
+    Config, IgnoreList, Workspace,
+    lint::{LintLevel, Report},
 };
+use codespan_reporting::files::{Error, Files, SimpleFile};
 use enum_map::EnumMap;
 use std::{ops::Range, path::Path, sync::Arc};
 
@@ -40,11 +40,12 @@ use super::driver;
 #[derive(Debug, Default)]
 pub struct Duck {
     config: Config,
+    caching: bool,
 }
 impl Duck {
     /// Creates a new Duck based on a DuckConfig.
     pub fn new(config: Config) -> Self {
-        Self { config }
+        Self { config, caching: false }
     }
 
     /// Goes through the entire process of finding, loading, parsing, and
@@ -58,19 +59,23 @@ impl Duck {
     /// ### Errors
     /// Returns an error if we fail to join any of the tokio tasks.
     pub async fn run(&self, project_directory: &Path) -> Result<RunSummary, tokio::task::JoinError> {
+        if self.caching {
+            return self.run_cached(project_directory).await;
+        }
+
         // Load everything in and await through the early pass...
         let config_arc = Arc::new(self.config.clone()); // TODO: this clone sucks
-        let (path_receiver, walker_handle) =
-            driver::start_gml_discovery(project_directory, self.config().ignored_file_paths.clone());
+        let (ignore_list, warnings) = IgnoreList::load(project_directory, &self.config.ignored_file_paths);
+        let (path_receiver, walker_handle) = driver::start_gml_discovery(project_directory, ignore_list);
         let (file_receiver, file_handle) = driver::start_file_load(path_receiver);
         let (parse_receiver, parse_handle) = driver::start_parse(file_receiver);
-        let (stmt_receiever, report_sender, report_receiver, mut ctx_receiver, _) =
-            driver::start_early_pass(config_arc.clone(), parse_receiver);
+        let (late_receiver, report_sender, report_receiver, mut ctx_receiver, _) =
+            driver::start_early_pass(config_arc, project_directory, parse_receiver);
 
-        let ctx = ctx_receiver.recv().await.unwrap(); // this defeats the speedy async purpose of the receivers below, but oh well
+        let mut ctx = ctx_receiver.recv().await.unwrap(); // this defeats the speedy async purpose of the receivers below, but oh well
+        ctx.collect_assets(project_directory);
 
-        let mut diagnostics =
-            driver::start_late_pass(config_arc.clone(), ctx, stmt_receiever, report_sender, report_receiver).await?;
+        let mut diagnostics = driver::start_late_pass(ctx, late_receiver, report_sender, report_receiver).await?;
 
         // Extract any errors that were found...
         let (line_count, library, mut io_errors) = file_handle.await?;
@@ -78,7 +83,15 @@ impl Duck {
         diagnostics.append(&mut parse_handle.await?);
 
         // Return the result!
-        Ok(RunSummary::new(library, diagnostics, io_errors, line_count))
+        Ok(RunSummary::new(library, diagnostics, io_errors, warnings, line_count))
+    }
+
+    /// Performs a run using the [Cache] within the project's directory, only parsing and linting
+    /// the files that have changed since the previous run. See [Duck::set_caching].
+    async fn run_cached(&self, project_directory: &Path) -> Result<RunSummary, tokio::task::JoinError> {
+        Workspace::new(project_directory, self.config.clone(), true)
+            .run(&[])
+            .await
     }
 
     /// The blocking counterpart to [Duck::run].
@@ -103,32 +116,41 @@ impl Duck {
     pub fn config_mut(&mut self) -> &mut Config {
         &mut self.config
     }
+
+    /// Sets whether runs should store their results in the project's `.duck/cache`, reusing them
+    /// for any file that has not changed since. Disabled by default.
+    pub fn set_caching(&mut self, caching: bool) {
+        self.caching = caching;
+    }
 }
 
 /// The data returned by calling [Duck::run].
 pub struct RunSummary {
     library: GmlLibrary,
     diagonstic_counts: EnumMap<LintLevel, usize>,
-    diagnostics: Vec<Diagnostic<FileId>>,
+    diagnostics: Vec<Report>,
     io_errors: Vec<std::io::Error>,
+    warnings: Vec<String>,
     lines_parsed: usize,
 }
 impl RunSummary {
-    fn new(
+    pub(crate) fn new(
         library: GmlLibrary,
-        diagnostics: Vec<Diagnostic<FileId>>,
+        diagnostics: Vec<Report>,
         io_errors: Vec<std::io::Error>,
+        warnings: Vec<String>,
         lines_parsed: usize,
     ) -> Self {
         let mut diagonstic_counts: EnumMap<LintLevel, usize> = EnumMap::default();
         for report in diagnostics.iter() {
-            diagonstic_counts[report.severity.into()] += 1;
+            diagonstic_counts[report.diagnostic.severity.into()] += 1;
         }
         Self {
             library,
             diagonstic_counts,
             diagnostics,
             io_errors,
+            warnings,
             lines_parsed,
         }
     }
@@ -143,16 +165,35 @@ impl RunSummary {
         self.diagonstic_counts[LintLevel::Deny]
     }
 
-    /// Get a reference to the run summary's diagnostics.
-    pub fn diagnostics(&self) -> &[Diagnostic<usize>] {
+    /// Get a reference to the run summary's diagnostics, alongside the suggestions that fix them.
+    pub fn diagnostics(&self) -> &[Report] {
         self.diagnostics.as_ref()
     }
 
+    /// Removes every diagnostic the predicate returns false for, such as those recorded in a
+    /// baseline, updating the warning and denial counts to match.
+    pub fn retain_diagnostics(&mut self, mut predicate: impl FnMut(&Report) -> bool) {
+        let diagonstic_counts = &mut self.diagonstic_counts;
+        self.diagnostics.retain(|report| {
+            let retained = predicate(report);
+            if !retained {
+                diagonstic_counts[report.diagnostic.severity.into()] -= 1;
+            }
+            retained
+        });
+    }
+
     /// Get a reference to the run result's io errors.
     pub fn io_errors(&self) -> &[std::io::Error] {
         self.io_errors.as_ref()
     }
 
+    /// Get a reference to the problems found with the run's settings that did not stop it, such
+    /// as ignored files that do not exist.
+    pub fn warnings(&self) -> &[String] {
+        self.warnings.as_ref()
+    }
+
     /// Get the run result's lines parsed.
     pub fn lines_parsed(&self) -> usize {
         self.lines_parsed
@@ -191,6 +232,17 @@ impl GmlLibrary {
     pub fn get(&self, file_id: usize) -> Result<&SimpleFile<String, &'static str>, Error> {
         self.files.get(file_id).ok_or(Error::FileMissing)
     }
+
+    /// Returns the number of files in the database. As ids are handed out in order, this is also
+    /// the id the next file added will be given.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Returns whether the database has no files.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
 }
 impl<'a> Files<'a> for GmlLibrary {
     type FileId = FileId;
@@ -0,0 +1,161 @@
+use regex::Regex;
+use std::path::Path;
+
+/// A pattern matched against paths relative to a project's directory, such as `scripts/lib_*`.
+///
+/// `*` matches anything within a single directory, `**` matches across any number of
+/// directories, `?` matches a single character, and `[abc]` matches any character within the
+/// brackets (`[!abc]` matching any character outside of them). A pattern that matches a directory
+/// also matches everything inside of it.
+#[derive(Debug, Clone)]
+pub struct Glob {
+    pattern: String,
+    regex: Regex,
+}
+impl Glob {
+    /// Compiles a new glob.
+    ///
+    /// ### Errors
+    /// Returns an error if the pattern contains an unclosed bracket or an invalid character class.
+    pub fn new(pattern: &str) -> Result<Self, GlobError> {
+        let normalized = pattern.replace('\\', "/");
+        let normalized = normalized.trim_start_matches("./").trim_matches('/');
+        let mut regex = String::from("^");
+        let mut chars = normalized.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '*' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    if chars.next_if_eq(&'/').is_some() {
+                        regex.push_str("(?:.*/)?");
+                    } else {
+                        regex.push_str(".*");
+                    }
+                }
+                '*' => regex.push_str("[^/]*"),
+                '?' => regex.push_str("[^/]"),
+                '[' => {
+                    regex.push('[');
+                    if chars.next_if_eq(&'!').is_some() {
+                        regex.push('^');
+                    }
+                    let mut closed = false;
+                    for c in chars.by_ref() {
+                        if c == ']' {
+                            closed = true;
+                            break;
+                        }
+                        if c == '\\' || c == '[' {
+                            regex.push('\\');
+                        }
+                        regex.push(c);
+                    }
+                    if !closed {
+                        return Err(GlobError::UnclosedBracket);
+                    }
+                    regex.push(']');
+                }
+                c => regex.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+            }
+        }
+        regex.push_str("(?:/.*)?$");
+        Ok(Self {
+            pattern: pattern.to_string(),
+            regex: Regex::new(&regex).map_err(GlobError::InvalidClass)?,
+        })
+    }
+
+    /// Returns whether the path matches the glob.
+    pub fn is_match(&self, path: &Path) -> bool {
+        let path = path.to_string_lossy().replace('\\', "/");
+        self.regex.is_match(path.trim_start_matches("./"))
+    }
+
+    /// Returns the pattern the glob was created from.
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+}
+
+/// The reasons a pattern can fail to compile into a [Glob].
+#[derive(Debug)]
+pub enum GlobError {
+    /// A `[` that is never closed by a `]`.
+    UnclosedBracket,
+    /// A character class that cannot be matched, such as `[z-a]`.
+    InvalidClass(regex::Error),
+}
+impl std::fmt::Display for GlobError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GlobError::UnclosedBracket => write!(f, "unclosed `[`"),
+            GlobError::InvalidClass(error) => write!(f, "invalid character class: {error}"),
+        }
+    }
+}
+
+/// The files duck should skip within a project, built from `ignored_file_paths` and the project's
+/// `.duckignore` file.
+///
+/// Patterns follow gitignore's rules: a pattern containing a `/` (other than a trailing one) is
+/// matched against the path relative to the project's directory, while any other pattern matches
+/// files and folders by name at any depth. Patterns starting with `!` re-include files that an
+/// earlier pattern ignored, and the last pattern to match a file decides whether it is ignored.
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreList {
+    patterns: Vec<(Glob, bool)>,
+}
+impl IgnoreList {
+    /// Builds the ignore list for the project in the given directory, reading its `.duckignore`
+    /// (if it has one) before the given patterns. Returns a warning for every pattern that is
+    /// invalid, or that names a path that does not exist.
+    pub fn load(directory: &Path, patterns: &[String]) -> (Self, Vec<String>) {
+        let duckignore = std::fs::read_to_string(directory.join(".duckignore")).unwrap_or_default();
+        let duckignore_patterns = duckignore
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+        let mut ignore_list = Self::default();
+        let mut warnings = vec![];
+        for pattern in duckignore_patterns.chain(patterns.iter().map(String::as_str)) {
+            if let Err(warning) = ignore_list.add(directory, pattern) {
+                warnings.push(warning);
+            }
+        }
+        (ignore_list, warnings)
+    }
+
+    /// Adds a single pattern to the list.
+    fn add(&mut self, directory: &Path, pattern: &str) -> Result<(), String> {
+        let (negated, pattern) = match pattern.strip_prefix('!') {
+            Some(pattern) => (true, pattern),
+            None => (false, pattern),
+        };
+        let normalized = pattern.replace('\\', "/");
+        let anchored = normalized.trim_end_matches('/').contains('/');
+        let glob = if anchored {
+            Glob::new(&normalized)
+        } else {
+            Glob::new(&format!("**/{normalized}"))
+        }
+        .map_err(|error| format!("`{pattern}` is not a valid pattern: {error}"))?;
+        self.patterns.push((glob, negated));
+
+        // A plain path that does not exist is most likely a typo or a file that has since moved
+        let is_glob = pattern.contains(['*', '?', '[']);
+        if !is_glob && anchored && !directory.join(normalized.trim_start_matches('/')).exists() {
+            return Err(format!("`{pattern}` is ignored, but does not exist"));
+        }
+        Ok(())
+    }
+
+    /// Returns whether the file at the given path, relative to the project's directory, is
+    /// ignored.
+    pub fn is_ignored(&self, relative_path: &Path) -> bool {
+        self.patterns
+            .iter()
+            .rev()
+            .find(|(glob, _)| glob.is_match(relative_path))
+            .is_some_and(|(_, negated)| !negated)
+    }
+}
@@ -0,0 +1,321 @@
+use crate::parse::{AstOrigin, EventType, ObjectEvent};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Returns the path to the `.yyp` file within the given directory, if there is one.
+pub fn find_yyp(directory: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(directory)
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|extension| extension == "yyp"))
+        .min()
+}
+
+/// Reads the contents of a `.yyp` file, returning the paths to the `.yy` file of every resource
+/// within the project, relative to the project's directory.
+///
+/// ### Errors
+/// Returns an error if the file is not valid json, or is missing its list of resources.
+pub fn yyp_resources(yyp: &str) -> Result<Vec<PathBuf>, std::io::Error> {
+    let project = read_json(yyp)?;
+    let resources = project
+        .get("resources")
+        .and_then(Value::as_array)
+        .ok_or_else(|| invalid_data("the project does not contain a list of resources"))?;
+    Ok(resources
+        .iter()
+        .filter_map(|resource| resource.get("id")?.get("path")?.as_str())
+        .map(PathBuf::from)
+        .collect())
+}
+
+/// Reads the contents of a resource's `.yy` file, returning the paths to every gml file that
+/// belongs to it, relative to the project's directory. Resources that contain no gml, such as
+/// sprites or notes, return nothing.
+///
+/// ### Errors
+/// Returns an error if the file is not valid json.
+pub fn resource_gml_paths(resource_path: &Path, yy: &str) -> Result<Vec<PathBuf>, std::io::Error> {
+    let resource = read_json(yy)?;
+    let file_names: Vec<String> = match resource_type(&resource) {
+        Some("GMScript") => resource_path
+            .file_stem()
+            .map(|name| format!("{}.gml", name.to_string_lossy()))
+            .into_iter()
+            .collect(),
+        Some("GMObject") => array(&resource, "eventList")
+            .filter_map(event_from_yy)
+            .map(|event| event_file_name(&event))
+            .collect(),
+        Some("GMTimeline") => array(&resource, "momentList")
+            .filter_map(|moment| moment.get("moment")?.as_u64())
+            .map(|moment| format!("moment_{moment}.gml"))
+            .collect(),
+        Some("GMExtension") => array(&resource, "files")
+            .filter_map(|file| file.get("filename")?.as_str())
+            .filter(|name| Path::new(name).extension().is_some_and(|extension| extension == "gml"))
+            .map(String::from)
+            .collect(),
+        Some("GMRoom") => instances_with_creation_code(&resource)
+            .into_iter()
+            .map(|name| format!("InstanceCreationCode_{name}.gml"))
+            .collect(),
+        _ => vec![],
+    };
+    let directory = resource_path.parent().unwrap_or_else(|| Path::new(""));
+    let mut paths: Vec<PathBuf> = file_names.into_iter().map(|name| directory.join(name)).collect();
+
+    // Unlike everything else, a room's creation code is stored relative to the project
+    if let Some(path) = resource
+        .get("creationCodeFile")
+        .and_then(Value::as_str)
+        .filter(|path| !path.is_empty())
+    {
+        paths.insert(0, PathBuf::from(path));
+    }
+    Ok(paths)
+}
+
+/// Returns the name of every resource within the project in the directory, such as its sprites
+/// and objects, along with the names its resources declare outside of gml: the functions and
+/// constants of its extensions, and the variables defined on its objects. Resources are found
+/// through the project's `.yyp` file, or by the directories they are stored in if it has none.
+///
+/// ### Errors
+/// Returns an error if the `.yyp` file cannot be read.
+pub fn asset_names(directory: &Path) -> Result<Vec<String>, std::io::Error> {
+    let resource_paths = match find_yyp(directory) {
+        Some(yyp_path) => yyp_resources(&std::fs::read_to_string(yyp_path)?)?,
+        None => RESOURCE_DIRECTORIES
+            .iter()
+            .filter_map(|category| Some((category, std::fs::read_dir(directory.join(category)).ok()?)))
+            .flat_map(|(category, entries)| {
+                entries
+                    .filter_map(Result::ok)
+                    .filter(|entry| entry.path().is_dir())
+                    .map(move |entry| {
+                        let name = entry.file_name().to_string_lossy().into_owned();
+                        Path::new(category).join(&name).join(format!("{name}.yy"))
+                    })
+            })
+            .collect(),
+    };
+    let mut names = vec![];
+    for resource_path in resource_paths {
+        if let Some(name) = resource_path.file_stem() {
+            names.push(name.to_string_lossy().into_owned());
+        }
+        if !resource_path.starts_with("extensions") && !resource_path.starts_with("objects") {
+            continue;
+        }
+
+        // Resources that cannot be read are already reported while discovering gml
+        let Ok(resource) = std::fs::read_to_string(directory.join(&resource_path)).and_then(|yy| read_json(&yy)) else {
+            continue;
+        };
+        names.extend(
+            array(&resource, "properties")
+                .filter_map(|property| property.get("varName")?.as_str())
+                .map(String::from),
+        );
+        names.extend(array(&resource, "files").flat_map(|file| {
+            array(file, "functions")
+                .chain(array(file, "constants"))
+                .filter_map(|declaration| declaration.get("name")?.as_str())
+                .map(String::from)
+        }));
+    }
+    Ok(names)
+}
+
+/// The directories GameMaker stores each kind of resource in.
+const RESOURCE_DIRECTORIES: [&str; 13] = [
+    "animcurves",
+    "extensions",
+    "fonts",
+    "objects",
+    "paths",
+    "rooms",
+    "scripts",
+    "sequences",
+    "shaders",
+    "sounds",
+    "sprites",
+    "tilesets",
+    "timelines",
+];
+
+/// Returns the type of a resource, such as `GMObject`. Older projects store this in a
+/// `resourceType` field, while newer ones use a key prefixed with `$`.
+fn resource_type(resource: &Value) -> Option<&str> {
+    resource
+        .get("resourceType")
+        .and_then(Value::as_str)
+        .or_else(|| resource.as_object()?.keys().find_map(|key| key.strip_prefix('$')))
+}
+
+/// Returns where in the project the gml file at the given path belongs, based on the name of the
+/// file and the resource directory it is within.
+pub fn ast_origin(path: &Path) -> AstOrigin {
+    let mut components = path.iter().rev().map(|component| component.to_string_lossy());
+    let (Some(_), Some(resource), Some(category)) = (components.next(), components.next(), components.next()) else {
+        return AstOrigin::Unknown;
+    };
+    let resource = resource.into_owned();
+    let file_stem = path.file_stem().map(|stem| stem.to_string_lossy());
+    match category.as_ref() {
+        "scripts" => AstOrigin::Script { name: resource },
+        // Rooms keep their own creation code alongside that of each instance placed within them
+        "rooms" => match file_stem.as_deref() {
+            Some("RoomCreationCode") => AstOrigin::RoomCreationCode { room: resource },
+            Some(stem) => stem
+                .strip_prefix("InstanceCreationCode_")
+                .map_or(AstOrigin::Unknown, |instance| AstOrigin::InstanceCreationCode {
+                    room: resource,
+                    instance: instance.to_string(),
+                }),
+            None => AstOrigin::Unknown,
+        },
+        "objects" => file_stem
+            .and_then(|stem| event_from_file_name(&stem))
+            .map_or(AstOrigin::Unknown, |event| AstOrigin::ObjectEvent {
+                object: resource,
+                event,
+            }),
+        _ => AstOrigin::Unknown,
+    }
+}
+
+/// Reads an event from an object's `.yy` file.
+fn event_from_yy(event: &Value) -> Option<ObjectEvent> {
+    let number = event.get("eventNum")?.as_u64()?;
+    let event_type = match event.get("eventType")?.as_u64()? {
+        0 => EventType::Create,
+        1 => EventType::Destroy,
+        2 => EventType::Alarm,
+        3 => EventType::Step,
+        4 => EventType::Collision(event.get("collisionObjectId")?.get("name")?.as_str()?.to_string()),
+        5 => EventType::Keyboard,
+        6 => EventType::Mouse,
+        7 => EventType::Other,
+        8 => EventType::Draw,
+        9 => EventType::KeyPress,
+        10 => EventType::KeyRelease,
+        12 => EventType::CleanUp,
+        13 => EventType::Gesture,
+        14 => EventType::PreCreate,
+        _ => return None,
+    };
+    Some(ObjectEvent { event_type, number })
+}
+
+/// Reads an event from the name of the file that contains its code, such as `Draw_64`.
+fn event_from_file_name(file_stem: &str) -> Option<ObjectEvent> {
+    let (name, suffix) = file_stem.split_once('_')?;
+    let event_type = match name {
+        "Collision" => {
+            return Some(ObjectEvent {
+                event_type: EventType::Collision(suffix.to_string()),
+                number: 0,
+            });
+        }
+        "Create" => EventType::Create,
+        "Destroy" => EventType::Destroy,
+        "Alarm" => EventType::Alarm,
+        "Step" => EventType::Step,
+        "Keyboard" => EventType::Keyboard,
+        "Mouse" => EventType::Mouse,
+        "Other" => EventType::Other,
+        "Draw" => EventType::Draw,
+        "KeyPress" => EventType::KeyPress,
+        "KeyRelease" => EventType::KeyRelease,
+        "CleanUp" => EventType::CleanUp,
+        "Gesture" => EventType::Gesture,
+        "PreCreate" => EventType::PreCreate,
+        _ => return None,
+    };
+    Some(ObjectEvent {
+        event_type,
+        number: suffix.parse().ok()?,
+    })
+}
+
+/// Returns the name of the gml file that contains the code for an object's event.
+fn event_file_name(event: &ObjectEvent) -> String {
+    let name = match &event.event_type {
+        EventType::Collision(object) => return format!("Collision_{object}.gml"),
+        EventType::Create => "Create",
+        EventType::Destroy => "Destroy",
+        EventType::Alarm => "Alarm",
+        EventType::Step => "Step",
+        EventType::Keyboard => "Keyboard",
+        EventType::Mouse => "Mouse",
+        EventType::Other => "Other",
+        EventType::Draw => "Draw",
+        EventType::KeyPress => "KeyPress",
+        EventType::KeyRelease => "KeyRelease",
+        EventType::CleanUp => "CleanUp",
+        EventType::Gesture => "Gesture",
+        EventType::PreCreate => "PreCreate",
+    };
+    format!("{name}_{}.gml", event.number)
+}
+
+/// Returns the names of every instance within a room's layers that has creation code.
+fn instances_with_creation_code(room: &Value) -> Vec<&str> {
+    fn visit<'a>(layer: &'a Value, names: &mut Vec<&'a str>) {
+        for instance in array(layer, "instances") {
+            if instance.get("hasCreationCode").and_then(Value::as_bool) == Some(true) {
+                names.extend(instance.get("name").and_then(Value::as_str));
+            }
+        }
+        for layer in array(layer, "layers") {
+            visit(layer, names);
+        }
+    }
+    let mut names = vec![];
+    visit(room, &mut names);
+    names
+}
+
+fn array<'a>(value: &'a Value, key: &str) -> impl Iterator<Item = &'a Value> {
+    value.get(key).and_then(Value::as_array).into_iter().flatten()
+}
+
+/// Parses one of GameMaker's json files, which are permitted to contain trailing commas.
+fn read_json(text: &str) -> Result<Value, std::io::Error> {
+    serde_json::from_str(&strip_trailing_commas(text)).map_err(|error| invalid_data(error.to_string()))
+}
+
+/// Removes every comma that is followed only by whitespace before a closing bracket.
+fn strip_trailing_commas(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.char_indices();
+    let mut in_string = false;
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => in_string = !in_string,
+            '\\' if in_string => {
+                output.push(c);
+                if let Some((_, escaped)) = chars.next() {
+                    output.push(escaped);
+                }
+                continue;
+            }
+            ',' if !in_string => {
+                let rest = text[i + 1..].trim_start();
+                if rest.starts_with('}') || rest.starts_with(']') {
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        output.push(c);
+    }
+    output
+}
+
+fn invalid_data(message: impl Into<String>) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message.into())
+}
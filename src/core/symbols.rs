@@ -0,0 +1,176 @@
+use crate::{FileId, parse::Location};
+use hashbrown::HashMap;
+
+/// Every name declared globally throughout a project, such as its functions, enums and macros,
+/// alongside where each was declared. This allows names to be resolved across files.
+///
+/// Enum members are not global names themselves, so they are found by their full path, such as
+/// `Direction.Left`.
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(from = "Vec<Symbol>", into = "Vec<Symbol>")]
+pub struct SymbolTable {
+    symbols: Vec<Symbol>,
+    by_name: HashMap<String, Vec<usize>>,
+}
+impl SymbolTable {
+    /// Records a symbol. Global variables and objects have no single declaration, so only the
+    /// first place they are seen is kept.
+    pub fn insert(&mut self, symbol: Symbol) {
+        let path = symbol.path();
+        if matches!(symbol.kind, SymbolKind::GlobalVariable | SymbolKind::Object)
+            && self.get(&path).any(|other| other.kind == symbol.kind)
+        {
+            return;
+        }
+        self.by_name.entry(path).or_default().push(self.symbols.len());
+        self.symbols.push(symbol);
+    }
+
+    /// Returns every symbol declared with the given name.
+    pub fn get(&self, name: &str) -> impl Iterator<Item = &Symbol> {
+        self.by_name
+            .get(name)
+            .into_iter()
+            .flatten()
+            .map(|index| &self.symbols[*index])
+    }
+
+    /// Returns the first symbol declared with the given name, if any.
+    pub fn find(&self, name: &str) -> Option<&Symbol> {
+        self.get(name).next()
+    }
+
+    /// Returns whether anything was declared with the given name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.by_name.contains_key(name)
+    }
+
+    /// Returns whether the name belongs to a global function, including constructors.
+    pub fn is_function(&self, name: &str) -> bool {
+        self.get(name).any(|symbol| symbol.kind.is_function())
+    }
+
+    /// Returns the enum with the given name, if one was declared.
+    pub fn find_enum(&self, name: &str) -> Option<&Symbol> {
+        self.get(name).find(|symbol| symbol.kind == SymbolKind::Enum)
+    }
+
+    /// Returns the members of the enum with the given name, in the order they were declared.
+    pub fn enum_members<'a>(&'a self, enum_name: &'a str) -> impl Iterator<Item = &'a Symbol> {
+        self.symbols.iter().filter(
+            move |symbol| matches!(&symbol.kind, SymbolKind::EnumMember { enum_name: name } if name == enum_name),
+        )
+    }
+
+    /// Returns every symbol in the table, in the order they were recorded.
+    pub fn iter(&self) -> impl Iterator<Item = &Symbol> {
+        self.symbols.iter()
+    }
+
+    /// Returns the number of symbols in the table.
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Returns whether the table contains no symbols.
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// Adds every symbol recorded in another table, such as that of a single file.
+    pub fn extend(&mut self, other: SymbolTable) {
+        other.symbols.into_iter().for_each(|symbol| self.insert(symbol));
+    }
+
+    /// Replaces every symbol declared within a file with those recorded in another table. Used
+    /// when a file's declarations are collected again after it changes.
+    pub fn replace_file(&mut self, file_id: FileId, other: SymbolTable) {
+        let symbols: Vec<Symbol> = std::mem::take(&mut self.symbols)
+            .into_iter()
+            .filter(|symbol| symbol.location.0 != file_id)
+            .collect();
+        *self = Self::from(symbols);
+        self.extend(other);
+    }
+
+    /// Moves every symbol to the given file. Used when reusing the symbols of a single file from a
+    /// previous run, in which the file may have had a different id.
+    pub fn set_file_id(&mut self, file_id: FileId) {
+        self.symbols.iter_mut().for_each(|symbol| symbol.location.0 = file_id);
+    }
+}
+impl From<Vec<Symbol>> for SymbolTable {
+    fn from(symbols: Vec<Symbol>) -> Self {
+        let mut table = Self::default();
+        symbols.into_iter().for_each(|symbol| table.insert(symbol));
+        table
+    }
+}
+impl From<SymbolTable> for Vec<Symbol> {
+    fn from(table: SymbolTable) -> Self {
+        table.symbols
+    }
+}
+
+/// A single name declared within a [SymbolTable].
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Symbol {
+    /// The name the symbol was declared with.
+    pub name: String,
+    /// What kind of declaration the symbol came from.
+    pub kind: SymbolKind,
+    /// Where the symbol was declared. Objects are not declared in gml, so theirs points to the
+    /// start of one of their events.
+    pub location: Location,
+}
+impl Symbol {
+    /// Creates a new symbol.
+    pub fn new(name: impl Into<String>, kind: SymbolKind, location: Location) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            location,
+        }
+    }
+
+    /// Returns the path the symbol is found by in a [SymbolTable], which is its name unless it is
+    /// an enum member.
+    pub fn path(&self) -> String {
+        match &self.kind {
+            SymbolKind::EnumMember { enum_name } => format!("{enum_name}.{}", self.name),
+            _ => self.name.clone(),
+        }
+    }
+}
+
+/// The different kinds of declarations recorded in a [SymbolTable].
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SymbolKind {
+    /// A function declared at the top level of a script.
+    Function,
+    /// A constructor declared at the top level of a script.
+    Constructor,
+    /// An enum.
+    Enum,
+    /// A member of an enum.
+    EnumMember {
+        /// The name of the enum the member belongs to.
+        enum_name: String,
+    },
+    /// A macro.
+    Macro {
+        /// The config the macro is bound to, if any.
+        config: Option<String>,
+    },
+    /// A global variable, declared with `globalvar` or by assigning to `global`.
+    GlobalVariable,
+    /// An object in the project.
+    Object,
+}
+impl SymbolKind {
+    /// Returns whether the symbol can be called as a function, which includes constructors.
+    pub fn is_function(&self) -> bool {
+        matches!(self, Self::Function | Self::Constructor)
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{Config, GmlLibrary, IgnoreList, RunSummary, cache::Cache, driver};
+use hashbrown::{HashMap, HashSet};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tokio::sync::mpsc::channel;
+
+/// A project held in memory between runs, so that as its files change only those files need to
+/// be loaded, parsed and linted again. Used by `duck run --watch`.
+///
+/// Each run still searches the project for its files, so that files which are added, removed, or
+/// newly ignored are noticed, but only loads the files it has not seen before or that it is told
+/// have changed. The workspace's [Cache] then runs the files that changed through the [driver]'s
+/// passes.
+#[derive(Debug)]
+pub struct Workspace {
+    project_directory: PathBuf,
+    config: Arc<Config>,
+    cache: Cache,
+    caching: bool,
+    sources: HashMap<PathBuf, &'static str>,
+}
+impl Workspace {
+    /// Creates a new workspace for the project. If `caching` is enabled, the workspace starts from
+    /// the cache within the project's directory, and writes back to it after every run.
+    pub fn new(project_directory: &Path, config: Config, caching: bool) -> Self {
+        let cache = if caching {
+            Cache::load(project_directory, &config)
+        } else {
+            Cache::new(&config)
+        };
+        Self {
+            project_directory: project_directory.to_path_buf(),
+            config: Arc::new(config),
+            cache,
+            caching,
+            sources: HashMap::new(),
+        }
+    }
+
+    /// Lints the project, reloading the given paths from disk alongside any files that were not
+    /// part of the previous run.
+    ///
+    /// ### Errors
+    /// Returns an error if we fail to join any of the tokio tasks.
+    ///
+    /// ### Panics
+    /// Panics if the receiver for the file loader closes. This should not be possible!
+    pub async fn run(&mut self, changed_paths: &[PathBuf]) -> Result<RunSummary, tokio::task::JoinError> {
+        let (ignore_list, mut warnings) = IgnoreList::load(&self.project_directory, &self.config.ignored_file_paths);
+        let (mut path_receiver, walker_handle) = driver::start_gml_discovery(&self.project_directory, ignore_list);
+        let mut paths = vec![];
+        while let Some(path) = path_receiver.recv().await {
+            paths.push(path);
+        }
+        let mut io_errors = walker_handle.await?;
+
+        // Only the files we have not seen, or that have changed, need to be loaded
+        let changed_paths: HashSet<PathBuf> = changed_paths.iter().map(|path| canonical(path)).collect();
+        let stale_paths: Vec<PathBuf> = paths
+            .iter()
+            .filter(|path| {
+                let path = canonical(path);
+                changed_paths.contains(&path) || !self.sources.contains_key(&path)
+            })
+            .cloned()
+            .collect();
+        let (stale_sender, stale_receiver) = channel::<PathBuf>(1000);
+        tokio::task::spawn(async move {
+            for path in stale_paths {
+                stale_sender.send(path).await.unwrap();
+            }
+        });
+        let (mut file_receiver, file_handle) = driver::start_file_load(stale_receiver);
+        let mut loaded = HashMap::new();
+        while let Some((_, gml, path)) = file_receiver.recv().await {
+            loaded.insert(path, gml);
+        }
+        let (_, _, mut load_errors) = file_handle.await?;
+        io_errors.append(&mut load_errors);
+
+        // Rebuild the library in the order the files were found, as the cache expects
+        let mut library = GmlLibrary::new();
+        let mut sources = HashMap::new();
+        let mut files = vec![];
+        let mut line_count = 0;
+        for path in paths {
+            let key = canonical(&path);
+            let Some(gml) = loaded.remove(&path).or_else(|| self.sources.get(&key).copied()) else {
+                continue;
+            };
+            line_count += gml.lines().count();
+            let file_id = library.add(key.to_string_lossy().to_string(), gml);
+            files.push((file_id, gml, path));
+            sources.insert(key, gml);
+        }
+        self.sources = sources;
+
+        let diagnostics = self
+            .cache
+            .update(files, self.config.clone(), &self.project_directory)
+            .await?;
+        if self.caching {
+            if let Err(error) = self.cache.save(&self.project_directory) {
+                warnings.push(format!("failed to write the cache: {error}"));
+            }
+        }
+        Ok(RunSummary::new(library, diagnostics, io_errors, warnings, line_count))
+    }
+
+    /// Get a reference to the workspace's config.
+    pub fn config(&self) -> &Config {
+        self.config.as_ref()
+    }
+}
+
+/// Returns the canonical form of a path, which is how files are identified between runs.
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
@@ -0,0 +1,819 @@
+use std::ops::Range;
+
+use crate::{
+    BraceStyle, Config, FormattingRules,
+    parse::{
+        Access, Assignment, Ast, Call, Constructor, DoUntil, Enum, Equality, Evaluation, EvaluationOp, Expr, ExprKind,
+        Field, For, Function, Globalvar, Grouping, If, LocalVariables, Logical, LogicalOp, Macro, NullCoalecence,
+        Postfix, Repeat, Return, Static, Stmt, StmtKind, Switch, TemplatePart, Ternary, Throw, Token, TokenKind,
+        TryCatch, Unary, UnaryOp, While, With,
+    },
+};
+
+/// Pretty-prints an [Ast] back into gml, following the formatting rules and keyword preferences in
+/// the provided [Config]. Comments, tags and regions are only preserved if the ast was parsed with
+/// [crate::parse::Parser::new_lossless]. The source is used to reproduce literals exactly as they
+/// were written.
+pub fn format_ast(ast: &Ast, source: &str, config: &Config) -> String {
+    let mut formatter = Formatter::new(source, config);
+    formatter.stmt_list(ast.stmts(), false, true);
+    formatter.own_line_comments(
+        ast.trailing_trivia(),
+        if ast.stmts().is_empty() { usize::MAX } else { 1 },
+    );
+    formatter.finish()
+}
+
+/// A piece of trivia that the formatter needs to keep.
+enum TriviaPiece {
+    Comment(String),
+    LineComment(String),
+    Region(String),
+    Newline,
+}
+
+struct Formatter<'a> {
+    source: &'a str,
+    config: &'a Config,
+    rules: &'a FormattingRules,
+    output: String,
+    depth: usize,
+    line_is_empty: bool,
+    needs_newline: bool,
+    /// The last character of an operator that was written without spaces around it, which must
+    /// be kept apart from a following `+` or `-` so that the two don't lex as `++` or `--`.
+    operator_end: Option<char>,
+}
+impl<'a> Formatter<'a> {
+    fn new(source: &'a str, config: &'a Config) -> Self {
+        Self {
+            source,
+            config,
+            rules: &config.formatting_rules,
+            output: String::new(),
+            depth: 0,
+            line_is_empty: true,
+            needs_newline: false,
+            operator_end: None,
+        }
+    }
+
+    fn finish(mut self) -> String {
+        let trimmed_length = self.output.trim_end().len();
+        self.output.truncate(trimmed_length);
+        if !self.output.is_empty() {
+            self.output.push('\n');
+        }
+        self.output
+    }
+
+    // Writing
+    /// Writes text to the current line, indenting it first if it is the start of the line.
+    fn push(&mut self, text: &str) {
+        if self.needs_newline {
+            // A line comment was written earlier on this line, so we need to move on
+            self.needs_newline = false;
+            self.depth += 1;
+            self.newline();
+            self.push(text);
+            self.depth -= 1;
+            return;
+        }
+        if self.line_is_empty {
+            self.line_is_empty = false;
+            for _ in 0..self.depth {
+                self.output.push_str(&self.rules.indentation());
+            }
+        }
+        if self.operator_end.take().is_some_and(|end| text.starts_with(end)) {
+            self.output.push(' ');
+        }
+        self.output.push_str(text);
+    }
+
+    /// Ends the current line.
+    fn newline(&mut self) {
+        let trimmed_length = self.output.trim_end_matches([' ', '\t']).len();
+        self.output.truncate(trimmed_length);
+        self.output.push('\n');
+        self.line_is_empty = true;
+        self.needs_newline = false;
+        self.operator_end = None;
+    }
+
+    /// Inserts an empty line, unless there already is one or we are at the start of the output.
+    fn blank_line(&mut self) {
+        if !self.output.is_empty() && !self.output.ends_with("\n\n") && !self.output.ends_with("{\n") {
+            self.output.push('\n');
+        }
+    }
+
+    /// Writes text at the end of the line that was most recently ended.
+    fn push_to_previous_line(&mut self, text: &str) {
+        if self.line_is_empty && self.output.ends_with('\n') {
+            self.output.pop();
+            self.output.push(' ');
+            self.output.push_str(text);
+            self.output.push('\n');
+        } else {
+            self.push(text);
+            self.newline();
+        }
+    }
+
+    fn push_operator(&mut self, operator: &str) {
+        let is_keyword = operator.chars().all(|c| c.is_alphabetic());
+        if self.rules.spaces_around_operators || is_keyword {
+            self.push(&format!(" {operator} "));
+        } else {
+            self.push(operator);
+            self.operator_end = operator.chars().last().filter(|c| matches!(c, '+' | '-'));
+        }
+    }
+
+    /// Separates a statement from its opening brace, based on the brace style.
+    fn brace_separator(&mut self) {
+        match self.rules.brace_style {
+            BraceStyle::SameLine => self.push(" "),
+            BraceStyle::NextLine => self.newline(),
+        }
+    }
+
+    /// Writes a keyword that continues a statement after a body, such as `else`.
+    fn continuation(&mut self, keyword: &str, body: &Stmt) {
+        if body.kind().as_block().is_some() && self.rules.brace_style == BraceStyle::SameLine {
+            self.push(&format!(" {keyword}"));
+        } else {
+            self.newline();
+            self.push(keyword);
+        }
+    }
+
+    // Trivia
+    fn text_of(&self, token: &Token) -> Option<String> {
+        let range = Range::from(token.span);
+        if !range.is_empty() && range.end <= self.source.len() {
+            Some(self.source[range].trim_end().to_string())
+        } else {
+            None
+        }
+    }
+
+    fn trivia_pieces(&self, tokens: &[Token]) -> Vec<TriviaPiece> {
+        tokens
+            .iter()
+            .filter_map(|token| match token.token_type {
+                TokenKind::Whitespace(whitespace) if whitespace.contains('\n') => Some(TriviaPiece::Newline),
+                TokenKind::Comment(comment) if comment.starts_with("//") => {
+                    Some(TriviaPiece::LineComment(comment.trim_end().to_string()))
+                }
+                TokenKind::Comment(comment) => Some(TriviaPiece::Comment(comment.to_string())),
+                TokenKind::Tag(..) | TokenKind::FileTag(..) => Some(TriviaPiece::LineComment(
+                    self.text_of(token).unwrap_or_else(|| token.to_string()),
+                )),
+                TokenKind::Region(region) => Some(TriviaPiece::Region(region.trim_end().to_string())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Writes the trivia found before a statement, each comment on its own line. Comments found
+    /// before the first newline were written on the line before the statement, and stay there.
+    /// Returns whether a blank line should precede the statement, which is the case once the
+    /// number of newlines between it and the previous comment meets the threshold.
+    fn leading_comments(&mut self, tokens: &[Token], continues_line: bool, mut blank_line_threshold: usize) -> bool {
+        let mut on_first_line = continues_line;
+        let mut newlines = 0;
+        for piece in self.trivia_pieces(tokens) {
+            match piece {
+                TriviaPiece::Newline => {
+                    newlines += 1;
+                    on_first_line = false;
+                }
+                TriviaPiece::Comment(text) | TriviaPiece::LineComment(text) if on_first_line => {
+                    self.push_to_previous_line(&text);
+                }
+                TriviaPiece::Comment(text) | TriviaPiece::LineComment(text) | TriviaPiece::Region(text) => {
+                    if newlines >= blank_line_threshold {
+                        self.blank_line();
+                    }
+                    self.push(&text);
+                    self.newline();
+                    newlines = 0;
+                    blank_line_threshold = 2;
+                }
+            }
+        }
+        newlines >= blank_line_threshold
+    }
+
+    /// Writes the trivia found after a statement. Comments on the statement's line are kept on it,
+    /// while the rest are written on their own lines.
+    fn trailing_comments(&mut self, tokens: &[Token]) {
+        let mut newlines = 0;
+        for piece in self.trivia_pieces(tokens) {
+            match piece {
+                TriviaPiece::Newline => newlines += 1,
+                TriviaPiece::Comment(text) | TriviaPiece::LineComment(text) if newlines == 0 => {
+                    self.push(&format!(" {text}"));
+                }
+                TriviaPiece::Comment(text) | TriviaPiece::LineComment(text) | TriviaPiece::Region(text) => {
+                    self.newline();
+                    if newlines >= 2 {
+                        self.blank_line();
+                    }
+                    self.push(&text);
+                    newlines = 0;
+                }
+            }
+        }
+    }
+
+    /// Returns whether the trivia contains anything that the formatter would write.
+    fn has_comments(&self, tokens: &[Token]) -> bool {
+        self.trivia_pieces(tokens)
+            .iter()
+            .any(|piece| !matches!(piece, TriviaPiece::Newline))
+    }
+
+    /// Writes each comment in the trivia on its own line, preceding the first with a blank line if
+    /// it is at least `blank_line_threshold` newlines away.
+    fn own_line_comments(&mut self, tokens: &[Token], mut blank_line_threshold: usize) {
+        let mut newlines = 0;
+        for piece in self.trivia_pieces(tokens) {
+            match piece {
+                TriviaPiece::Newline => newlines += 1,
+                TriviaPiece::Comment(text) | TriviaPiece::LineComment(text) | TriviaPiece::Region(text) => {
+                    if newlines >= blank_line_threshold {
+                        self.blank_line();
+                    }
+                    self.push(&text);
+                    self.newline();
+                    newlines = 0;
+                    blank_line_threshold = 2;
+                }
+            }
+        }
+    }
+
+    /// Writes comments in the middle of a line, such as those within an expression.
+    fn inline_comments(&mut self, tokens: &[Token], trailing: bool) {
+        for piece in self.trivia_pieces(tokens) {
+            match piece {
+                TriviaPiece::Newline => {}
+                TriviaPiece::Comment(text) if trailing => self.push(&format!(" {text}")),
+                TriviaPiece::Comment(text) => self.push(&format!("{text} ")),
+                TriviaPiece::LineComment(text) | TriviaPiece::Region(text) => {
+                    if trailing || self.line_is_empty {
+                        self.push(&format!(" {text}"));
+                    } else {
+                        self.push(&text);
+                    }
+                    self.needs_newline = true;
+                }
+            }
+        }
+    }
+
+    // Statements
+    /// Writes a series of statements, each on their own line.
+    fn stmt_list(&mut self, stmts: &[Stmt], continues_line: bool, top_level: bool) {
+        for (i, stmt) in stmts.iter().enumerate() {
+            let first = i == 0;
+            self.line_stmt(stmt, first && continues_line, first && (continues_line || top_level));
+            self.newline();
+        }
+    }
+
+    /// Writes a statement that begins its own line, along with the trivia around it.
+    fn line_stmt(&mut self, stmt: &Stmt, continues_line: bool, first: bool) {
+        let leading = stmt.trivia().map_or(&[][..], |trivia| &trivia.leading);
+        // The newline ending the previous statement's line is part of its trailing trivia
+        if self.leading_comments(leading, continues_line, if first { usize::MAX } else { 1 }) {
+            self.blank_line();
+        }
+        self.stmt(stmt, true);
+        if let Some(trivia) = stmt.trivia() {
+            if !matches!(
+                stmt.kind(),
+                StmtKind::Block(_) | StmtKind::Switch(_) | StmtKind::Enum(_)
+            ) && self.has_comments(&trivia.inner)
+            {
+                self.newline();
+                self.own_line_comments(&trivia.inner, usize::MAX);
+            }
+            self.trailing_comments(&trivia.trailing);
+        }
+    }
+
+    /// Writes a statement that sits within another statement, such as the initializer of a for
+    /// loop.
+    fn inline_stmt(&mut self, stmt: &Stmt, terminate: bool) {
+        if let Some(trivia) = stmt.trivia() {
+            self.inline_comments(&trivia.leading, false);
+        }
+        self.stmt(stmt, terminate);
+        if let Some(trivia) = stmt.trivia() {
+            self.inline_comments(&trivia.inner, true);
+            self.inline_comments(&trivia.trailing, true);
+        }
+    }
+
+    fn stmt(&mut self, stmt: &Stmt, terminate: bool) {
+        let semicolon = if terminate { ";" } else { "" };
+        match stmt.kind() {
+            StmtKind::Enum(Enum { name, members }) => {
+                self.push(&format!("enum {}", name.lexeme));
+                self.brace_separator();
+                self.push("{");
+                self.newline();
+                self.depth += 1;
+                for (i, member) in members.iter().enumerate() {
+                    let (name, trivia) = match member {
+                        Field::Uninitialized(expr) => (expr, expr.trivia()),
+                        Field::Initialized(stmt) => (member.name_expr(), stmt.trivia()),
+                    };
+                    let leading = trivia.map_or(&[][..], |trivia| &trivia.leading);
+                    // Members are separated by commas, so their leading trivia includes every newline
+                    if self.leading_comments(leading, true, if i == 0 { usize::MAX } else { 2 }) {
+                        self.blank_line();
+                    }
+                    self.push(&name.to_string());
+                    if let Some(value) = member.assignment_value() {
+                        self.push_operator("=");
+                        self.expr(value);
+                    }
+                    self.push(",");
+                    if let Some(trivia) = trivia {
+                        self.trailing_comments(&trivia.trailing);
+                    }
+                    self.newline();
+                }
+                if let Some(trivia) = stmt.trivia() {
+                    self.own_line_comments(&trivia.inner, if members.is_empty() { usize::MAX } else { 2 });
+                }
+                self.depth -= 1;
+                self.push("}");
+            }
+            StmtKind::Macro(Macro { name, config, body }) => {
+                if let Some(config) = config {
+                    self.push(&format!("#macro {config}:{} {body}", name.lexeme));
+                } else {
+                    self.push(&format!("#macro {} {body}", name.lexeme));
+                }
+            }
+            StmtKind::Globalvar(Globalvar { name }) => self.push(&format!("globalvar {}{semicolon}", name.lexeme)),
+            StmtKind::LocalVariables(LocalVariables { declarations }) => {
+                self.push("var ");
+                self.fields(declarations);
+                self.push(semicolon);
+            }
+            StmtKind::Static(Static { body }) => {
+                self.push("static ");
+                self.inline_stmt(body, terminate);
+            }
+            StmtKind::TryCatch(TryCatch {
+                try_body,
+                catch_expr,
+                catch_body,
+                finally_body,
+            }) => {
+                self.push("try");
+                self.body(try_body);
+                self.continuation("catch", try_body);
+                self.push(" ");
+                self.wrapped_condition(catch_expr, true);
+                self.body(catch_body);
+                if let Some(finally_body) = finally_body {
+                    self.continuation("finally", catch_body);
+                    self.body(finally_body);
+                }
+            }
+            StmtKind::For(For {
+                initializer,
+                condition,
+                iterator,
+                body,
+            }) => {
+                self.push("for (");
+                self.inline_stmt(initializer, false);
+                self.push("; ");
+                self.expr(condition);
+                self.push("; ");
+                self.inline_stmt(iterator, false);
+                self.push(")");
+                self.body(body);
+            }
+            StmtKind::With(With { identity, body }) => self.conditional("with", identity, body),
+            StmtKind::Repeat(Repeat { tick_counts, body }) => self.conditional("repeat", tick_counts, body),
+            StmtKind::While(While { condition, body }) => self.conditional("while", condition, body),
+            StmtKind::DoUntil(DoUntil { body, condition }) => {
+                self.push("do");
+                self.body(body);
+                self.continuation("until", body);
+                self.push(" ");
+                self.wrapped_condition(condition, true);
+                self.push(semicolon);
+            }
+            StmtKind::If(If {
+                condition,
+                body,
+                else_stmt,
+                ..
+            }) => {
+                self.conditional("if", condition, body);
+                if let Some(else_stmt) = else_stmt {
+                    self.continuation("else", body);
+                    if let StmtKind::If(_) = else_stmt.kind() {
+                        self.push(" ");
+                        self.inline_stmt(else_stmt, terminate);
+                    } else {
+                        self.body(else_stmt);
+                    }
+                }
+            }
+            StmtKind::Switch(Switch {
+                identity,
+                cases,
+                default_case,
+            }) => {
+                self.push("switch ");
+                self.wrapped_condition(identity, true);
+                self.brace_separator();
+                self.push("{");
+                self.newline();
+                self.depth += 1;
+                if let Some(trivia) = stmt.trivia() {
+                    self.own_line_comments(&trivia.inner, usize::MAX);
+                }
+                for case in cases {
+                    self.push("case ");
+                    self.expr(case.identity());
+                    self.push(":");
+                    let body: Vec<Stmt> = case.iter_body_statements().cloned().collect();
+                    self.case_body(&body);
+                }
+                if let Some(default_case) = default_case {
+                    self.push("default:");
+                    self.case_body(default_case);
+                }
+                self.depth -= 1;
+                self.push("}");
+            }
+            StmtKind::Block(block) => {
+                if block.body.is_empty() && stmt.trivia().is_none_or(|trivia| !self.has_comments(&trivia.inner)) {
+                    self.push("{}");
+                    return;
+                }
+                self.push("{");
+                self.newline();
+                self.depth += 1;
+                if let Some(trivia) = stmt.trivia() {
+                    self.own_line_comments(&trivia.inner, usize::MAX);
+                }
+                self.stmt_list(&block.body, true, false);
+                self.depth -= 1;
+                self.push("}");
+            }
+            StmtKind::Return(Return { value }) => {
+                self.push("return");
+                if let Some(value) = value {
+                    self.push(" ");
+                    self.expr(value);
+                }
+                self.push(semicolon);
+            }
+            StmtKind::Throw(Throw { value }) => {
+                self.push("throw ");
+                self.expr(value);
+                self.push(semicolon);
+            }
+            StmtKind::Delete(delete) => {
+                self.push("delete ");
+                self.expr(&delete.value);
+                self.push(semicolon);
+            }
+            StmtKind::Break => self.push(&format!("break{semicolon}")),
+            StmtKind::Continue => self.push(&format!("continue{semicolon}")),
+            StmtKind::Exit => self.push(&format!("exit{semicolon}")),
+            StmtKind::Assignment(Assignment { left, op, right }) => {
+                self.expr(left);
+                self.push_operator(&op.token().to_string());
+                self.expr(right);
+                self.push(semicolon);
+            }
+            StmtKind::Error => {
+                // We can't format what we couldn't parse, so it is kept exactly as it was written
+                let range = Range::from(stmt.span());
+                if range.end <= self.source.len() {
+                    self.push(self.source[range].trim());
+                }
+            }
+            StmtKind::Expr(expr) => {
+                self.expr(expr);
+                if !matches!(expr.kind(), ExprKind::Function(Function { name: Some(_), .. })) {
+                    self.push(semicolon);
+                }
+            }
+        }
+    }
+
+    /// Writes the body of a statement, such as the block following an if statement.
+    fn body(&mut self, body: &Stmt) {
+        if body.kind().as_block().is_some() {
+            if let Some(trivia) = body.trivia() {
+                self.inline_comments(&trivia.leading, true);
+            }
+            self.brace_separator();
+            self.stmt(body, true);
+        } else {
+            self.depth += 1;
+            self.newline();
+            self.line_stmt(body, true, true);
+            self.depth -= 1;
+        }
+    }
+
+    fn case_body(&mut self, body: &[Stmt]) {
+        self.newline();
+        self.depth += 1;
+        self.stmt_list(body, true, false);
+        self.depth -= 1;
+    }
+
+    /// Writes a statement made up of a keyword, a condition, and a body.
+    fn conditional(&mut self, keyword: &str, condition: &Expr, body: &Stmt) {
+        self.push(keyword);
+        self.push(" ");
+        self.wrapped_condition(condition, body.kind().as_block().is_some());
+        self.body(body);
+    }
+
+    /// Writes a condition, wrapping it in parenthesis or removing them based on the config. They
+    /// are only removed if `can_unwrap` is true, as some conditions are ambiguous without them.
+    fn wrapped_condition(&mut self, condition: &Expr, can_unwrap: bool) {
+        match condition.kind() {
+            ExprKind::Grouping(Grouping { inner, .. }) if !self.config.statement_parentheticals && can_unwrap => {
+                self.inline_trivia_around(condition, |formatter| formatter.expr(inner));
+            }
+            ExprKind::Grouping(_) => self.expr(condition),
+            _ if self.config.statement_parentheticals => {
+                self.push("(");
+                self.expr(condition);
+                self.push(")");
+            }
+            _ => self.expr(condition),
+        }
+    }
+
+    fn fields(&mut self, fields: &[Field]) {
+        for (i, field) in fields.iter().enumerate() {
+            if i != 0 {
+                self.push(", ");
+            }
+            match field {
+                Field::Uninitialized(expr) => self.expr(expr),
+                Field::Initialized(stmt) => self.inline_stmt(stmt, false),
+            }
+        }
+    }
+
+    // Expressions
+    fn inline_trivia_around(&mut self, expr: &Expr, write: impl FnOnce(&mut Self)) {
+        if let Some(trivia) = expr.trivia() {
+            self.inline_comments(&trivia.leading, false);
+        }
+        write(self);
+        if let Some(trivia) = expr.trivia() {
+            self.inline_comments(&trivia.inner, true);
+            self.inline_comments(&trivia.trailing, true);
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        self.inline_trivia_around(expr, |formatter| formatter.expr_kind(expr));
+    }
+
+    fn exprs(&mut self, exprs: &[Expr]) {
+        for (i, expr) in exprs.iter().enumerate() {
+            if i != 0 {
+                self.push(", ");
+            }
+            self.expr(expr);
+        }
+    }
+
+    fn expr_kind(&mut self, expr: &Expr) {
+        match expr.kind() {
+            ExprKind::Function(Function {
+                name,
+                parameters,
+                constructor,
+                body,
+            }) => {
+                self.push("function");
+                if let Some(name) = name {
+                    self.push(&format!(" {}", name.lexeme));
+                }
+                self.push("(");
+                self.fields(parameters);
+                self.push(")");
+                if let Some(Constructor { inheritance }) = constructor {
+                    if let Some(inheritance) = inheritance {
+                        self.push(" : ");
+                        self.expr(inheritance);
+                    }
+                    self.push(" constructor");
+                }
+                self.body(body);
+            }
+            ExprKind::Logical(Logical { left, op, right }) => {
+                let operator = match op {
+                    LogicalOp::And(_) if self.config.prefer_and_keyword() => "and".into(),
+                    LogicalOp::And(_) => "&&".into(),
+                    LogicalOp::Or(_) if self.config.prefer_or_keyword() => "or".into(),
+                    LogicalOp::Or(_) => "||".into(),
+                    LogicalOp::Xor(token) => token.to_string(),
+                };
+                self.binary(left, &operator, right);
+            }
+            ExprKind::Equality(Equality { left, op, right }) => self.binary(left, &op.to_string(), right),
+            ExprKind::Evaluation(Evaluation { left, op, right }) => {
+                let operator = match op {
+                    EvaluationOp::Modulo(_) if self.config.prefer_mod_keyword() => "mod".into(),
+                    EvaluationOp::Modulo(_) => "%".into(),
+                    op => op.to_string(),
+                };
+                self.binary(left, &operator, right);
+            }
+            ExprKind::NullCoalecence(NullCoalecence { left, right }) => self.binary(left, "??", right),
+            ExprKind::Ternary(Ternary {
+                condition,
+                true_value,
+                false_value,
+            }) => {
+                let can_unwrap = condition.kind().as_grouping().is_some_and(|grouping| {
+                    !matches!(
+                        grouping.inner.kind(),
+                        ExprKind::Ternary(_) | ExprKind::NullCoalecence(_)
+                    )
+                });
+                self.wrapped_condition(condition, can_unwrap);
+                self.push(" ? ");
+                self.expr(true_value);
+                self.push(" : ");
+                self.expr(false_value);
+            }
+            ExprKind::Unary(Unary { op, right }) => {
+                match op {
+                    UnaryOp::Not(_) if self.config.prefer_not_keyword() => self.push("not "),
+                    UnaryOp::Not(_) => self.push("!"),
+                    op => self.push(&op.to_string()),
+                }
+                // Prevents `- -a` from becoming `--a`
+                if let ExprKind::Unary(Unary { op: inner_op, .. }) = right.kind() {
+                    let collides = matches!(
+                        (op, inner_op),
+                        (
+                            UnaryOp::Negative(_) | UnaryOp::Decrement(_),
+                            UnaryOp::Negative(_) | UnaryOp::Decrement(_)
+                        ) | (
+                            UnaryOp::Positive(_) | UnaryOp::Increment(_),
+                            UnaryOp::Positive(_) | UnaryOp::Increment(_)
+                        )
+                    );
+                    if collides {
+                        self.push(" ");
+                    }
+                }
+                self.expr(right);
+            }
+            ExprKind::Postfix(Postfix { left, op }) => {
+                self.expr(left);
+                self.push(&op.to_string());
+            }
+            ExprKind::Access(access) => match access {
+                Access::Global { right } => self.push(&format!("global.{}", right.lexeme)),
+                Access::Identity { right } => self.push(&format!("self.{}", right.lexeme)),
+                Access::Other { right } => self.push(&format!("other.{}", right.lexeme)),
+                Access::Dot { left, right } => {
+                    self.expr(left);
+                    self.push(&format!(".{}", right.lexeme));
+                }
+                Access::Array {
+                    left,
+                    index_one,
+                    index_two,
+                    using_accessor,
+                } => {
+                    self.expr(left);
+                    self.push(if *using_accessor { "[@ " } else { "[" });
+                    self.expr(index_one);
+                    if let Some(index_two) = index_two {
+                        self.push(", ");
+                        self.expr(index_two);
+                    }
+                    self.push("]");
+                }
+                Access::Map { left, key } => self.accessor(left, "[? ", &[key]),
+                Access::Grid {
+                    left,
+                    index_one,
+                    index_two,
+                } => self.accessor(left, "[# ", &[index_one, index_two]),
+                Access::List { left, index } => self.accessor(left, "[| ", &[index]),
+                Access::Struct { left, key } => self.accessor(left, "[$ ", &[key]),
+            },
+            ExprKind::Call(Call {
+                left,
+                arguments,
+                uses_new,
+            }) => {
+                if *uses_new {
+                    self.push("new ");
+                }
+                self.expr(left);
+                self.push("(");
+                self.exprs(arguments);
+                self.push(")");
+            }
+            ExprKind::Grouping(Grouping { inner, .. }) => {
+                self.push("(");
+                self.expr(inner);
+                self.push(")");
+            }
+            ExprKind::Literal(literal) => match literal {
+                crate::parse::Literal::Template(parts) => {
+                    self.push("$\"");
+                    for part in parts {
+                        match part {
+                            TemplatePart::Text(text) => self.push(text),
+                            TemplatePart::Expr(expr) => {
+                                self.push("{");
+                                self.expr(expr);
+                                self.push("}");
+                            }
+                        }
+                    }
+                    self.push("\"");
+                }
+                crate::parse::Literal::Array(members) => {
+                    self.push("[");
+                    self.exprs(members);
+                    self.push("]");
+                }
+                crate::parse::Literal::Struct(fields) if fields.is_empty() => self.push("{}"),
+                crate::parse::Literal::Struct(fields) => {
+                    self.push("{ ");
+                    for (i, (name, value)) in fields.iter().enumerate() {
+                        if i != 0 {
+                            self.push(", ");
+                        }
+                        self.push(&format!("{}: ", name.lexeme));
+                        self.expr(value);
+                    }
+                    self.push(" }");
+                }
+                crate::parse::Literal::Hex(hex) => {
+                    let text = self.literal_text(expr).unwrap_or_else(|| format!("0x{hex}"));
+                    self.push(&text);
+                }
+                _ => {
+                    // Strings and numbers are written exactly as they were in the source, since
+                    // their original form (such as `@'verbatim'` strings) is not kept in the ast
+                    let text = self.literal_text(expr).unwrap_or_else(|| expr.to_string());
+                    self.push(&text);
+                }
+            },
+            ExprKind::Identifier(identifier) => self.push(&identifier.lexeme),
+        }
+    }
+
+    fn binary(&mut self, left: &Expr, operator: &str, right: &Expr) {
+        self.expr(left);
+        self.push_operator(operator);
+        self.expr(right);
+    }
+
+    fn accessor(&mut self, left: &Expr, opener: &str, indexes: &[&Expr]) {
+        self.expr(left);
+        self.push(opener);
+        for (i, index) in indexes.iter().enumerate() {
+            if i != 0 {
+                self.push(", ");
+            }
+            self.expr(index);
+        }
+        self.push("]");
+    }
+
+    /// Returns the text of a literal from the source, if its span is known.
+    fn literal_text(&self, expr: &Expr) -> Option<String> {
+        let range = Range::from(expr.span());
+        if !range.is_empty() && range.end <= self.source.len() {
+            Some(self.source[range].to_string())
+        } else {
+            None
+        }
+    }
+}
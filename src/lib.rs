@@ -14,12 +14,22 @@
 extern crate lazy_static;
 
 mod core {
+    /// Storing the results of previous runs, so that unchanged files are not linted again.
+    pub mod cache;
     /// The core operations duck executes to process a project.
     pub mod driver;
     mod duck;
+    /// Reading GameMaker's project files to find the gml within them.
+    pub mod project;
     pub use crate::core::duck::*;
     mod config;
     pub use config::*;
+    mod glob;
+    pub use glob::*;
+    mod symbols;
+    pub use symbols::*;
+    mod workspace;
+    pub use workspace::*;
 }
 pub use crate::core::*;
 
@@ -28,6 +38,8 @@ pub mod lint {
     #[allow(clippy::module_inception)]
     mod lint;
     pub use lint::*;
+    mod suggestion;
+    pub use suggestion::*;
 
     /// Collection of all of the various lints in duck.
     pub mod collection;
@@ -83,6 +95,7 @@ pub mod parse {
             mod r#macro;
             mod repeat;
             mod r#return;
+            mod r#static;
             mod switch;
             mod throw;
             mod try_catch;
@@ -100,6 +113,7 @@ pub mod parse {
             pub use r#macro::*;
             pub use repeat::*;
             pub use r#return::*;
+            pub use r#static::*;
             pub use switch::*;
             pub use throw::*;
             pub use try_catch::*;
@@ -120,17 +134,25 @@ pub mod parse {
     mod ast;
     mod lexer;
     mod parser;
+    mod trivia;
     mod utils;
     pub use ast::*;
     pub use gml::*;
     pub use lexer::*;
     pub use parser::*;
+    pub use trivia::*;
     pub use utils::*;
 
     #[cfg(test)]
     mod tests;
 }
 
+/// Formatting of gml code.
+pub mod format {
+    mod formatter;
+    pub use formatter::*;
+}
+
 /// Operations to perform static analysis on GML.
 #[allow(missing_docs)]
 #[allow(clippy::unimplemented)]
@@ -15,10 +15,14 @@ mod condition_wrapper;
 pub use condition_wrapper::ConditionWrapper;
 mod deprecated;
 pub use deprecated::Deprecated;
+mod draw_outside_draw_event;
+pub use draw_outside_draw_event::DrawOutsideDrawEvent;
 mod draw_sprite;
 pub use draw_sprite::DrawSprite;
 mod draw_text;
 pub use draw_text::DrawText;
+mod duplicate_declaration;
+pub use duplicate_declaration::DuplicateDeclaration;
 mod english_flavor_violation;
 pub use english_flavor_violation::EnglishFlavorViolation;
 mod exit;
@@ -71,8 +75,14 @@ mod try_catch;
 pub use try_catch::TryCatch;
 mod unassigned_constructor;
 pub use unassigned_constructor::UnassignedConstructor;
+mod undefined_identifier;
+pub use undefined_identifier::UndefinedIdentifier;
 mod unnecessary_grouping;
 pub use unnecessary_grouping::UnnecessaryGrouping;
+mod unused_allow;
+pub use unused_allow::UnusedAllow;
+mod unused_function;
+pub use unused_function::UnusedFunction;
 mod unused_local_variable;
 pub use unused_local_variable::UnusedLocalVariable;
 mod unused_parameter;
@@ -1,9 +1,9 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 use crate::{
-    Config, FileId,
-    lint::{EarlyExprPass, Lint, LintLevel},
-    parse::{Call, Expr, ExprKind, Literal},
+    Config,
+    lint::{EarlyExprPass, Lint, LintLevel, Report, Suggestion},
+    parse::{Call, Expr, ExprKind, Literal, Span},
 };
 
 #[derive(Debug, PartialEq)]
@@ -22,8 +22,40 @@ impl Lint for AccessorAlternative {
     }
 }
 
+impl AccessorAlternative {
+    /// Returns the suggestions needed to replace a call with an accessor, such as replacing
+    /// `ds_list_find_value(list, 0)` with `list[| 0]`.
+    fn accessor_suggestions(call: &Expr, arguments: &[Expr], opener: &str, arity: usize) -> Vec<Suggestion> {
+        if arguments.len() != arity || !arguments[0].kind().binds_tightly() {
+            return vec![];
+        }
+        let file_id = call.file_id();
+        let mut suggestions = vec![
+            Suggestion::remove(file_id, Span::new(call.span().start(), arguments[0].span().start())),
+            Suggestion::new(
+                file_id,
+                Span::new(arguments[0].span().end(), arguments[1].span().start()),
+                opener,
+            ),
+        ];
+        for pair in arguments[1..].windows(2) {
+            suggestions.push(Suggestion::new(
+                file_id,
+                Span::new(pair[0].span().end(), pair[1].span().start()),
+                ", ",
+            ));
+        }
+        suggestions.push(Suggestion::new(
+            file_id,
+            Span::new(arguments[arity - 1].span().end(), call.span().end()),
+            "]",
+        ));
+        suggestions
+    }
+}
+
 impl EarlyExprPass for AccessorAlternative {
-    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Report>) {
         if let ExprKind::Call(Call { left, arguments, .. }) = expr.kind() {
             if let ExprKind::Identifier(identifier) = left.kind() {
                 reports.push(match identifier.lexeme.as_ref() {
@@ -32,26 +64,30 @@ impl EarlyExprPass for AccessorAlternative {
                         .with_labels(vec![
                             Label::primary(expr.file_id(), expr.span())
                                 .with_message("this can be replaced with an accessor syntax (`list[| index]`"),
-                        ]),
+                        ])
+                        .with_suggestions(Self::accessor_suggestions(expr, arguments, "[| ", 2)),
 
                     "ds_grid_get" => Self::diagnostic(config)
                         .with_message("Use of `ds_grid_get`")
                         .with_labels(vec![
                             Label::primary(expr.file_id(), expr.span())
                                 .with_message("this can be replaced with an accessor syntax (`grid[# x, y]`"),
-                        ]),
+                        ])
+                        .with_suggestions(Self::accessor_suggestions(expr, arguments, "[# ", 3)),
                     "ds_map_find_value" => Self::diagnostic(config)
                         .with_message("Use of `ds_map_find_value`")
                         .with_labels(vec![
                             Label::primary(expr.file_id(), expr.span())
                                 .with_message("this can be replaced with an accessor syntax (`map[? key]`"),
-                        ]),
+                        ])
+                        .with_suggestions(Self::accessor_suggestions(expr, arguments, "[? ", 2)),
                     "array_get" => Self::diagnostic(config)
                         .with_message("Use of `array_get`")
                         .with_labels(vec![
                             Label::primary(expr.file_id(), expr.span())
                                 .with_message("this can be replaced with an accessor syntax (`array[index]`"),
-                        ]),
+                        ])
+                        .with_suggestions(Self::accessor_suggestions(expr, arguments, "[", 2)),
                     "variable_struct_get" => {
                         let argument_one = arguments.get(1);
                         match argument_one {
@@ -61,6 +97,22 @@ impl EarlyExprPass for AccessorAlternative {
                                     ExprKind::Literal(Literal::String(string))
                                         if string.chars().all(|v| v.is_alphanumeric() || v == '_') =>
                                     {
+                                        let suggestions = if arguments.len() == 2 && arguments[0].kind().binds_tightly()
+                                        {
+                                            vec![
+                                                Suggestion::remove(
+                                                    expr.file_id(),
+                                                    Span::new(expr.span().start(), arguments[0].span().start()),
+                                                ),
+                                                Suggestion::new(
+                                                    expr.file_id(),
+                                                    Span::new(arguments[0].span().end(), expr.span().end()),
+                                                    format!(".{string}"),
+                                                ),
+                                            ]
+                                        } else {
+                                            vec![]
+                                        };
                                         Self::diagnostic(config)
                                             .with_message("Use of `variable_struct_get`")
                                             .with_labels(vec![
@@ -68,6 +120,7 @@ impl EarlyExprPass for AccessorAlternative {
                                                     "this can be replaced with a dot access syntax (`struct.property`)",
                                                 ),
                                             ])
+                                            .with_suggestions(suggestions)
                                     }
 
                                     // If not, they could still use the $ accessor
@@ -75,7 +128,8 @@ impl EarlyExprPass for AccessorAlternative {
                                         .with_message("Use of `variable_struct_get`")
                                         .with_labels(vec![Label::primary(expr.file_id(), expr.span()).with_message(
                                             "this can be replaced with an accessor syntax (`struct[$ key]`)",
-                                        )]),
+                                        )])
+                                        .with_suggestions(Self::accessor_suggestions(expr, arguments, "[$ ", 2)),
                                 }
                             }
                             None => return, // missing argument, invalid gml -- we will validate this in the future
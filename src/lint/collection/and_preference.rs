@@ -1,8 +1,8 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 use crate::{
-    Config, FileId,
-    lint::{EarlyExprPass, Lint, LintLevel},
+    Config,
+    lint::{EarlyExprPass, Lint, LintLevel, Report, Suggestion},
     parse::{Expr, ExprKind, Logical, LogicalOp, TokenKind},
 };
 
@@ -22,20 +22,33 @@ impl Lint for AndPreference {
     }
 }
 impl EarlyExprPass for AndPreference {
-    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Report>) {
         if let ExprKind::Logical(Logical {
             op: LogicalOp::And(token),
-            ..
+            left,
+            right,
         }) = expr.kind()
         {
             if config.prefer_and_keyword() && token.token_type != TokenKind::And {
-                reports.push(Self::diagnostic(config).with_message("Use of `&&`").with_labels(vec![
-                    Label::primary(expr.file_id(), token.span).with_message("use the `and` keyword instead of `&&`"),
-                ]));
-            } else if token.token_type == TokenKind::And {
-                reports.push(Self::diagnostic(config).with_message("Use of `and`").with_labels(vec![
-                    Label::primary(expr.file_id(), token.span).with_message("use the `&&` opreator instead of `and`"),
-                ]));
+                reports.push(
+                    Self::diagnostic(config)
+                        .with_message("Use of `&&`")
+                        .with_labels(vec![
+                            Label::primary(expr.file_id(), token.span)
+                                .with_message("use the `and` keyword instead of `&&`"),
+                        ])
+                        .with_suggestions(vec![Suggestion::replace_operator(token, Some(left), right, "and")]),
+                );
+            } else if !config.prefer_and_keyword() && token.token_type == TokenKind::And {
+                reports.push(
+                    Self::diagnostic(config)
+                        .with_message("Use of `and`")
+                        .with_labels(vec![
+                            Label::primary(expr.file_id(), token.span)
+                                .with_message("use the `&&` opreator instead of `and`"),
+                        ])
+                        .with_suggestions(vec![Suggestion::replace_operator(token, Some(left), right, "&&")]),
+                );
             }
         }
     }
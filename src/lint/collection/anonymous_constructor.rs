@@ -1,8 +1,8 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 use crate::{
-    Config, FileId,
-    lint::{EarlyExprPass, Lint, LintLevel},
+    Config,
+    lint::{EarlyExprPass, Lint, LintLevel, Report},
     parse::{Expr, ExprKind, Function},
 };
 
@@ -23,7 +23,7 @@ impl Lint for AnonymousConstructor {
 }
 
 impl EarlyExprPass for AnonymousConstructor {
-    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Report>) {
         if let ExprKind::Function(Function {
             name: None,
             constructor: Some(_),
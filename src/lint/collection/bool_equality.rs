@@ -1,9 +1,9 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 use crate::{
-    Config, FileId,
-    lint::{EarlyExprPass, Lint, LintLevel},
-    parse::{Equality, EqualityOp, Expr, ExprKind, Literal},
+    Config,
+    lint::{EarlyExprPass, Lint, LintLevel, Report, Suggestion},
+    parse::{Equality, EqualityOp, Expr, ExprKind, Literal, Span},
 };
 
 #[derive(Debug, PartialEq)]
@@ -22,8 +22,26 @@ impl Lint for BoolEquality {
     }
 }
 
+impl BoolEquality {
+    /// Returns the suggestions needed to replace `foo == false` with `!foo`.
+    fn negation(left: &Expr, comparison: Suggestion, config: &Config) -> Vec<Suggestion> {
+        let not = if config.prefer_not_keyword() { "not " } else { "!" };
+        if left.kind().binds_tightly() {
+            vec![Suggestion::insert(left.file_id(), left.span().start(), not), comparison]
+        } else {
+            vec![
+                Suggestion::insert(left.file_id(), left.span().start(), format!("{not}(")),
+                Suggestion {
+                    replacement: ")".into(),
+                    ..comparison
+                },
+            ]
+        }
+    }
+}
+
 impl EarlyExprPass for BoolEquality {
-    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Report>) {
         if let ExprKind::Equality(Equality {
             left,
             op: EqualityOp::Equal(token),
@@ -31,12 +49,14 @@ impl EarlyExprPass for BoolEquality {
         }) = expr.kind()
         {
             if let Some(literal) = right.kind().as_literal() {
+                let comparison = Suggestion::remove(expr.file_id(), Span::new(left.span().end(), right.span().end()));
                 reports.push(match literal {
                     Literal::True => Self::diagnostic(config)
                         .with_message("Equality check with `true`")
                         .with_labels(vec![
                             Label::primary(right.file_id(), right.span()).with_message("this can be omitted"),
-                        ]),
+                        ])
+                        .with_suggestions(vec![comparison]),
                     Literal::False => Self::diagnostic(config)
                         .with_message("Equality check with `false`")
                         .with_labels(vec![
@@ -44,7 +64,8 @@ impl EarlyExprPass for BoolEquality {
                                 .with_message("this can be omitted..."),
                             Label::secondary(left.file_id(), left.span().start()..left.span().start())
                                 .with_message("...if you add a not operator here (`!`, `not`)"),
-                        ]),
+                        ])
+                        .with_suggestions(Self::negation(left, comparison, config)),
                     _ => return,
                 });
             }
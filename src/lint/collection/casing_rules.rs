@@ -1,9 +1,9 @@
 use crate::{
     Casing, Config, FileId,
-    lint::{EarlyExprPass, EarlyStmtPass, Lint, LintLevel},
+    lint::{EarlyExprPass, EarlyStmtPass, Lint, LintLevel, Report},
     parse::{Access, Expr, ExprKind, Function, Globalvar, Identifier, Literal, LocalVariables, Macro, Stmt, StmtKind},
 };
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 #[derive(Debug, PartialEq)]
 pub struct CasingRules;
@@ -22,13 +22,7 @@ impl Lint for CasingRules {
 }
 
 impl CasingRules {
-    fn check_for(
-        identifier: &Identifier,
-        casing: Casing,
-        file_id: FileId,
-        config: &Config,
-        reports: &mut Vec<Diagnostic<FileId>>,
-    ) {
+    fn check_for(identifier: &Identifier, casing: Casing, file_id: FileId, config: &Config, reports: &mut Vec<Report>) {
         if let Some(ideal) = casing.test(&identifier.lexeme) {
             reports.push(
                 Self::diagnostic(config)
@@ -43,7 +37,7 @@ impl CasingRules {
 }
 
 impl EarlyExprPass for CasingRules {
-    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Diagnostic<crate::FileId>>) {
+    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Report>) {
         match expr.kind() {
             ExprKind::Function(Function {
                 name: Some(name),
@@ -85,7 +79,7 @@ impl EarlyExprPass for CasingRules {
 }
 
 impl EarlyStmtPass for CasingRules {
-    fn visit_stmt_early(stmt: &Stmt, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_stmt_early(stmt: &Stmt, config: &Config, reports: &mut Vec<Report>) {
         match stmt.kind() {
             StmtKind::Enum(gml_enum) => {
                 Self::check_for(
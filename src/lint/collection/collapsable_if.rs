@@ -1,8 +1,8 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 use crate::{
-    Config, FileId,
-    lint::{EarlyStmtPass, Lint, LintLevel},
+    Config,
+    lint::{EarlyStmtPass, Lint, LintLevel, Report},
     parse::{If, Stmt, StmtKind},
 };
 
@@ -23,7 +23,7 @@ impl Lint for CollapsableIf {
 }
 
 impl EarlyStmtPass for CollapsableIf {
-    fn visit_stmt_early(stmt: &Stmt, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_stmt_early(stmt: &Stmt, config: &Config, reports: &mut Vec<Report>) {
         if let StmtKind::If(If {
             body: first_body,
             else_stmt,
@@ -39,7 +39,7 @@ impl EarlyStmtPass for CollapsableIf {
     }
 }
 
-fn inner(stmt: &Stmt, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+fn inner(stmt: &Stmt, config: &Config, reports: &mut Vec<Report>) {
     if let Some(block) = stmt.kind().as_block().filter(|block| block.body.len() == 1) {
         let nested_stmt = block.body.first().unwrap();
         if let StmtKind::If(If { else_stmt: None, .. }) = nested_stmt.kind() {
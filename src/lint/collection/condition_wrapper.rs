@@ -1,8 +1,8 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 use crate::{
-    Config, FileId,
-    lint::{EarlyExprPass, EarlyStmtPass, Lint, LintLevel},
+    Config,
+    lint::{EarlyExprPass, EarlyStmtPass, Lint, LintLevel, Report},
     parse::{DoUntil, Expr, ExprKind, If, Repeat, Stmt, StmtKind, Switch, Ternary, While, With},
 };
 
@@ -23,7 +23,7 @@ impl Lint for ConditionWrapper {
 }
 
 impl ConditionWrapper {
-    pub fn test(expr: &Expr, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    pub fn test(expr: &Expr, config: &Config, reports: &mut Vec<Report>) {
         if let Some(grouping) = expr.kind().as_grouping() {
             let (left_token, right_token) = grouping.parenthesis();
             if !config.statement_parentheticals {
@@ -49,7 +49,7 @@ impl ConditionWrapper {
 }
 
 impl EarlyExprPass for ConditionWrapper {
-    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Report>) {
         if let ExprKind::Ternary(Ternary { condition, .. }) = expr.kind() {
             Self::test(condition, config, reports)
         }
@@ -57,7 +57,7 @@ impl EarlyExprPass for ConditionWrapper {
 }
 
 impl EarlyStmtPass for ConditionWrapper {
-    fn visit_stmt_early(stmt: &Stmt, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_stmt_early(stmt: &Stmt, config: &Config, reports: &mut Vec<Report>) {
         match stmt.kind() {
             StmtKind::Switch(Switch { identity: expr, .. })
             | StmtKind::If(If { condition: expr, .. })
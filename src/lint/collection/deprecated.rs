@@ -1,9 +1,8 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 use crate::{
-    FileId,
-    lint::{EarlyExprPass, EarlyStmtPass, Lint, LintLevel},
-    parse::{Access, Call, Expr, ExprKind, Globalvar, Stmt, StmtKind},
+    lint::{EarlyExprPass, EarlyStmtPass, Lint, LintLevel, Report, Suggestion},
+    parse::{Access, Call, Expr, ExprKind, Globalvar, Span, Stmt, StmtKind},
 };
 
 #[derive(Debug, PartialEq)]
@@ -23,7 +22,7 @@ impl Lint for Deprecated {
 }
 
 impl EarlyStmtPass for Deprecated {
-    fn visit_stmt_early(stmt: &Stmt, config: &crate::Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_stmt_early(stmt: &Stmt, config: &crate::Config, reports: &mut Vec<Report>) {
         if let StmtKind::Globalvar(Globalvar { name }) = stmt.kind() {
             reports.push(
                 Self::diagnostic(config)
@@ -38,32 +37,57 @@ impl EarlyStmtPass for Deprecated {
 }
 
 impl EarlyExprPass for Deprecated {
-    fn visit_expr_early(expr: &Expr, config: &crate::Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_expr_early(expr: &Expr, config: &crate::Config, reports: &mut Vec<Report>) {
         if let ExprKind::Call(Call { left, .. }) = expr.kind() {
             if let ExprKind::Identifier(identifier) = left.kind() {
                 if gm_deprecated_functions().contains(&identifier.lexeme.as_str()) {
+                    let suggestions = gm_deprecated_function_replacement(&identifier.lexeme)
+                        .map(|replacement| vec![Suggestion::new(left.file_id(), left.span(), replacement)])
+                        .unwrap_or_default();
                     reports.push(
                         Self::diagnostic(config)
                             .with_message(format!("Use of deprecated function: {}", identifier.lexeme))
                             .with_labels(vec![
                                 Label::primary(left.file_id(), left.span()).with_message("this function is deprecated"),
-                            ]),
+                            ])
+                            .with_suggestions(suggestions),
                     );
                 }
             }
-        } else if let ExprKind::Access(Access::Array { index_two: Some(_), .. }) = expr.kind() {
+        } else if let ExprKind::Access(Access::Array {
+            index_one,
+            index_two: Some(index_two),
+            ..
+        }) = expr.kind()
+        {
             reports.push(
                 Self::diagnostic(config)
                     .with_message("Use of 2d array")
                     .with_labels(vec![
                         Label::primary(expr.file_id(), expr.span())
                             .with_message("use chained arrays instead (`foo[0][0]`)"),
-                    ]),
+                    ])
+                    .with_suggestions(vec![Suggestion::new(
+                        expr.file_id(),
+                        Span::new(index_one.span().end(), index_two.span().start()),
+                        "][",
+                    )]),
             );
         }
     }
 }
 
+/// Returns the function that directly replaces a deprecated function, if there is one.
+fn gm_deprecated_function_replacement(name: &str) -> Option<&'static str> {
+    match name {
+        "variable_struct_get" => Some("struct_get"),
+        "variable_struct_set" => Some("struct_set"),
+        "variable_struct_get_names" => Some("struct_get_names"),
+        "variable_struct_names_count" => Some("struct_names_count"),
+        _ => None,
+    }
+}
+
 fn gm_deprecated_functions() -> &'static [&'static str] {
     &[
         "array_height_2d",
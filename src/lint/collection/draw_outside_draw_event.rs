@@ -0,0 +1,87 @@
+use codespan_reporting::diagnostic::Label;
+
+use crate::{
+    Config, FileId,
+    lint::{AstPass, Lint, LintLevel, Report},
+    parse::{Ast, AstOrigin, Call, EventType, Expr, ExprKind, ParseVisitor, Span, Stmt},
+};
+
+#[derive(Debug, PartialEq)]
+pub struct DrawOutsideDrawEvent;
+impl Lint for DrawOutsideDrawEvent {
+    fn explanation() -> &'static str {
+        "Outside of an object's Draw events, the `draw_*` functions draw onto the application surface before it is cleared for the next frame, so they rarely show anything. Drawing onto a surface set with `surface_set_target` is the exception, and is not reported."
+    }
+
+    fn default_level() -> LintLevel {
+        LintLevel::Warn
+    }
+
+    fn tag() -> &'static str {
+        "draw_outside_draw_event"
+    }
+}
+
+impl AstPass for DrawOutsideDrawEvent {
+    fn visit_ast(ast: &Ast, config: &Config, reports: &mut Vec<Report>) {
+        // Scripts and the like can be called from anywhere, so only events can be checked
+        let AstOrigin::ObjectEvent { event, .. } = ast.origin() else {
+            return;
+        };
+        if event.event_type == EventType::Draw {
+            return;
+        }
+        let mut calls = vec![];
+        for stmt in ast.stmts() {
+            visit_stmt(stmt, &mut calls);
+        }
+
+        // Events that draw onto their own surfaces are drawing on purpose
+        if calls.iter().any(|(name, ..)| name.starts_with("surface_set_target")) {
+            return;
+        }
+        for (name, file_id, left) in calls.into_iter().filter(|(name, ..)| is_draw_function(name)) {
+            reports.push(
+                Self::diagnostic(config)
+                    .with_message(format!("Use of `{name}` outside of a Draw event"))
+                    .with_labels(vec![
+                        Label::primary(file_id, left)
+                            .with_message("this will not be visible unless it is drawn onto a surface"),
+                    ]),
+            );
+        }
+    }
+}
+
+/// Collects the name and location of every function called within the statement.
+fn visit_stmt(stmt: &Stmt, calls: &mut Vec<(String, FileId, Span)>) {
+    stmt.visit_child_stmts(|stmt| visit_stmt(stmt, calls));
+    stmt.visit_child_exprs(|expr| visit_expr(expr, calls));
+}
+
+/// Collects the name and location of every function called within the expression.
+fn visit_expr(expr: &Expr, calls: &mut Vec<(String, FileId, Span)>) {
+    if let ExprKind::Call(Call { left, .. }) = expr.kind() {
+        if let ExprKind::Identifier(identifier) = left.kind() {
+            calls.push((identifier.lexeme.clone(), left.file_id(), left.span()));
+        }
+    }
+    expr.visit_child_stmts(|stmt| visit_stmt(stmt, calls));
+    expr.visit_child_exprs(|expr| visit_expr(expr, calls));
+}
+
+/// Returns whether the function draws something. Functions that only read or change the draw
+/// state, such as `draw_set_color`, are commonly used elsewhere and are not included, and neither
+/// is `draw_clear`, which is how surfaces are prepared.
+fn is_draw_function(name: &str) -> bool {
+    const NON_DRAWING_PREFIXES: [&str; 7] = [
+        "draw_set_",
+        "draw_get_",
+        "draw_clear",
+        "draw_enable_",
+        "draw_light_",
+        "draw_flush",
+        "draw_texture_flush",
+    ];
+    name.starts_with("draw_") && !NON_DRAWING_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+}
@@ -1,8 +1,7 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 use crate::{
-    FileId,
-    lint::{EarlyExprPass, Lint, LintLevel},
+    lint::{EarlyExprPass, Lint, LintLevel, Report},
     parse::{Call, Expr, ExprKind},
 };
 
@@ -23,7 +22,7 @@ impl Lint for DrawSprite {
 }
 
 impl EarlyExprPass for DrawSprite {
-    fn visit_expr_early(expr: &Expr, config: &crate::Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_expr_early(expr: &Expr, config: &crate::Config, reports: &mut Vec<Report>) {
         if let ExprKind::Call(Call { left, .. }) = expr.kind() {
             if let ExprKind::Identifier(identifier) = left.kind() {
                 if gm_draw_sprite_functions().contains(&identifier.lexeme.as_str()) {
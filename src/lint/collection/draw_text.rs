@@ -1,8 +1,8 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 use crate::{
-    Config, FileId,
-    lint::{EarlyExprPass, Lint, LintLevel},
+    Config,
+    lint::{EarlyExprPass, Lint, LintLevel, Report},
     parse::{Call, Expr, ExprKind},
 };
 
@@ -23,7 +23,7 @@ impl Lint for DrawText {
 }
 
 impl EarlyExprPass for DrawText {
-    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Report>) {
         if let ExprKind::Call(Call { left, .. }) = expr.kind() {
             if let ExprKind::Identifier(identifier) = left.kind() {
                 if gm_draw_text_functions().contains(&identifier.lexeme.as_str()) {
@@ -0,0 +1,95 @@
+use codespan_reporting::diagnostic::Label;
+
+use crate::{
+    Config, Symbol, SymbolKind,
+    driver::Ctx,
+    lint::{LateAstPass, Lint, LintLevel, Report},
+    parse::{Ast, Location},
+};
+
+#[derive(Debug, PartialEq)]
+pub struct DuplicateDeclaration;
+impl Lint for DuplicateDeclaration {
+    fn explanation() -> &'static str {
+        "GameMaker refuses to compile a project that declares the same global function, enum, enum member or macro more than once."
+    }
+
+    fn default_level() -> LintLevel {
+        LintLevel::Deny
+    }
+
+    fn tag() -> &'static str {
+        "duplicate_declaration"
+    }
+}
+
+impl LateAstPass for DuplicateDeclaration {
+    fn visit_ast_late(ast: &Ast, config: &Config, ctx: &Ctx, reports: &mut Vec<Report>) {
+        // Each declaration is reported in its own file, so that it can be allowed where it is made
+        let mut declarations = Ctx::default();
+        declarations.collect_ast(ast);
+        for symbol in declarations.symbols.iter() {
+            let path = symbol.path();
+            let duplicates: Vec<&Symbol> = ctx
+                .symbols
+                .get(&path)
+                .filter(|other| is_same_declaration(&symbol.kind, &other.kind))
+                .collect();
+            if duplicates.len() < 2 {
+                continue;
+            }
+
+            // A duplicated enum duplicates all of its members too, which would only be noise
+            if let SymbolKind::EnumMember { enum_name } = &symbol.kind {
+                if ctx
+                    .symbols
+                    .get(enum_name)
+                    .filter(|other| other.kind == SymbolKind::Enum)
+                    .count()
+                    > 1
+                {
+                    continue;
+                }
+            }
+
+            let Location(file_id, span) = symbol.location;
+            let mut labels = vec![Label::primary(file_id, span).with_message(format!("`{path}` is declared here..."))];
+            labels.extend(
+                duplicates
+                    .iter()
+                    .filter(|other| other.location != symbol.location)
+                    .map(|other| {
+                        let Location(file_id, span) = other.location;
+                        Label::secondary(file_id, span).with_message("...and again here")
+                    }),
+            );
+            reports.push(
+                Self::diagnostic(config)
+                    .with_message(format!("Duplicate {}", kind_name(&symbol.kind)))
+                    .with_labels(labels),
+            );
+        }
+    }
+}
+
+/// Returns whether two symbols of the same name would conflict. Functions and constructors share
+/// a namespace, while macros only conflict when bound to the same config.
+fn is_same_declaration(kind: &SymbolKind, other: &SymbolKind) -> bool {
+    match kind {
+        SymbolKind::Function | SymbolKind::Constructor => other.is_function(),
+        SymbolKind::Enum | SymbolKind::EnumMember { .. } | SymbolKind::Macro { .. } => kind == other,
+        SymbolKind::GlobalVariable | SymbolKind::Object => false,
+    }
+}
+
+fn kind_name(kind: &SymbolKind) -> &'static str {
+    match kind {
+        SymbolKind::Function => "function",
+        SymbolKind::Constructor => "constructor",
+        SymbolKind::Enum => "enum",
+        SymbolKind::EnumMember { .. } => "enum member",
+        SymbolKind::Macro { .. } => "macro",
+        SymbolKind::GlobalVariable => "global variable",
+        SymbolKind::Object => "object",
+    }
+}
@@ -1,10 +1,10 @@
 use bimap::BiHashMap;
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 use once_cell::sync::Lazy;
 
 use crate::{
-    EnglishFlavor, FileId,
-    lint::{EarlyExprPass, Lint, LintLevel},
+    EnglishFlavor,
+    lint::{EarlyExprPass, Lint, LintLevel, Report, Suggestion},
     parse::{Call, Expr, ExprKind},
 };
 
@@ -25,7 +25,7 @@ impl Lint for EnglishFlavorViolation {
 }
 
 impl EarlyExprPass for EnglishFlavorViolation {
-    fn visit_expr_early(expr: &Expr, config: &crate::Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_expr_early(expr: &Expr, config: &crate::Config, reports: &mut Vec<Report>) {
         let english_flavor = &config.english_flavor;
         if let ExprKind::Call(Call { left, .. }) = expr.kind() {
             if let ExprKind::Identifier(identifier) = left.kind() {
@@ -40,7 +40,12 @@ impl EarlyExprPass for EnglishFlavorViolation {
                                     .with_labels(vec![
                                         Label::primary(left.file_id(), left.span())
                                             .with_message(format!("replace this with `{}`", american_spelling)),
-                                    ]),
+                                    ])
+                                    .with_suggestions(vec![Suggestion::new(
+                                        left.file_id(),
+                                        left.span(),
+                                        *american_spelling,
+                                    )]),
                             );
                         }
                     }
@@ -54,7 +59,12 @@ impl EarlyExprPass for EnglishFlavorViolation {
                                     .with_labels(vec![
                                         Label::primary(left.file_id(), left.span())
                                             .with_message(format!("replace this with `{}`", british_spelling)),
-                                    ]),
+                                    ])
+                                    .with_suggestions(vec![Suggestion::new(
+                                        left.file_id(),
+                                        left.span(),
+                                        *british_spelling,
+                                    )]),
                             );
                         }
                     }
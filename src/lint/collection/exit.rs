@@ -1,8 +1,7 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 use crate::{
-    FileId,
-    lint::{EarlyStmtPass, Lint, LintLevel},
+    lint::{EarlyStmtPass, Lint, LintLevel, Report},
     parse::{Stmt, StmtKind},
 };
 
@@ -23,7 +22,7 @@ impl Lint for Exit {
 }
 
 impl EarlyStmtPass for Exit {
-    fn visit_stmt_early(stmt: &Stmt, config: &crate::Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_stmt_early(stmt: &Stmt, config: &crate::Config, reports: &mut Vec<Report>) {
         if let StmtKind::Exit = stmt.kind() {
             reports.push(Self::diagnostic(config).with_message("Use of `exit`").with_labels(vec![
                 Label::primary(stmt.file_id(), stmt.span()).with_message("replace this with `return`"),
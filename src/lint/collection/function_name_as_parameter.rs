@@ -1,9 +1,8 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 use crate::{
-    FileId,
     driver::Ctx,
-    lint::{LateExprPass, Lint, LintLevel},
+    lint::{LateExprPass, Lint, LintLevel, Report},
     parse::{Expr, ExprKind, Function},
 };
 
@@ -24,10 +23,10 @@ impl Lint for FunctionNameAsParameter {
 }
 
 impl LateExprPass for FunctionNameAsParameter {
-    fn visit_expr_late(expr: &Expr, config: &crate::Config, ctx: &Ctx, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_expr_late(expr: &Expr, config: &crate::Config, ctx: &Ctx, reports: &mut Vec<Report>) {
         if let ExprKind::Function(Function { parameters, .. }) = expr.kind() {
             for param in parameters {
-                if ctx.global_function_names.iter().any(|v| v == param.name()) {
+                if ctx.symbols.is_function(param.name()) {
                     reports.push(
                         Self::diagnostic(config)
                             .with_message("Function name as parameter")
@@ -1,8 +1,7 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 use crate::{
-    FileId,
-    lint::{EarlyStmtPass, Lint, LintLevel},
+    lint::{EarlyStmtPass, Lint, LintLevel, Report},
     parse::{Access, Assignment, ExprKind, Globalvar, Stmt, StmtKind},
 };
 
@@ -23,7 +22,7 @@ impl Lint for Global {
 }
 
 impl EarlyStmtPass for Global {
-    fn visit_stmt_early(stmt: &Stmt, config: &crate::Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_stmt_early(stmt: &Stmt, config: &crate::Config, reports: &mut Vec<Report>) {
         match stmt.kind() {
             StmtKind::Assignment(Assignment { left, .. }) => {
                 if let ExprKind::Access(Access::Global { .. }) = left.kind() {
@@ -1,8 +1,8 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 use crate::{
-    Config, FileId,
-    lint::{EarlyExprPass, Lint, LintLevel},
+    Config,
+    lint::{EarlyExprPass, Lint, LintLevel, Report},
     parse::{Expr, ExprKind, Logical},
 };
 
@@ -22,7 +22,7 @@ impl Lint for InvalidComparison {
     }
 }
 impl InvalidComparison {
-    fn test_expr(expr: &Expr, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn test_expr(expr: &Expr, config: &Config, reports: &mut Vec<Report>) {
         let is_valid = !matches!(expr.kind(), ExprKind::Function(_));
         if !is_valid {
             reports.push(
@@ -38,7 +38,7 @@ impl InvalidComparison {
 }
 
 impl EarlyExprPass for InvalidComparison {
-    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Report>) {
         if let ExprKind::Logical(Logical { left, right, .. }) = expr.kind() {
             Self::test_expr(left, config, reports);
             Self::test_expr(right, config, reports);
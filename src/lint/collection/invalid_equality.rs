@@ -1,8 +1,8 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 use crate::{
-    Config, FileId,
-    lint::{EarlyExprPass, Lint, LintLevel},
+    Config,
+    lint::{EarlyExprPass, Lint, LintLevel, Report},
     parse::{Equality, Expr, ExprKind},
 };
 
@@ -23,7 +23,7 @@ impl Lint for InvalidEquality {
 }
 
 impl InvalidEquality {
-    fn test_expr(expr: &Expr, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn test_expr(expr: &Expr, config: &Config, reports: &mut Vec<Report>) {
         let is_valid = !matches!(expr.kind(), ExprKind::Function(_));
         if !is_valid {
             reports.push(
@@ -39,7 +39,7 @@ impl InvalidEquality {
 }
 
 impl EarlyExprPass for InvalidEquality {
-    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Report>) {
         if let ExprKind::Equality(Equality { left, right, .. }) = expr.kind() {
             Self::test_expr(left, config, reports);
             Self::test_expr(right, config, reports);
@@ -1,8 +1,8 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 use crate::{
-    Config, FileId,
-    lint::{EarlyExprPass, Lint, LintLevel},
+    Config,
+    lint::{EarlyExprPass, Lint, LintLevel, Report, Suggestion},
     parse::{Evaluation, EvaluationOp, Expr, ExprKind, TokenKind},
 };
 
@@ -22,20 +22,33 @@ impl Lint for ModPreference {
     }
 }
 impl EarlyExprPass for ModPreference {
-    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Report>) {
         if let ExprKind::Evaluation(Evaluation {
             op: EvaluationOp::Modulo(token),
-            ..
+            left,
+            right,
         }) = expr.kind()
         {
             if config.prefer_mod_keyword() && token.token_type != TokenKind::Mod {
-                reports.push(Self::diagnostic(config).with_message("Use of `%`").with_labels(vec![
-                    Label::primary(expr.file_id(), token.span).with_message("use the `mod` keyword instead of `%`"),
-                ]));
-            } else if token.token_type == TokenKind::Mod {
-                reports.push(Self::diagnostic(config).with_message("Use of `mod`").with_labels(vec![
-                    Label::primary(expr.file_id(), token.span).with_message("use the `%` operator instead of `mod`"),
-                ]));
+                reports.push(
+                    Self::diagnostic(config)
+                        .with_message("Use of `%`")
+                        .with_labels(vec![
+                            Label::primary(expr.file_id(), token.span)
+                                .with_message("use the `mod` keyword instead of `%`"),
+                        ])
+                        .with_suggestions(vec![Suggestion::replace_operator(token, Some(left), right, "mod")]),
+                );
+            } else if !config.prefer_mod_keyword() && token.token_type == TokenKind::Mod {
+                reports.push(
+                    Self::diagnostic(config)
+                        .with_message("Use of `mod`")
+                        .with_labels(vec![
+                            Label::primary(expr.file_id(), token.span)
+                                .with_message("use the `%` operator instead of `mod`"),
+                        ])
+                        .with_suggestions(vec![Suggestion::replace_operator(token, Some(left), right, "%")]),
+                );
             }
         }
     }
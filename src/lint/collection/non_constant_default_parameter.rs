@@ -1,9 +1,6 @@
-use codespan_reporting::diagnostic::Diagnostic;
-
 use crate::{
-    FileId,
     driver::Ctx,
-    lint::{LateExprPass, Lint, LintLevel},
+    lint::{LateExprPass, Lint, LintLevel, Report},
     parse::Expr,
 };
 
@@ -49,7 +46,7 @@ impl NonConstantDefaultParameter {
 }
 
 impl LateExprPass for NonConstantDefaultParameter {
-    fn visit_expr_late(_expr: &Expr, _config: &crate::Config, _ctx: &Ctx, _reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_expr_late(_expr: &Expr, _config: &crate::Config, _ctx: &Ctx, _reports: &mut Vec<Report>) {
         // if let ExprType::Function(Function { parameters, .. }) = expr.kind() {
         //     for param in parameters {
         //         if let Some(default_expr) = param.assignment_value() {
@@ -1,9 +1,8 @@
 use crate::{
-    FileId,
-    lint::{EarlyExprPass, Lint, LintLevel},
+    lint::{EarlyExprPass, Lint, LintLevel, Report},
     parse::{Evaluation, EvaluationOp, Expr, ExprKind, Grouping, Literal},
 };
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 #[derive(Debug, PartialEq)]
 pub struct NonSimplifiedExpression;
@@ -32,7 +31,7 @@ impl NonSimplifiedExpression {
 }
 
 impl EarlyExprPass for NonSimplifiedExpression {
-    fn visit_expr_early(expr: &Expr, config: &crate::Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_expr_early(expr: &Expr, config: &crate::Config, reports: &mut Vec<Report>) {
         if let ExprKind::Evaluation(Evaluation { left, op, right }) = expr.kind() {
             let left = Self::filter_groups(left).unwrap_or(left);
             let right = Self::filter_groups(right).unwrap_or(right);
@@ -1,9 +1,9 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 use crate::{
-    Config, FileId,
-    lint::{EarlyExprPass, Lint, LintLevel},
-    parse::{Expr, ExprKind, TokenKind, Unary, UnaryOp},
+    Config,
+    lint::{EarlyExprPass, Lint, LintLevel, Report, Suggestion},
+    parse::{Expr, ExprKind, Span, TokenKind, Unary, UnaryOp},
 };
 
 #[derive(Debug, PartialEq)]
@@ -22,20 +22,36 @@ impl Lint for NotPreference {
     }
 }
 impl EarlyExprPass for NotPreference {
-    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Report>) {
         if let ExprKind::Unary(Unary {
             op: UnaryOp::Not(token),
-            ..
+            right,
         }) = expr.kind()
         {
             if config.prefer_not_keyword() && token.token_type != TokenKind::Not {
-                reports.push(Self::diagnostic(config).with_message("Use of `!`").with_labels(vec![
-                    Label::primary(expr.file_id(), token.span).with_message("use the `not` keyword instead of `!`"),
-                ]));
-            } else if token.token_type == TokenKind::Not {
-                reports.push(Self::diagnostic(config).with_message("Use of `not`").with_labels(vec![
-                    Label::primary(expr.file_id(), token.span).with_message("use the `!` operator instead of `not`"),
-                ]));
+                reports.push(
+                    Self::diagnostic(config)
+                        .with_message("Use of `!`")
+                        .with_labels(vec![
+                            Label::primary(expr.file_id(), token.span)
+                                .with_message("use the `not` keyword instead of `!`"),
+                        ])
+                        .with_suggestions(vec![Suggestion::replace_operator(token, None, right, "not")]),
+                );
+            } else if !config.prefer_not_keyword() && token.token_type == TokenKind::Not {
+                reports.push(
+                    Self::diagnostic(config)
+                        .with_message("Use of `not`")
+                        .with_labels(vec![
+                            Label::primary(expr.file_id(), token.span)
+                                .with_message("use the `!` operator instead of `not`"),
+                        ])
+                        .with_suggestions(vec![Suggestion::new(
+                            expr.file_id(),
+                            Span::new(token.span.start(), right.span().start()),
+                            "!",
+                        )]),
+                );
             }
         }
     }
@@ -1,8 +1,7 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 use crate::{
-    FileId,
-    lint::{EarlyStmtPass, Lint, LintLevel},
+    lint::{EarlyStmtPass, Lint, LintLevel, Report},
     parse::{Assignment, AssignmentOp, Stmt, StmtKind},
 };
 
@@ -23,7 +22,7 @@ impl Lint for NullCoalescenceEqual {
 }
 
 impl EarlyStmtPass for NullCoalescenceEqual {
-    fn visit_stmt_early(stmt: &Stmt, config: &crate::Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_stmt_early(stmt: &Stmt, config: &crate::Config, reports: &mut Vec<Report>) {
         if let StmtKind::Assignment(Assignment {
             op: AssignmentOp::NullCoalecenceEqual(_),
             left,
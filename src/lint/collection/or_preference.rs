@@ -1,8 +1,8 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 use crate::{
-    Config, FileId,
-    lint::{EarlyExprPass, Lint, LintLevel},
+    Config,
+    lint::{EarlyExprPass, Lint, LintLevel, Report, Suggestion},
     parse::{Expr, ExprKind, Logical, LogicalOp, TokenKind},
 };
 
@@ -22,20 +22,33 @@ impl Lint for OrPreference {
     }
 }
 impl EarlyExprPass for OrPreference {
-    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Report>) {
         if let ExprKind::Logical(Logical {
             op: LogicalOp::Or(token),
-            ..
+            left,
+            right,
         }) = expr.kind()
         {
             if config.prefer_or_keyword() && token.token_type != TokenKind::Or {
-                reports.push(Self::diagnostic(config).with_message("Use of `||`").with_labels(vec![
-                    Label::primary(expr.file_id(), token.span).with_message("use the `or` keyword instead of `||`"),
-                ]));
-            } else if token.token_type == TokenKind::Or {
-                reports.push(Self::diagnostic(config).with_message("Use of `or`").with_labels(vec![
-                    Label::primary(expr.file_id(), token.span).with_message("use the `||` operator instead of `or`"),
-                ]));
+                reports.push(
+                    Self::diagnostic(config)
+                        .with_message("Use of `||`")
+                        .with_labels(vec![
+                            Label::primary(expr.file_id(), token.span)
+                                .with_message("use the `or` keyword instead of `||`"),
+                        ])
+                        .with_suggestions(vec![Suggestion::replace_operator(token, Some(left), right, "or")]),
+                );
+            } else if !config.prefer_or_keyword() && token.token_type == TokenKind::Or {
+                reports.push(
+                    Self::diagnostic(config)
+                        .with_message("Use of `or`")
+                        .with_labels(vec![
+                            Label::primary(expr.file_id(), token.span)
+                                .with_message("use the `||` operator instead of `or`"),
+                        ])
+                        .with_suggestions(vec![Suggestion::replace_operator(token, Some(left), right, "||")]),
+                );
             }
         }
     }
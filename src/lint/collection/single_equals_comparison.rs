@@ -1,8 +1,8 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 use crate::{
-    Config, FileId,
-    lint::{EarlyExprPass, Lint, LintLevel},
+    Config,
+    lint::{EarlyExprPass, Lint, LintLevel, Report},
     parse::{Equality, EqualityOp, Expr, ExprKind, Token, TokenKind},
 };
 
@@ -23,7 +23,7 @@ impl Lint for SingleEqualsComparison {
 }
 
 impl EarlyExprPass for SingleEqualsComparison {
-    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Report>) {
         if let ExprKind::Equality(Equality {
             op:
                 EqualityOp::Equal(Token {
@@ -1,8 +1,7 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 use crate::{
-    FileId,
-    lint::{EarlyExprPass, EarlyStmtPass, Lint, LintLevel},
+    lint::{EarlyExprPass, EarlyStmtPass, Lint, LintLevel, Report},
     parse::{
         Assignment, AssignmentOp, Equality, EqualityOp, Evaluation, EvaluationOp, Expr, ExprKind, Literal, Logical,
         Stmt, StmtKind,
@@ -26,7 +25,7 @@ impl Lint for SuspicousConstantUsage {
 }
 
 impl SuspicousConstantUsage {
-    fn report_expr(expr: &Expr, config: &crate::Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn report_expr(expr: &Expr, config: &crate::Config, reports: &mut Vec<Report>) {
         reports.push(
             Self::diagnostic(config)
                 .with_message("Suspicious constant usage")
@@ -40,7 +39,7 @@ impl SuspicousConstantUsage {
 }
 
 impl EarlyExprPass for SuspicousConstantUsage {
-    fn visit_expr_early(expr: &Expr, config: &crate::Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_expr_early(expr: &Expr, config: &crate::Config, reports: &mut Vec<Report>) {
         match expr.kind() {
             ExprKind::Evaluation(Evaluation {
                 op: operator, right, ..
@@ -72,7 +71,7 @@ impl EarlyExprPass for SuspicousConstantUsage {
     }
 }
 impl EarlyStmtPass for SuspicousConstantUsage {
-    fn visit_stmt_early(stmt: &Stmt, config: &crate::Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_stmt_early(stmt: &Stmt, config: &crate::Config, reports: &mut Vec<Report>) {
         if let StmtKind::Assignment(Assignment {
             op: operator, right, ..
         }) = stmt.kind()
@@ -108,7 +107,7 @@ fn literal_is_suspicous(literal: &Literal, operation_wrapper: OpWrapper) -> bool
         | Literal::Noone
         | Literal::Array(_)
         | Literal::Struct(_) => true,
-        Literal::String(_) | Literal::Real(_) | Literal::Hex(_) => false,
+        Literal::String(_) | Literal::Template(_) | Literal::Real(_) | Literal::Hex(_) => false,
         Literal::Misc(literal) => {
             match literal.as_str() {
                 "tile_index_mask" | "tile_flip" | "tile_mirror" | "tile_rotate" => {
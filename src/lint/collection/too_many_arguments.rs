@@ -1,8 +1,8 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 use crate::{
-    Config, FileId,
-    lint::{EarlyExprPass, Lint, LintLevel},
+    Config,
+    lint::{EarlyExprPass, Lint, LintLevel, Report},
     parse::{Expr, ExprKind, Field, Function},
 };
 
@@ -23,7 +23,7 @@ impl Lint for TooManyArguments {
 }
 
 impl EarlyExprPass for TooManyArguments {
-    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Report>) {
         if let ExprKind::Function(Function { parameters, .. }) = expr.kind() {
             if parameters.len() > config.max_arguments {
                 let start = parameters.first().unwrap().name_expr().span().start();
@@ -1,9 +1,9 @@
 use crate::{
-    Config, FileId,
-    lint::{EarlyStmtPass, Lint, LintLevel},
+    Config,
+    lint::{EarlyStmtPass, Lint, LintLevel, Report},
     parse::{Call, ExprKind, Stmt, StmtKind},
 };
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 #[derive(Debug, PartialEq)]
 pub struct UnassignedConstructor;
@@ -22,7 +22,7 @@ impl Lint for UnassignedConstructor {
 }
 
 impl EarlyStmtPass for UnassignedConstructor {
-    fn visit_stmt_early(stmt: &Stmt, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_stmt_early(stmt: &Stmt, config: &Config, reports: &mut Vec<Report>) {
         if let StmtKind::Expr(expr) = stmt.kind() {
             if let ExprKind::Call(Call { uses_new: true, .. }) = expr.kind() {
                 reports.push(
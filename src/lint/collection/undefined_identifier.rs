@@ -0,0 +1,223 @@
+use codespan_reporting::diagnostic::Label;
+use colored::Colorize;
+use hashbrown::HashSet;
+
+use crate::{
+    Config, SymbolKind,
+    driver::Ctx,
+    lint::{LateAstPass, Lint, LintLevel, Report},
+    parse::{
+        Assignment, Ast, Call, Constructor, Expr, ExprKind, Field, Function, GML_FUNCTIONS, Identifier,
+        MISC_GML_CONSTANTS, MISC_GML_VARIABLES, ParseVisitor, Stmt, StmtKind,
+    },
+};
+
+#[derive(Debug, PartialEq)]
+pub struct UndefinedIdentifier;
+impl Lint for UndefinedIdentifier {
+    fn explanation() -> &'static str {
+        "Reading or calling a name that is not declared anywhere in the project or in gml crashes the game when the code runs, and is most often a typo."
+    }
+
+    fn default_level() -> LintLevel {
+        LintLevel::Allow
+    }
+
+    fn tag() -> &'static str {
+        "undefined_identifier"
+    }
+}
+
+impl LateAstPass for UndefinedIdentifier {
+    fn visit_ast_late(ast: &Ast, config: &Config, ctx: &Ctx, reports: &mut Vec<Report>) {
+        let scope = Scope::new(ast.stmts(), &[]);
+        ast.stmts()
+            .iter()
+            .for_each(|stmt| check_stmt(stmt, &scope, config, ctx, reports));
+    }
+}
+
+/// The local variables visible within a file's top level or a function's body. Local variables
+/// in gml are visible throughout the entire function they are declared in, but functions cannot
+/// see the local variables of the function they are declared within.
+struct Scope {
+    locals: HashSet<String>,
+}
+impl Scope {
+    fn new(stmts: &[Stmt], parameters: &[Field]) -> Self {
+        let mut locals = parameters.iter().map(|param| param.name().to_string()).collect();
+        stmts.iter().for_each(|stmt| collect_locals(stmt, &mut locals));
+        Self { locals }
+    }
+
+    /// Returns whether the name can be resolved to a local, global, asset, instance variable or
+    /// anything built into gml.
+    fn is_defined(&self, name: &str, ctx: &Ctx) -> bool {
+        // The parser reads a bare `self` or `other` as an identifier
+        matches!(name, "self" | "other")
+            || self.locals.contains(name)
+            || ctx.symbols.contains(name)
+            || ctx.assets.contains(name)
+            || ctx.instance_variables.contains(name)
+            || GML_FUNCTIONS.contains(name)
+            || MISC_GML_VARIABLES.contains(name)
+            || MISC_GML_CONSTANTS.contains(name)
+    }
+}
+
+/// Records every local variable declared within the statement, ignoring those declared within
+/// nested functions.
+fn collect_locals(stmt: &Stmt, locals: &mut HashSet<String>) {
+    match stmt.kind() {
+        StmtKind::LocalVariables(local_variables) => {
+            locals.extend(
+                local_variables
+                    .declarations
+                    .iter()
+                    .map(|field| field.name().to_string()),
+            );
+        }
+        StmtKind::TryCatch(try_catch) => {
+            let catch_expr = match try_catch.catch_expr.kind().as_grouping() {
+                Some(grouping) => &grouping.inner,
+                None => &try_catch.catch_expr,
+            };
+            if let Some(identifier) = catch_expr.kind().as_identifier() {
+                locals.insert(identifier.lexeme.clone());
+            }
+        }
+        _ => {}
+    }
+    stmt.kind().visit_child_stmts(|stmt| collect_locals(stmt, locals));
+}
+
+fn check_stmt(stmt: &Stmt, scope: &Scope, config: &Config, ctx: &Ctx, reports: &mut Vec<Report>) {
+    match stmt.kind() {
+        // Declarations of global names are recorded in the ctx instead
+        StmtKind::Enum(_) | StmtKind::Macro(_) | StmtKind::Globalvar(_) => {}
+        StmtKind::LocalVariables(local_variables) => {
+            for value in local_variables.declarations.iter().filter_map(Field::assignment_value) {
+                check_expr(value, scope, config, ctx, reports);
+            }
+        }
+        StmtKind::Assignment(Assignment { left, right, .. }) => {
+            // Assigning to a bare name declares an instance variable, rather than reading one
+            if left.kind().as_identifier().is_none() {
+                check_expr(left, scope, config, ctx, reports);
+            }
+            check_expr(right, scope, config, ctx, reports);
+        }
+        StmtKind::TryCatch(try_catch) => {
+            check_stmt(&try_catch.try_body, scope, config, ctx, reports);
+            check_stmt(&try_catch.catch_body, scope, config, ctx, reports);
+            if let Some(finally_body) = &try_catch.finally_body {
+                check_stmt(finally_body, scope, config, ctx, reports);
+            }
+        }
+        kind => {
+            kind.visit_child_stmts(|stmt| check_stmt(stmt, scope, config, ctx, reports));
+            kind.visit_child_exprs(|expr| check_expr(expr, scope, config, ctx, reports));
+        }
+    }
+}
+
+fn check_expr(expr: &Expr, scope: &Scope, config: &Config, ctx: &Ctx, reports: &mut Vec<Report>) {
+    match expr.kind() {
+        ExprKind::Identifier(identifier) => {
+            if !scope.is_defined(&identifier.lexeme, ctx) {
+                reports.push(report(expr, identifier, false, scope, config, ctx));
+            }
+        }
+        ExprKind::Call(Call { left, arguments, .. }) => {
+            match left.kind().as_identifier() {
+                Some(identifier) if !scope.is_defined(&identifier.lexeme, ctx) => {
+                    reports.push(report(expr, identifier, true, scope, config, ctx));
+                }
+                Some(_) => {}
+                None => check_expr(left, scope, config, ctx, reports),
+            }
+            for argument in arguments {
+                check_expr(argument, scope, config, ctx, reports);
+            }
+        }
+        ExprKind::Function(function) => check_function(function, config, ctx, reports),
+        _ => {
+            expr.visit_child_stmts(|stmt| check_stmt(stmt, scope, config, ctx, reports));
+            expr.visit_child_exprs(|expr| check_expr(expr, scope, config, ctx, reports));
+        }
+    }
+}
+
+/// Checks a function with a scope of its own, as it cannot see the locals of its surroundings.
+fn check_function(function: &Function, config: &Config, ctx: &Ctx, reports: &mut Vec<Report>) {
+    let scope = Scope::new(std::slice::from_ref(&function.body), &function.parameters);
+    for value in function.parameters.iter().filter_map(Field::assignment_value) {
+        check_expr(value, &scope, config, ctx, reports);
+    }
+    if let Some(Constructor {
+        inheritance: Some(call),
+    }) = &function.constructor
+    {
+        check_expr(call, &scope, config, ctx, reports);
+    }
+    check_stmt(&function.body, &scope, config, ctx, reports);
+}
+
+fn report(expr: &Expr, identifier: &Identifier, is_call: bool, scope: &Scope, config: &Config, ctx: &Ctx) -> Report {
+    let (message, label) = if is_call {
+        (
+            "Call to undefined function",
+            format!("`{identifier}` is not a known function"),
+        )
+    } else {
+        (
+            "Undefined identifier",
+            format!("`{identifier}` is not declared anywhere"),
+        )
+    };
+    let mut notes = vec![];
+    if let Some(suggestion) = did_you_mean(&identifier.lexeme, is_call, scope, ctx) {
+        notes.push(format!("{}: did you mean `{suggestion}`?", "help".bold()));
+    }
+    UndefinedIdentifier::diagnostic(config)
+        .with_message(message)
+        .with_labels(vec![
+            Label::primary(expr.file_id(), identifier.span).with_message(label),
+        ])
+        .with_notes(notes)
+}
+
+/// Returns the known name most similar to the undefined one, if any are close enough to be a likely
+/// typo. Calls are only compared against functions.
+fn did_you_mean<'a>(name: &str, is_call: bool, scope: &'a Scope, ctx: &'a Ctx) -> Option<&'a str> {
+    let functions = ctx
+        .symbols
+        .iter()
+        .filter(|symbol| symbol.kind.is_function())
+        .map(|symbol| symbol.name.as_str())
+        .chain(GML_FUNCTIONS.iter().copied());
+    let candidates: Box<dyn Iterator<Item = &str>> = if is_call {
+        Box::new(functions)
+    } else {
+        Box::new(
+            scope
+                .locals
+                .iter()
+                .map(String::as_str)
+                .chain(ctx.instance_variables.iter().map(String::as_str))
+                .chain(
+                    ctx.symbols
+                        .iter()
+                        .filter(|symbol| !matches!(symbol.kind, SymbolKind::EnumMember { .. }))
+                        .map(|symbol| symbol.name.as_str()),
+                )
+                .chain(ctx.assets.iter().map(String::as_str))
+                .chain(MISC_GML_VARIABLES.iter().copied()),
+        )
+    };
+    // Many names share long prefixes, which jaro winkler rates identically, so the closest of the
+    // similar names is picked by their edit distance instead
+    candidates
+        .filter(|candidate| strsim::jaro_winkler(name, candidate) > 0.9)
+        .min_by_key(|candidate| strsim::levenshtein(name, candidate))
+}
@@ -1,9 +1,8 @@
 use crate::{
-    FileId,
-    lint::{EarlyExprPass, EarlyStmtPass, Lint, LintLevel},
+    lint::{EarlyExprPass, EarlyStmtPass, Lint, LintLevel, Report},
     parse::{Expr, ExprKind, ParseVisitor, Stmt, StmtKind},
 };
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 #[derive(Debug, PartialEq)]
 pub struct UnnecessaryGrouping;
@@ -22,7 +21,7 @@ impl Lint for UnnecessaryGrouping {
 }
 
 impl UnnecessaryGrouping {
-    fn test(expr: &Expr, config: &crate::Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn test(expr: &Expr, config: &crate::Config, reports: &mut Vec<Report>) {
         if let ExprKind::Grouping(grouping) = expr.kind() {
             let (left_token, right_token) = grouping.parenthesis();
             reports.push(
@@ -38,7 +37,7 @@ impl UnnecessaryGrouping {
 }
 
 impl EarlyExprPass for UnnecessaryGrouping {
-    fn visit_expr_early(expr: &Expr, config: &crate::Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_expr_early(expr: &Expr, config: &crate::Config, reports: &mut Vec<Report>) {
         match expr.kind() {
             // These are the blessed expressions that utilize groupings in meaningful ways
             ExprKind::Logical(_)
@@ -63,7 +62,7 @@ impl EarlyExprPass for UnnecessaryGrouping {
 }
 
 impl EarlyStmtPass for UnnecessaryGrouping {
-    fn visit_stmt_early(stmt: &Stmt, config: &crate::Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_stmt_early(stmt: &Stmt, config: &crate::Config, reports: &mut Vec<Report>) {
         match stmt.kind() {
             // These are a style preference, which instead is linted by `condition_wrapper`.
             StmtKind::TryCatch(_)
@@ -0,0 +1,64 @@
+use codespan_reporting::diagnostic::Label;
+use colored::Colorize;
+
+use crate::{
+    Config, FileId,
+    lint::{Lint, LintLevel, Report},
+    parse::{Span, Tag},
+};
+
+#[derive(Debug, PartialEq)]
+pub struct UnusedAllow;
+impl Lint for UnusedAllow {
+    fn explanation() -> &'static str {
+        "Allow tags that no longer suppress anything, that name a lint that does not exist, or that are written where they have no effect, are clutter that can hide real issues later on."
+    }
+
+    fn default_level() -> LintLevel {
+        LintLevel::Warn
+    }
+
+    fn tag() -> &'static str {
+        "unused_allow"
+    }
+}
+
+impl UnusedAllow {
+    /// Creates a report for an allow tag that did not suppress any diagnostics. Unlike other
+    /// lints, this is reported by the driver once every pass has run on a file.
+    pub fn unused_tag(config: &Config, file_id: FileId, span: Span, tag: &Tag) -> Report {
+        let lint = tag.1.as_deref().unwrap_or_default();
+        Self::diagnostic(config)
+            .with_message(format!("Unused tag: `{}({lint})`", tag.0))
+            .with_labels(vec![
+                Label::primary(file_id, span).with_message(format!("`{lint}` is never reported here")),
+            ])
+            .with_notes(vec![format!("{}: remove the tag", "help".bold())])
+    }
+
+    /// Creates a report for a level tag that names a lint that does not exist.
+    pub fn unknown_lint(config: &Config, file_id: FileId, span: Span, tag: &Tag) -> Report {
+        let lint = tag.1.as_deref().unwrap_or_default();
+        Self::diagnostic(config)
+            .with_message(format!("Unknown lint: `{lint}`"))
+            .with_labels(vec![
+                Label::primary(file_id, span).with_message(format!("`{}({lint})` has no effect", tag.0)),
+            ])
+    }
+
+    /// Creates a report for a file tag (`// #![allow(lint)]`) written after the first statement of
+    /// the file, which is not applied.
+    pub fn misplaced_file_tag(config: &Config, file_id: FileId, span: Span, tag: &Tag) -> Report {
+        let lint = tag.1.as_deref().unwrap_or_default();
+        Self::diagnostic(config)
+            .with_message(format!("Misplaced file tag: `{}({lint})`", tag.0))
+            .with_labels(vec![
+                Label::primary(file_id, span).with_message("file tags only apply before the first statement"),
+            ])
+            .with_notes(vec![format!(
+                "{}: move the tag to the top of the file, or use `// #[{}({lint})]` above a statement",
+                "help".bold(),
+                tag.0
+            )])
+    }
+}
@@ -0,0 +1,70 @@
+use codespan_reporting::diagnostic::Label;
+use colored::Colorize;
+
+use crate::{
+    Config,
+    driver::Ctx,
+    lint::{LateAstPass, Lint, LintLevel, Report},
+    parse::{Ast, ExprKind, Function, Location, StmtKind},
+};
+
+#[derive(Debug, PartialEq)]
+pub struct UnusedFunction;
+impl Lint for UnusedFunction {
+    fn explanation() -> &'static str {
+        "Global functions and constructors that are never referenced anywhere in the project are dead code that still has to be read and maintained."
+    }
+
+    fn default_level() -> LintLevel {
+        LintLevel::Allow
+    }
+
+    fn tag() -> &'static str {
+        "unused_function"
+    }
+}
+
+impl LateAstPass for UnusedFunction {
+    fn visit_ast_late(ast: &Ast, config: &Config, ctx: &Ctx, reports: &mut Vec<Report>) {
+        for stmt in ast.stmts() {
+            let StmtKind::Expr(expr) = stmt.kind() else {
+                continue;
+            };
+            let ExprKind::Function(Function {
+                name: Some(name),
+                constructor,
+                ..
+            }) = expr.kind()
+            else {
+                continue;
+            };
+
+            // Only functions that were recorded as globals are checked, as the rest are methods
+            let location = Location(stmt.file_id(), name.span);
+            let is_global = ctx
+                .symbols
+                .get(&name.lexeme)
+                .any(|symbol| symbol.kind.is_function() && symbol.location == location);
+            if !is_global || ctx.references.contains(&name.lexeme) {
+                continue;
+            }
+            let kind = if constructor.is_some() {
+                "constructor"
+            } else {
+                "function"
+            };
+            reports.push(
+                Self::diagnostic(config)
+                    .with_message(format!("Unused {kind}"))
+                    .with_labels(vec![
+                        Label::primary(stmt.file_id(), name.span)
+                            .with_message(format!("`{name}` is never referenced in the project")),
+                    ])
+                    .with_notes(vec![format!(
+                        "{}: if it is only used from outside of gml, add `// #[allow(unused_function)]` above it",
+                        "help".bold()
+                    )]),
+            );
+        }
+    }
+}
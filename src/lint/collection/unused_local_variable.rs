@@ -1,11 +1,11 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 use colored::Colorize;
-use hashbrown::{HashMap, HashSet};
+use hashbrown::HashMap;
 
 use crate::{
     Config, FileId,
-    lint::{AstPass, EarlyExprPass, Lint, LintLevel},
-    parse::{Ast, Block, Expr, ExprKind, For, Function, Identifier, ParseVisitor, Stmt, StmtKind},
+    lint::{AstPass, Lint, LintLevel, Report},
+    parse::{Ast, Block, Expr, ExprKind, Function, Identifier, ParseVisitor, Stmt, StmtKind},
 };
 
 #[derive(Debug, PartialEq)]
@@ -25,12 +25,12 @@ impl Lint for UnusedLocalVariable {
 }
 
 impl AstPass for UnusedLocalVariable {
-    fn visit_ast(ast: &Ast, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_ast(ast: &Ast, config: &Config, reports: &mut Vec<Report>) {
         analyze_scope_on_stmts(ast.stmts(), config, reports);
     }
 }
 
-fn analyze_scope_on_stmts(stmts: &[Stmt], config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+fn analyze_scope_on_stmts(stmts: &[Stmt], config: &Config, reports: &mut Vec<Report>) {
     let mut scope: HashMap<String, (Identifier, FileId)> = HashMap::new();
     stmts.iter().for_each(|v| analyze_stmt(v, &mut scope, config, reports));
 
@@ -54,7 +54,7 @@ fn analyze_stmt(
     stmt: &Stmt,
     scope: &mut HashMap<String, (Identifier, FileId)>,
     config: &Config,
-    reports: &mut Vec<Diagnostic<FileId>>,
+    reports: &mut Vec<Report>,
 ) {
     if let StmtKind::LocalVariables(variables) = stmt.kind() {
         variables.declarations.iter().for_each(|v| {
@@ -80,7 +80,7 @@ fn analyze_expr(
     expr: &Expr,
     scope: &mut HashMap<String, (Identifier, FileId)>,
     config: &Config,
-    reports: &mut Vec<Diagnostic<FileId>>,
+    reports: &mut Vec<Report>,
 ) {
     match expr.kind() {
         ExprKind::Function(Function { body, .. }) => {
@@ -1,9 +1,9 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 use colored::Colorize;
 
 use crate::{
-    Config, FileId,
-    lint::{EarlyExprPass, Lint, LintLevel},
+    Config,
+    lint::{EarlyExprPass, Lint, LintLevel, Report},
     parse::{Expr, ExprKind, Function, Identifier, ParseVisitor, Stmt},
 };
 
@@ -24,7 +24,7 @@ impl Lint for UnusedParameter {
 }
 
 impl EarlyExprPass for UnusedParameter {
-    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Report>) {
         if let ExprKind::Function(Function {
             parameters,
             name: _,
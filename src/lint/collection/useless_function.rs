@@ -1,9 +1,9 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 use colored::Colorize;
 
 use crate::{
-    Config, FileId,
-    lint::{EarlyStmtPass, Lint, LintLevel},
+    Config,
+    lint::{EarlyStmtPass, Lint, LintLevel, Report},
     parse::{ExprKind, Function, Stmt, StmtKind},
 };
 
@@ -24,7 +24,7 @@ impl Lint for UselessFunction {
 }
 
 impl EarlyStmtPass for UselessFunction {
-    fn visit_stmt_early(stmt: &Stmt, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_stmt_early(stmt: &Stmt, config: &Config, reports: &mut Vec<Report>) {
         if let StmtKind::Expr(expr) = stmt.kind() {
             if let ExprKind::Function(Function { name: None, .. }) = expr.kind() {
                 reports.push(
@@ -1,8 +1,8 @@
-use codespan_reporting::diagnostic::{Diagnostic, Label};
+use codespan_reporting::diagnostic::Label;
 
 use crate::{
-    Config, FileId,
-    lint::{EarlyStmtPass, Lint, LintLevel},
+    Config,
+    lint::{EarlyStmtPass, Lint, LintLevel, Report},
     parse::{LocalVariables, Stmt, StmtKind},
 };
 
@@ -23,7 +23,7 @@ impl Lint for VarPrefixViolation {
 }
 
 impl EarlyStmtPass for VarPrefixViolation {
-    fn visit_stmt_early(stmt: &Stmt, config: &Config, reports: &mut Vec<Diagnostic<FileId>>) {
+    fn visit_stmt_early(stmt: &Stmt, config: &Config, reports: &mut Vec<Report>) {
         if let StmtKind::LocalVariables(LocalVariables { declarations }) = stmt.kind() {
             for local_variable in declarations.iter() {
                 let name = local_variable.name();
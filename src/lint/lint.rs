@@ -1,6 +1,7 @@
 use crate::{
-    Config, FileId,
+    Config,
     driver::Ctx,
+    lint::Report,
     parse::{Ast, Expr, Stmt},
 };
 use codespan_reporting::diagnostic::{Diagnostic, Severity};
@@ -22,59 +23,68 @@ pub trait Lint {
     /// Returns an explanation of what the lint does and why it is useful.
     fn explanation() -> &'static str;
 
-    /// Creates a diagnostic based on the user's lint level for this lint.
+    /// Creates a diagnostic based on the user's lint level for this lint. Tags in the source code
+    /// can raise a lint above the level in the config, in which case the driver corrects the
+    /// severity of the diagnostic after the lint has run.
     #[must_use = "Diagnostics made by lints must be put into the reports collection."]
-    fn diagnostic(config: &Config) -> Diagnostic<FileId> {
+    fn diagnostic(config: &Config) -> Report {
         let level = config
             .lint_levels
             .iter()
             .find(|(key, _)| key.as_str() == Self::tag())
             .map_or_else(Self::default_level, |(_, level)| *level);
         match level {
-            LintLevel::Allow => unreachable!(),
-            LintLevel::Warn => Diagnostic::warning(),
+            LintLevel::Allow | LintLevel::Warn => Diagnostic::warning(),
             LintLevel::Deny => Diagnostic::error(),
         }
+        .with_code(Self::tag())
         .with_notes(vec![format!(
             "{}: for more information, run `{}`",
             "Note".bold(),
             format!("duck explain {}", Self::tag()).bold(),
         )])
+        .into()
     }
 }
 
 /// Lints who run a pass on an entire Ast.
 pub trait AstPass {
     /// Runs on the Ast in the Ast pass.
-    fn visit_ast(ast: &Ast, config: &Config, reports: &mut Vec<Diagnostic<FileId>>);
+    fn visit_ast(ast: &Ast, config: &Config, reports: &mut Vec<Report>);
+}
+
+/// Lints who run a late pass on an entire Ast (after type information has been collected).
+pub trait LateAstPass {
+    /// Runs on the Ast in the late pass.
+    fn visit_ast_late(ast: &Ast, config: &Config, ctx: &Ctx, reports: &mut Vec<Report>);
 }
 
 /// Lints who run an early pass on statements (before type information has been
 /// collected).
 pub trait EarlyStmtPass {
     /// Runs on statements in the early pass.
-    fn visit_stmt_early(stmt: &Stmt, config: &Config, reports: &mut Vec<Diagnostic<FileId>>);
+    fn visit_stmt_early(stmt: &Stmt, config: &Config, reports: &mut Vec<Report>);
 }
 
 /// Lints who run an early pass on expressions (before type information has been
 /// collected).
 pub trait EarlyExprPass {
     /// Runs on expressions in the early pass.
-    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Diagnostic<FileId>>);
+    fn visit_expr_early(expr: &Expr, config: &Config, reports: &mut Vec<Report>);
 }
 
 /// Lints who run a late pass on statements (after type information has been
 /// collected).
 pub trait LateStmtPass {
     /// Runs on statements in the late pass.
-    fn visit_stmt_late(stmt: &Stmt, config: &Config, ctx: &Ctx, reports: &mut Vec<Diagnostic<FileId>>);
+    fn visit_stmt_late(stmt: &Stmt, config: &Config, ctx: &Ctx, reports: &mut Vec<Report>);
 }
 
 /// Lints who run a late pass on expresions (after type information has been
 /// collected).
 pub trait LateExprPass {
     /// Runs on expressions in the late pass.
-    fn visit_expr_late(expr: &Expr, config: &Config, ctx: &Ctx, reports: &mut Vec<Diagnostic<FileId>>);
+    fn visit_expr_late(expr: &Expr, config: &Config, ctx: &Ctx, reports: &mut Vec<Report>);
 }
 
 /// The three different levels a lint can be set to, changing how it will be
@@ -101,6 +111,16 @@ impl LintLevel {
             _ => None,
         }
     }
+    /// Returns the severity of the diagnostics a lint at this level creates, or None if the lint
+    /// should not run at all.
+    pub fn severity(&self) -> Option<Severity> {
+        match self {
+            LintLevel::Allow => None,
+            LintLevel::Warn => Some(Severity::Warning),
+            LintLevel::Deny => Some(Severity::Error),
+        }
+    }
+
     /// Converts a lint level into a string.
     pub fn to_str(&self) -> &str {
         match self {
@@ -0,0 +1,141 @@
+use crate::{
+    FileId,
+    parse::{Expr, Span, Token},
+};
+use codespan_reporting::diagnostic::{Diagnostic, Label};
+
+/// A machine-applicable fix for the problem a lint found, replacing the source code within a span
+/// with new text. Lints attach these to their diagnostics with [Report::with_suggestions], and they
+/// are later applied by `duck fix` or offered as quick fixes by the language server.
+#[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Suggestion {
+    /// The file the suggestion applies to.
+    pub file_id: FileId,
+    /// The span of source code to replace. Empty spans insert the replacement.
+    pub span: Span,
+    /// The text to replace the span with. Empty replacements remove the span.
+    pub replacement: String,
+}
+impl Suggestion {
+    /// Creates a new suggestion.
+    pub fn new(file_id: FileId, span: Span, replacement: impl Into<String>) -> Self {
+        Self {
+            file_id,
+            span,
+            replacement: replacement.into(),
+        }
+    }
+
+    /// Creates a suggestion that inserts text at the given position.
+    pub fn insert(file_id: FileId, position: usize, text: impl Into<String>) -> Self {
+        Self::new(file_id, Span::new(position, position), text)
+    }
+
+    /// Creates a suggestion that removes the source code within a span.
+    pub fn remove(file_id: FileId, span: Span) -> Self {
+        Self::new(file_id, span, "")
+    }
+
+    /// Creates a suggestion that replaces an operator. Keywords are padded with spaces if the
+    /// operator was touching its operands, so that `a&&b` does not become `aandb`.
+    pub fn replace_operator(operator: &Token, left: Option<&Expr>, right: &Expr, replacement: &str) -> Self {
+        let is_keyword = replacement.chars().all(|c| c.is_alphabetic());
+        let mut text = replacement.to_string();
+        if is_keyword && left.is_some_and(|left| left.span().end() == operator.span.start()) {
+            text.insert(0, ' ');
+        }
+        if is_keyword && right.span().start() == operator.span.end() {
+            text.push(' ');
+        }
+        Self::new(right.file_id(), operator.span, text)
+    }
+
+    /// Returns if this suggestion would touch any of the same source code as another.
+    fn conflicts_with(&self, other: &Suggestion) -> bool {
+        let (start, end) = (self.span.start(), self.span.end());
+        let (other_start, other_end) = (other.span.start(), other.span.end());
+        if start == end || other_start == other_end {
+            // Insertions conflict with anything that touches their position
+            start <= other_end && other_start <= end
+        } else {
+            start < other_end && other_start < end
+        }
+    }
+}
+
+/// A diagnostic created by a lint, alongside the [Suggestion]s that would fix the problem it
+/// describes. Lints create these with [crate::lint::Lint::diagnostic] and build them up just as
+/// they would a [Diagnostic].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Report {
+    /// The diagnostic shown to the user.
+    pub diagnostic: Diagnostic<FileId>,
+    /// The suggestions that fix the diagnostic. All of them are applied together, so a fix can be
+    /// made up of many separate edits.
+    pub suggestions: Vec<Suggestion>,
+}
+impl Report {
+    /// Sets the message of the diagnostic.
+    #[must_use]
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.diagnostic = self.diagnostic.with_message(message);
+        self
+    }
+
+    /// Adds labels to the diagnostic.
+    #[must_use]
+    pub fn with_labels(mut self, labels: Vec<Label<FileId>>) -> Self {
+        self.diagnostic = self.diagnostic.with_labels(labels);
+        self
+    }
+
+    /// Adds notes to the diagnostic.
+    #[must_use]
+    pub fn with_notes(mut self, notes: Vec<String>) -> Self {
+        self.diagnostic = self.diagnostic.with_notes(notes);
+        self
+    }
+
+    /// Adds suggestions that fix the diagnostic. Only suggestions that are always correct to apply
+    /// should be added, as `duck fix` applies them without asking.
+    #[must_use]
+    pub fn with_suggestions(mut self, suggestions: Vec<Suggestion>) -> Self {
+        self.suggestions.extend(suggestions);
+        self
+    }
+}
+impl From<Diagnostic<FileId>> for Report {
+    fn from(diagnostic: Diagnostic<FileId>) -> Self {
+        Self {
+            diagnostic,
+            suggestions: vec![],
+        }
+    }
+}
+
+/// Applies a series of fixes to the source code of a single file. Each fix is a group of
+/// suggestions (typically those of one diagnostic) that are applied together. Fixes that conflict
+/// with one applied before them are skipped entirely, and can be applied by running this again
+/// on the result once the source has been linted again.
+///
+/// Returns the new source code alongside the number of fixes that were applied.
+pub fn apply_suggestions(source: &str, fixes: &[Vec<Suggestion>]) -> (String, usize) {
+    let mut accepted: Vec<&Suggestion> = vec![];
+    let mut applied_fixes = 0;
+    for fix in fixes.iter().filter(|fix| !fix.is_empty()) {
+        let conflicts = fix.iter().enumerate().any(|(i, suggestion)| {
+            fix[..i].iter().any(|other| suggestion.conflicts_with(other))
+                || accepted.iter().any(|other| suggestion.conflicts_with(other))
+        });
+        if !conflicts {
+            accepted.extend(fix.iter());
+            applied_fixes += 1;
+        }
+    }
+    accepted.sort_by_key(|suggestion| std::cmp::Reverse(suggestion.span.start()));
+    let mut output = source.to_string();
+    for suggestion in accepted {
+        output.replace_range(suggestion.span.start()..suggestion.span.end(), &suggestion.replacement);
+    }
+    (output, applied_fixes)
+}
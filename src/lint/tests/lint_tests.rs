@@ -15,24 +15,24 @@ fn config_for_lint<T: Lint>() -> Config {
 }
 
 pub(super) fn harness_lint<T: Lint>(source: &'static str, expected_number: usize) {
+    harness_lint_with_origin::<T>(source, AstOrigin::Unknown, expected_number);
+}
+
+fn harness_lint_with_origin<T: Lint>(source: &'static str, origin: AstOrigin, expected_number: usize) {
     let config = config_for_lint::<T>();
-    let ctx = Ctx::default();
+    let mut ctx = Ctx::default();
     let mut library = GmlLibrary::new();
     let file_id = library.add("test.gml".into(), source);
     let mut ast = Parser::new_with_default_ids(source, file_id).into_ast().unwrap();
+    ast.set_origin(origin);
+    ctx.collect_ast(&ast);
     let mut reports = vec![];
-    driver::process_ast(&ast, &mut reports, &config);
-    for stmt in ast.stmts_mut() {
-        driver::process_stmt_early(stmt, &mut reports, &config);
-    }
-    for stmt in ast.stmts() {
-        driver::process_stmt_late(stmt, &mut reports, &config, &ctx);
-    }
+    driver::lint_ast(&ast, &mut reports, &config, &ctx);
     let writer = StandardStream::stdout(ColorChoice::Always);
     let config = codespan_reporting::term::Config::default();
     if reports.len() != expected_number {
         for report in reports.iter() {
-            codespan_reporting::term::emit(&mut writer.lock(), &config, &library, report).unwrap();
+            codespan_reporting::term::emit(&mut writer.lock(), &config, &library, &report.diagnostic).unwrap();
         }
         assert_eq!(
             reports.len(),
@@ -69,6 +69,16 @@ fn and_preference() {
     );
 }
 
+#[test]
+fn lints_within_template_strings() {
+    harness_lint::<AndPreference>(
+        "
+            foo = $\"{bar and buzz}\";
+        ",
+        1,
+    );
+}
+
 #[test]
 fn anonymous_constructor() {
     harness_lint::<AnonymousConstructor>(
@@ -209,6 +219,45 @@ fn deprecated() {
     );
 }
 
+#[test]
+fn draw_outside_draw_event() {
+    let event = |event_type, number| AstOrigin::ObjectEvent {
+        object: "obj_player".into(),
+        event: ObjectEvent { event_type, number },
+    };
+    let source = "
+        draw_sprite(spr_player, 0, x, y);
+        draw_set_color(c_red);
+        if draw_get_alpha() > 0 {
+            draw_text(x, y, \"hi\");
+        }
+    ";
+    harness_lint_with_origin::<DrawOutsideDrawEvent>(source, event(EventType::Step, 0), 2);
+    harness_lint_with_origin::<DrawOutsideDrawEvent>(source, event(EventType::Draw, 0), 0);
+    harness_lint_with_origin::<DrawOutsideDrawEvent>(source, event(EventType::Draw, 64), 0);
+    harness_lint_with_origin::<DrawOutsideDrawEvent>(
+        source,
+        AstOrigin::Script {
+            name: "scr_draw".into(),
+        },
+        0,
+    );
+    harness_lint::<DrawOutsideDrawEvent>(source, 0);
+
+    // Clearing and drawing onto surfaces are done outside of Draw events on purpose
+    harness_lint_with_origin::<DrawOutsideDrawEvent>("draw_clear_alpha(c_black, 0);", event(EventType::Create, 0), 0);
+    harness_lint_with_origin::<DrawOutsideDrawEvent>(
+        "
+            surface_set_target(surface);
+            draw_clear(c_black);
+            draw_sprite(spr_player, 0, 0, 0);
+            surface_reset_target();
+        ",
+        event(EventType::Step, 0),
+        0,
+    );
+}
+
 #[test]
 fn draw_sprite() {
     harness_lint::<DrawSprite>(
@@ -231,6 +280,49 @@ fn draw_text() {
     );
 }
 
+#[test]
+fn duplicate_declaration() {
+    harness_lint::<DuplicateDeclaration>(
+        "
+            function foo() {}
+            function foo() {}
+            function Bar() constructor {}
+            function Bar() {}
+        ",
+        4,
+    );
+    harness_lint::<DuplicateDeclaration>(
+        "
+            enum Dir { Left, Right, Left }
+            #macro SPEED 4
+            #macro SPEED 8
+        ",
+        4,
+    );
+    harness_lint::<DuplicateDeclaration>(
+        "
+            enum Dir { Left }
+            enum Dir { Left }
+        ",
+        2,
+    );
+    harness_lint::<DuplicateDeclaration>(
+        "
+            #macro SPEED 4
+            #macro Debug:SPEED 8
+            enum Dir { Left }
+            enum Axis { Left }
+            global.foo = 0;
+            global.foo = 1;
+            function foo() {}
+            function bar() {
+                function foo() {}
+            }
+        ",
+        0,
+    );
+}
+
 #[test]
 fn english_flavor_violation() {
     harness_lint::<EnglishFlavorViolation>(
@@ -275,6 +367,37 @@ fn missing_case_member() {
                 case Foo.Bar: break;
             }
         ",
+        1,
+    );
+    harness_lint::<MissingCaseMember>(
+        "
+            enum Foo {
+                Bar,
+                Buzz,
+                Len
+            }
+            switch foo {
+                case Foo.Bar: break;
+                case Foo.Buzz: break;
+            }
+        ",
+        0,
+    );
+    harness_lint::<MissingCaseMember>(
+        "
+            enum Foo {
+                Bar,
+                Buzz
+            }
+            // matches over more than one enum, or an enum we don't know about
+            switch foo {
+                case Foo.Bar: break;
+                case Fizz.Buzz: break;
+            }
+            switch foo {
+                case Fizz.Bar: break;
+            }
+        ",
         0,
     );
     harness_lint::<MissingCaseMember>(
@@ -508,6 +631,61 @@ fn unassigned_constructor() {
     );
 }
 
+#[test]
+fn undefined_identifier() {
+    harness_lint::<UndefinedIdentifier>(
+        "
+            show_debug_mesage(undefined_thing);
+        ",
+        2,
+    );
+    harness_lint::<UndefinedIdentifier>(
+        "
+            enum Dir { Left }
+            #macro SPEED 4
+            globalvar lives_left;
+            function foo(a, b = Dir.Left) {
+                var c = a + b + SPEED + lives_left + global.bar;
+                try {} catch (e) { show_debug_message(e); }
+                return c + x + pi + argument_count;
+            }
+            foo(1);
+        ",
+        0,
+    );
+    harness_lint::<UndefinedIdentifier>(
+        "
+            health_max = 10;
+            function Vec2() constructor {
+                static zero = 0;
+                zero++;
+            }
+            var v = new Vec2();
+            v.z = health_max;
+        ",
+        0,
+    );
+    harness_lint::<UndefinedIdentifier>(
+        "
+            var a = 0;
+            function foo() {
+                return a;
+            }
+        ",
+        1,
+    );
+    harness_lint::<UndefinedIdentifier>(
+        "
+            var inst = self;
+            var o = other;
+            var r = ref_create(self, \"x\");
+            var t = time_source_create(time_source_global, 1, time_source_units_frames, inst.fn);
+            self.x = o.x + r + t;
+        ",
+        0,
+    );
+}
+
 #[test]
 fn unnecessary_grouping() {
     harness_lint::<UnnecessaryGrouping>(
@@ -546,6 +724,50 @@ fn unnecessary_grouping() {
     );
 }
 
+#[test]
+fn unused_function() {
+    harness_lint::<UnusedFunction>(
+        "
+            function foo() {}
+            function Vec2() constructor {}
+            function count(n) {
+                return n > 0 ? count(n - 1) : 0;
+            }
+        ",
+        3,
+    );
+    harness_lint::<UnusedFunction>(
+        "
+            function foo() {}
+            function bar() {}
+            function baz() {}
+            function buzz() {}
+            function Vec2() constructor {}
+            // #[allow(unused_function)]
+            function called_by_extension() {}
+            foo();
+            script_execute(bar);
+            var callback = method(self, baz);
+            var index = asset_get_index(\"buzz\");
+            var v = new Vec2();
+        ",
+        0,
+    );
+    harness_lint_with_origin::<UnusedFunction>(
+        "
+            function jump() {}
+        ",
+        AstOrigin::ObjectEvent {
+            object: "obj_player".into(),
+            event: ObjectEvent {
+                event_type: EventType::Create,
+                number: 0,
+            },
+        },
+        0,
+    );
+}
+
 #[test]
 fn unused_parameter() {
     harness_lint::<UnusedParameter>(
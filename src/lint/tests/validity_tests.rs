@@ -106,3 +106,12 @@ fn logical_targets() {
     harness_valid("a = b++ && b;");
     harness_valid_but_linted::<InvalidComparison>("a = function(){} && b;");
 }
+
+#[test]
+fn static_targets() {
+    harness_valid("static a = 0;");
+    harness_valid("static a = function() {};");
+    harness_valid("static function a() {}");
+    harness_invalid("static a();");
+    harness_invalid("static static a = 0;");
+}
@@ -1,17 +1,106 @@
-use crate::parse::ParseVisitor;
+use crate::{FileId, parse::ParseVisitor};
+use std::{ops::Range, path::PathBuf};
 
-use super::{Expr, Stmt};
+use super::{Expr, Span, Stmt, Token};
 
 /// A collection of statements.
 #[derive(Debug, Clone, serde::Serialize)]
 #[serde(rename_all = "snake_case")]
 pub struct Ast {
     stmts: Vec<Stmt>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    trailing_trivia: Vec<Token>,
+    #[serde(skip)]
+    tokens: Vec<Token>,
+    #[serde(skip_serializing_if = "AstOrigin::is_unknown")]
+    origin: AstOrigin,
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    #[serde(skip)]
+    file_id: FileId,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    file_tags: Vec<(Span, Tag)>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    misplaced_file_tags: Vec<(Span, Tag)>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    region_tags: Vec<(Span, Tag)>,
 }
 impl Ast {
     /// Creates a new Ast with the given statements.
     pub fn new(stmts: Vec<Stmt>) -> Self {
-        Self { stmts }
+        Self {
+            stmts,
+            trailing_trivia: vec![],
+            tokens: vec![],
+            origin: AstOrigin::Unknown,
+            path: None,
+            file_id: 0,
+            file_tags: vec![],
+            misplaced_file_tags: vec![],
+            region_tags: vec![],
+        }
+    }
+
+    /// Get a reference to the tags written as `// #![tag]`, which apply to the entire file,
+    /// alongside the span of the comment they were written in.
+    pub fn file_tags(&self) -> &[(Span, Tag)] {
+        self.file_tags.as_ref()
+    }
+
+    /// Get a mutable reference to the tags that apply to the entire file.
+    pub fn file_tags_mut(&mut self) -> &mut Vec<(Span, Tag)> {
+        &mut self.file_tags
+    }
+
+    /// Get a reference to the file tags written after the first statement, which have no effect.
+    pub fn misplaced_file_tags(&self) -> &[(Span, Tag)] {
+        self.misplaced_file_tags.as_ref()
+    }
+
+    /// Get a mutable reference to the file tags written after the first statement.
+    pub fn misplaced_file_tags_mut(&mut self) -> &mut Vec<(Span, Tag)> {
+        &mut self.misplaced_file_tags
+    }
+
+    /// Get a reference to the tags written directly above a `#region`, alongside the span of the
+    /// region they apply to.
+    pub fn region_tags(&self) -> &[(Span, Tag)] {
+        self.region_tags.as_ref()
+    }
+
+    /// Get a mutable reference to the tags that apply to regions.
+    pub fn region_tags_mut(&mut self) -> &mut Vec<(Span, Tag)> {
+        &mut self.region_tags
+    }
+
+    /// Get a reference to where in the project this ast's source code came from.
+    pub fn origin(&self) -> &AstOrigin {
+        &self.origin
+    }
+
+    /// Set where in the project this ast's source code came from.
+    pub fn set_origin(&mut self, origin: AstOrigin) {
+        self.origin = origin;
+    }
+
+    /// Get the path to the file this ast was parsed from, if it came from one.
+    pub fn path(&self) -> Option<&PathBuf> {
+        self.path.as_ref()
+    }
+
+    /// Set the path to the file this ast was parsed from.
+    pub fn set_path(&mut self, path: PathBuf) {
+        self.path = Some(path);
+    }
+
+    /// Get the id of the file this ast was parsed from.
+    pub fn file_id(&self) -> FileId {
+        self.file_id
+    }
+
+    /// Set the id of the file this ast was parsed from.
+    pub fn set_file_id(&mut self, file_id: FileId) {
+        self.file_id = file_id;
     }
 
     /// Consumes the Ast into its inner collection of statements.
@@ -29,6 +118,57 @@ impl Ast {
         &mut self.stmts
     }
 
+    /// Get a reference to the trivia found after the final statement's line.
+    pub fn trailing_trivia(&self) -> &[Token] {
+        self.trailing_trivia.as_ref()
+    }
+
+    /// Get a mutable reference to the trivia found after the final statement's line.
+    pub fn trailing_trivia_mut(&mut self) -> &mut Vec<Token> {
+        &mut self.trailing_trivia
+    }
+
+    /// Get a reference to the significant (non-trivia) tokens the ast was parsed from. Only
+    /// recorded by [super::Parser::new_lossless].
+    pub fn tokens(&self) -> &[Token] {
+        self.tokens.as_ref()
+    }
+
+    /// Get a mutable reference to the significant tokens the ast was parsed from.
+    pub fn tokens_mut(&mut self) -> &mut Vec<Token> {
+        &mut self.tokens
+    }
+
+    /// Reproduces the source code this ast was parsed from by printing its significant tokens
+    /// alongside the trivia attached to its nodes, in order. Trivia that was never attached to a
+    /// node is lost, so only an ast created with [super::Parser::new_lossless] will reproduce the
+    /// source exactly. The source is used to look up the text of each token.
+    pub fn reconstruct(&self, source: &str) -> String {
+        fn collect_stmt_trivia(stmt: &Stmt, tokens: &mut Vec<Token>) {
+            if let Some(trivia) = stmt.trivia() {
+                tokens.extend(trivia.leading.iter().chain(&trivia.trailing).chain(&trivia.inner));
+            }
+            stmt.visit_child_stmts(|stmt| collect_stmt_trivia(stmt, tokens));
+            stmt.visit_child_exprs(|expr| collect_expr_trivia(expr, tokens));
+        }
+        fn collect_expr_trivia(expr: &Expr, tokens: &mut Vec<Token>) {
+            if let Some(trivia) = expr.trivia() {
+                tokens.extend(trivia.leading.iter().chain(&trivia.trailing).chain(&trivia.inner));
+            }
+            expr.visit_child_stmts(|stmt| collect_stmt_trivia(stmt, tokens));
+            expr.visit_child_exprs(|expr| collect_expr_trivia(expr, tokens));
+        }
+        let mut tokens: Vec<Token> = self.tokens.iter().chain(&self.trailing_trivia).copied().collect();
+        for stmt in self.stmts() {
+            collect_stmt_trivia(stmt, &mut tokens);
+        }
+        tokens.sort_by_key(|token| token.span.start());
+        tokens
+            .into_iter()
+            .map(|token| &source[Range::from(token.span)])
+            .collect()
+    }
+
     /// Returns all nodes in the ast that match the provided tag, or have any tag at all if None is
     /// provided.
     pub fn tagged_nodes(&self, tag: Option<&Tag>) -> Vec<Node> {
@@ -64,6 +204,78 @@ impl Ast {
     }
 }
 
+/// Where in a GameMaker project the source code of an [Ast] came from.
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize)]
+#[serde(rename_all = "snake_case", tag = "origin_kind")]
+pub enum AstOrigin {
+    /// The source code did not come from a recognized location, such as a file open in an editor
+    /// outside of a project.
+    #[default]
+    Unknown,
+    /// A script resource.
+    Script {
+        /// The name of the script.
+        name: String,
+    },
+    /// One of an object's events.
+    ObjectEvent {
+        /// The name of the object.
+        object: String,
+        /// The event the code runs in.
+        event: ObjectEvent,
+    },
+    /// The creation code of a room.
+    RoomCreationCode {
+        /// The name of the room.
+        room: String,
+    },
+    /// The creation code of an instance placed within a room.
+    InstanceCreationCode {
+        /// The name of the room.
+        room: String,
+        /// The name of the instance, such as `inst_5A3B1C2D`.
+        instance: String,
+    },
+}
+impl AstOrigin {
+    /// Returns whether the origin is [AstOrigin::Unknown].
+    pub fn is_unknown(&self) -> bool {
+        *self == Self::Unknown
+    }
+}
+
+/// An event within a GameMaker object.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct ObjectEvent {
+    /// The type of the event.
+    pub event_type: EventType,
+    /// The number of the event, distinguishing between events of the same type, such as the Draw
+    /// event (0) and the Draw GUI event (64).
+    pub number: u64,
+}
+
+/// The types of events GameMaker objects can have.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+#[allow(missing_docs)]
+pub enum EventType {
+    Create,
+    Destroy,
+    Alarm,
+    Step,
+    /// A collision with another object, containing the name of that object.
+    Collision(String),
+    Keyboard,
+    Mouse,
+    Other,
+    Draw,
+    KeyPress,
+    KeyRelease,
+    CleanUp,
+    Gesture,
+    PreCreate,
+}
+
 /// An identifier for an individual Ast.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Default, serde::Serialize)]
 pub struct AstId(u64);
@@ -94,4 +306,12 @@ impl Node {
             Node::Expr(expr) => expr.tag(),
         }
     }
+
+    /// Returns the span of the node.
+    pub fn span(&self) -> Span {
+        match self {
+            Node::Stmt(stmt) => stmt.span(),
+            Node::Expr(expr) => expr.span(),
+        }
+    }
 }
@@ -111,6 +111,16 @@ impl ExprKind {
             _ => false,
         }
     }
+
+    /// Returns if the Expr binds tightly enough to be accessed, called, or negated without being
+    /// wrapped in parenthesis (ie: `foo` in `foo[0]`, but not `a + b`).
+    pub fn binds_tightly(&self) -> bool {
+        match self {
+            ExprKind::Call(Call { uses_new, .. }) => !uses_new,
+            ExprKind::Identifier(_) | ExprKind::Access(_) | ExprKind::Grouping(_) | ExprKind::Literal(_) => true,
+            _ => false,
+        }
+    }
 }
 impl IntoExpr for ExprKind {}
 
@@ -126,6 +136,8 @@ pub struct Expr {
     location: Location,
     #[serde(skip_serializing_if = "Option::is_none")]
     tag: Option<Tag>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trivia: Option<Box<Trivia>>,
 }
 impl Expr {
     /// Get a reference to the inner ExprKind.
@@ -155,6 +167,15 @@ impl Expr {
         self.tag.as_ref()
     }
 
+    /// Returns the trivia surrounding this expression, if it was parsed losslessly.
+    pub fn trivia(&self) -> Option<&Trivia> {
+        self.trivia.as_deref()
+    }
+    /// Returns a mutable reference to the trivia surrounding this expression, creating it if needed.
+    pub fn trivia_mut(&mut self) -> &mut Trivia {
+        self.trivia.get_or_insert_with(Default::default)
+    }
+
     /// Get the expr's id.
     pub fn id(&self) -> ExprId {
         self.id
@@ -329,6 +350,16 @@ impl std::fmt::Display for Expr {
                 Literal::Undefined => f.pad("undefined"),
                 Literal::Noone => f.pad("noone"),
                 Literal::String(s) => f.pad(&format!("\"{}\"", s)),
+                Literal::Template(parts) => f.pad(&format!(
+                    "$\"{}\"",
+                    parts
+                        .iter()
+                        .map(|part| match part {
+                            TemplatePart::Text(text) => text.clone(),
+                            TemplatePart::Expr(expr) => format!("{{{expr}}}"),
+                        })
+                        .join("")
+                )),
                 Literal::Real(r) => f.pad(&r.to_string()),
                 Literal::Hex(h) => f.pad(&format!("hex<{}>", h)),
                 Literal::Array(members) => f.pad(&format!(
@@ -359,6 +390,7 @@ pub trait IntoExpr: Sized + Into<ExprKind> {
             id,
             location: Location(file_id, span),
             tag,
+            trivia: None,
         }
     }
 
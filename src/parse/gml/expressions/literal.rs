@@ -18,6 +18,8 @@ pub enum Literal {
     Noone,
     /// A string literal
     String(String),
+    /// A template string literal, containing interpolated expressions ($"foo {bar}")
+    Template(Vec<TemplatePart>),
     /// Any number
     Real(f64),
     /// A hex-format number
@@ -30,6 +32,16 @@ pub enum Literal {
     Misc(String),
 }
 
+/// A single piece of a template string.
+#[derive(Debug, PartialEq, Clone, serde::Serialize)]
+#[serde(tag = "part", content = "value", rename_all = "snake_case")]
+pub enum TemplatePart {
+    /// Raw text within the string, exactly as written in the source.
+    Text(String),
+    /// An expression interpolated into the string (ie: the `bar` in `$"foo {bar}"`).
+    Expr(Expr),
+}
+
 type StructField = (Identifier, Expr);
 #[derive(serde::Serialize)]
 struct SerializedStructField {
@@ -67,6 +79,13 @@ impl ParseVisitor for Literal {
                     visitor(value)
                 }
             }
+            Literal::Template(parts) => {
+                for part in parts.iter() {
+                    if let TemplatePart::Expr(expr) = part {
+                        visitor(expr)
+                    }
+                }
+            }
             _ => {}
         }
     }
@@ -82,6 +101,13 @@ impl ParseVisitor for Literal {
                     visitor(value)
                 }
             }
+            Literal::Template(parts) => {
+                for part in parts.iter_mut() {
+                    if let TemplatePart::Expr(expr) = part {
+                        visitor(expr)
+                    }
+                }
+            }
             _ => {}
         }
     }
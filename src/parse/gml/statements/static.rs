@@ -0,0 +1,31 @@
+use crate::parse::{Expr, IntoStmt, ParseVisitor, Stmt, StmtKind};
+
+/// Representation of a static declaration in gml, such as `static foo = 0;` or `static function
+/// foo() {}`.
+#[derive(Debug, PartialEq, Clone, serde::Serialize)]
+pub struct Static {
+    /// The declaration being made static, which is either an assignment or a function statement.
+    pub body: Stmt,
+}
+impl Static {
+    /// Creates a new static declaration wrapping the given statement.
+    pub fn new(body: Stmt) -> Self {
+        Self { body }
+    }
+}
+impl From<Static> for StmtKind {
+    fn from(stat: Static) -> Self {
+        Self::Static(stat)
+    }
+}
+impl IntoStmt for Static {}
+impl ParseVisitor for Static {
+    fn visit_child_exprs<E: FnMut(&Expr)>(&self, mut _visitor: E) {}
+    fn visit_child_exprs_mut<E: FnMut(&mut Expr)>(&mut self, _visitor: E) {}
+    fn visit_child_stmts<S: FnMut(&Stmt)>(&self, mut visitor: S) {
+        visitor(&self.body);
+    }
+    fn visit_child_stmts_mut<S: FnMut(&mut Stmt)>(&mut self, mut visitor: S) {
+        visitor(&mut self.body);
+    }
+}
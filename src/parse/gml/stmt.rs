@@ -16,6 +16,8 @@ pub enum StmtKind {
     Globalvar(Globalvar),
     /// Declaration of one or more local variables.
     LocalVariables(LocalVariables),
+    /// Declaration of a static variable or function.
+    Static(Static),
     /// Declaration of a try / catch call.
     TryCatch(TryCatch),
     /// A for loop.
@@ -48,6 +50,9 @@ pub enum StmtKind {
     Exit,
     /// An assignment statement.
     Assignment(Assignment),
+    /// A statement that could not be parsed. The error itself is reported by the parser, and the
+    /// statement's span covers the source that was skipped while recovering from it.
+    Error,
     /// A statement expression, or in other words, an expression being executed on its own.
     /// Common examples below:
     /// ```gml
@@ -67,6 +72,7 @@ impl ParseVisitor for StmtKind {
             StmtKind::Macro(inner) => inner.visit_child_exprs(visitor),
             StmtKind::Globalvar(inner) => inner.visit_child_exprs(visitor),
             StmtKind::LocalVariables(inner) => inner.visit_child_exprs(visitor),
+            StmtKind::Static(inner) => inner.visit_child_exprs(visitor),
             StmtKind::TryCatch(inner) => inner.visit_child_exprs(visitor),
             StmtKind::For(inner) => inner.visit_child_exprs(visitor),
             StmtKind::With(inner) => inner.visit_child_exprs(visitor),
@@ -81,7 +87,7 @@ impl ParseVisitor for StmtKind {
             StmtKind::Delete(inner) => inner.visit_child_exprs(visitor),
             StmtKind::Assignment(inner) => inner.visit_child_exprs(visitor),
             StmtKind::Expr(inner) => visitor(inner),
-            StmtKind::Break | StmtKind::Continue | StmtKind::Exit => {}
+            StmtKind::Break | StmtKind::Continue | StmtKind::Exit | StmtKind::Error => {}
         }
     }
     fn visit_child_exprs_mut<E>(&mut self, mut visitor: E)
@@ -93,6 +99,7 @@ impl ParseVisitor for StmtKind {
             StmtKind::Macro(inner) => inner.visit_child_exprs_mut(visitor),
             StmtKind::Globalvar(inner) => inner.visit_child_exprs_mut(visitor),
             StmtKind::LocalVariables(inner) => inner.visit_child_exprs_mut(visitor),
+            StmtKind::Static(inner) => inner.visit_child_exprs_mut(visitor),
             StmtKind::TryCatch(inner) => inner.visit_child_exprs_mut(visitor),
             StmtKind::For(inner) => inner.visit_child_exprs_mut(visitor),
             StmtKind::With(inner) => inner.visit_child_exprs_mut(visitor),
@@ -107,7 +114,7 @@ impl ParseVisitor for StmtKind {
             StmtKind::Delete(inner) => inner.visit_child_exprs_mut(visitor),
             StmtKind::Assignment(inner) => inner.visit_child_exprs_mut(visitor),
             StmtKind::Expr(inner) => visitor(inner),
-            StmtKind::Break | StmtKind::Continue | StmtKind::Exit => {}
+            StmtKind::Break | StmtKind::Continue | StmtKind::Exit | StmtKind::Error => {}
         }
     }
 
@@ -120,6 +127,7 @@ impl ParseVisitor for StmtKind {
             StmtKind::Macro(inner) => inner.visit_child_stmts(visitor),
             StmtKind::Globalvar(inner) => inner.visit_child_stmts(visitor),
             StmtKind::LocalVariables(inner) => inner.visit_child_stmts(visitor),
+            StmtKind::Static(inner) => inner.visit_child_stmts(visitor),
             StmtKind::TryCatch(inner) => inner.visit_child_stmts(visitor),
             StmtKind::For(inner) => inner.visit_child_stmts(visitor),
             StmtKind::With(inner) => inner.visit_child_stmts(visitor),
@@ -134,7 +142,7 @@ impl ParseVisitor for StmtKind {
             StmtKind::Delete(inner) => inner.visit_child_stmts(visitor),
             StmtKind::Assignment(inner) => inner.visit_child_stmts(visitor),
             StmtKind::Expr(_) => {}
-            StmtKind::Break | StmtKind::Continue | StmtKind::Exit => {}
+            StmtKind::Break | StmtKind::Continue | StmtKind::Exit | StmtKind::Error => {}
         }
     }
 
@@ -147,6 +155,7 @@ impl ParseVisitor for StmtKind {
             StmtKind::Macro(inner) => inner.visit_child_stmts_mut(visitor),
             StmtKind::Globalvar(inner) => inner.visit_child_stmts_mut(visitor),
             StmtKind::LocalVariables(inner) => inner.visit_child_stmts_mut(visitor),
+            StmtKind::Static(inner) => inner.visit_child_stmts_mut(visitor),
             StmtKind::TryCatch(inner) => inner.visit_child_stmts_mut(visitor),
             StmtKind::For(inner) => inner.visit_child_stmts_mut(visitor),
             StmtKind::With(inner) => inner.visit_child_stmts_mut(visitor),
@@ -161,7 +170,7 @@ impl ParseVisitor for StmtKind {
             StmtKind::Delete(inner) => inner.visit_child_stmts_mut(visitor),
             StmtKind::Assignment(inner) => inner.visit_child_stmts_mut(visitor),
             StmtKind::Expr(_) => {}
-            StmtKind::Break | StmtKind::Continue | StmtKind::Exit => {}
+            StmtKind::Break | StmtKind::Continue | StmtKind::Exit | StmtKind::Error => {}
         }
     }
 }
@@ -202,6 +211,8 @@ pub struct Stmt {
     location: Location,
     #[serde(skip_serializing_if = "Option::is_none")]
     tag: Option<Tag>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trivia: Option<Box<Trivia>>,
 }
 impl Stmt {
     /// Returns a reference to the inner StmtKind.
@@ -228,6 +239,19 @@ impl Stmt {
     pub fn tag(&self) -> Option<&Tag> {
         self.tag.as_ref()
     }
+    /// Sets the tag attached to this statement.
+    pub fn set_tag(&mut self, tag: Option<Tag>) {
+        self.tag = tag;
+    }
+
+    /// Returns the trivia surrounding this statement, if it was parsed losslessly.
+    pub fn trivia(&self) -> Option<&Trivia> {
+        self.trivia.as_deref()
+    }
+    /// Returns a mutable reference to the trivia surrounding this statement, creating it if needed.
+    pub fn trivia_mut(&mut self) -> &mut Trivia {
+        self.trivia.get_or_insert_with(Default::default)
+    }
 
     /// Get the stmt's id.
     pub fn id(&self) -> StmtId {
@@ -276,6 +300,7 @@ impl std::fmt::Display for Stmt {
                     })
                     .join(", ")
             )),
+            StmtKind::Static(stat) => f.pad(&format!("static {}", stat.body)),
             StmtKind::TryCatch(try_catch) => f.pad(&format!(
                 "try {{ ... }} catch {}{}",
                 try_catch.catch_expr,
@@ -309,6 +334,7 @@ impl std::fmt::Display for Stmt {
             StmtKind::Break => f.pad("break;"),
             StmtKind::Continue => f.pad("continue;"),
             StmtKind::Exit => f.pad("exit;"),
+            StmtKind::Error => f.pad("<error>"),
             StmtKind::Assignment(assign) => f.pad(&format!("{} {} {}", assign.left, assign.op.token(), assign.right)),
             StmtKind::Expr(expr) => f.pad(&expr.to_string()),
         }
@@ -325,6 +351,7 @@ pub trait IntoStmt: Sized + Into<StmtKind> {
             id,
             location: Location(file_id, span),
             tag,
+            trivia: None,
         }
     }
 
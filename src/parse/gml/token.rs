@@ -1,5 +1,5 @@
 #![allow(missing_docs)]
-use super::{AssignmentOp, EqualityOp, EvaluationOp, Literal, LogicalOp, PostfixOp, UnaryOp};
+use super::{AssignmentOp, EqualityOp, EvaluationOp, Literal, LogicalOp, PostfixOp, TemplatePart, UnaryOp};
 use crate::parse::Span;
 use std::fmt::Display;
 
@@ -21,6 +21,19 @@ impl Token {
     pub fn lazy(token_type: TokenKind) -> Self {
         Self::new(token_type, Span::default())
     }
+
+    /// Returns whether this token is trivia (whitespace, comments, tags and regions), which has no
+    /// bearing on the meaning of the code around it.
+    pub fn is_trivia(&self) -> bool {
+        matches!(
+            self.token_type,
+            TokenKind::Whitespace(_)
+                | TokenKind::Comment(_)
+                | TokenKind::Region(_)
+                | TokenKind::Tag(..)
+                | TokenKind::FileTag(..)
+        )
+    }
 }
 
 impl Token {
@@ -32,6 +45,8 @@ impl Token {
             TokenKind::Undefined => Some(Literal::Undefined),
             TokenKind::Noone => Some(Literal::Noone),
             TokenKind::StringLiteral(lexeme) => Some(Literal::String(lexeme.to_string())),
+            TokenKind::TemplateString("") => Some(Literal::Template(vec![])),
+            TokenKind::TemplateString(lexeme) => Some(Literal::Template(vec![TemplatePart::Text(lexeme.to_string())])),
             TokenKind::Real(value) => Some(Literal::Real(value)),
             TokenKind::Hex(lexeme) => Some(Literal::Hex(lexeme.to_string())),
             TokenKind::MiscConstant(lexeme) => Some(Literal::Misc(lexeme.to_string())),
@@ -228,10 +243,20 @@ pub enum TokenKind {
     LessThanGreaterThan,
     Macro(&'static str, Option<&'static str>, &'static str),
     Comment(&'static str),
+    Whitespace(&'static str),
+    Region(&'static str),
     Identifier(&'static str),
     Real(f64),
     StringLiteral(&'static str),
+    TemplateString(&'static str),
+    TemplateStart(&'static str),
+    TemplateMiddle(&'static str),
+    TemplateEnd(&'static str),
+    /// The rest of a template string that is never closed, which runs to the end of the file.
+    /// Contains the position of the string's opening `$"`.
+    UnterminatedTemplate(usize),
     Tag(&'static str, Option<&'static str>),
+    FileTag(&'static str, Option<&'static str>),
     Hex(&'static str),
     MiscConstant(&'static str),
     Invalid(&'static str),
@@ -341,9 +366,14 @@ impl Display for TokenKind {
                 };
             }
             TokenKind::Comment(body) => return f.pad(&format!("// {body}")),
+            TokenKind::Whitespace(lexeme) | TokenKind::Region(lexeme) => return f.pad(lexeme),
             TokenKind::Identifier(iden) => iden,
             TokenKind::Real(r) => return f.pad(&r.to_string()),
             TokenKind::StringLiteral(s) => return f.pad(&format!("\"{s}\"")),
+            TokenKind::TemplateString(s) => return f.pad(&format!("$\"{s}\"")),
+            TokenKind::TemplateStart(s) => return f.pad(&format!("$\"{s}{{")),
+            TokenKind::TemplateMiddle(s) => return f.pad(&format!("}}{s}{{")),
+            TokenKind::TemplateEnd(s) => return f.pad(&format!("}}{s}\"")),
             TokenKind::Tag(label, param) => {
                 if let Some(param) = param {
                     return f.pad(&format!("// #[{label}({param})]"));
@@ -351,8 +381,16 @@ impl Display for TokenKind {
                     return f.pad(&format!("// #[{label}]"));
                 }
             }
+            TokenKind::FileTag(label, param) => {
+                if let Some(param) = param {
+                    return f.pad(&format!("// #![{label}({param})]"));
+                } else {
+                    return f.pad(&format!("// #![{label}]"));
+                }
+            }
             TokenKind::Hex(hex) => hex,
             TokenKind::MiscConstant(con) => con,
+            TokenKind::UnterminatedTemplate(_) => "$\"",
             TokenKind::Invalid(_) => "INVALID_TOKEN",
             TokenKind::Eof => "",
         };
@@ -9,6 +9,9 @@ pub struct Lexer {
     source: &'static str,
     input_characters: Peekable<GraphemeIndices<'static>>,
     next_char_boundary: usize,
+    template_braces: Vec<usize>,
+    template_starts: Vec<usize>,
+    lossless: bool,
 }
 impl Lexer {
     /// Creates a new Lexer, taking a string of gml source.
@@ -17,14 +20,29 @@ impl Lexer {
             source,
             input_characters: source.grapheme_indices(true).peekable(),
             next_char_boundary: 1,
+            template_braces: vec![],
+            template_starts: vec![],
+            lossless: false,
         }
     }
 
+    /// Creates a new Lexer that also returns whitespace and `#region` lines as tokens, meaning that
+    /// the returned tokens cover every byte of the source.
+    pub fn new_lossless(source: &'static str) -> Self {
+        let mut lexer = Self::new(source);
+        lexer.lossless = true;
+        lexer
+    }
+
     /// Consumes the Lexer's source code until it identifies the next Token.
     fn lex(&mut self) -> Token {
         if let Some((start_index, chr)) = self.take() {
             let token_type = match chr {
-                id if id.is_whitespace() => return self.lex(),
+                id if id.is_whitespace() && !self.lossless => return self.lex(),
+                '\r' | '\n' => Some(TokenKind::Whitespace(
+                    &self.source[start_index..self.next_char_boundary],
+                )),
+                id if id.is_whitespace() => Some(TokenKind::Whitespace(self.consume_whitespace_on_line(start_index))),
                 '.' => {
                     if self.peek().is_some_and(|c| c.is_ascii_digit()) {
                         let lexeme = self.construct_number(start_index);
@@ -56,8 +74,28 @@ impl Lexer {
                         Some(TokenKind::Equal)
                     }
                 }
-                '{' => Some(TokenKind::LeftBrace),
-                '}' => Some(TokenKind::RightBrace),
+                '{' => {
+                    if let Some(depth) = self.template_braces.last_mut() {
+                        *depth += 1;
+                    }
+                    Some(TokenKind::LeftBrace)
+                }
+                '}' => match self.template_braces.last_mut() {
+                    // This closes the interpolation in a template string, so we resume the string
+                    Some(0) => {
+                        self.template_braces.pop();
+                        let template_start = self.template_starts.pop().unwrap_or(start_index);
+                        match self.construct_template_segment(template_start, false) {
+                            Some(token_type) => Some(token_type),
+                            None => return self.unterminated_template(start_index, template_start),
+                        }
+                    }
+                    Some(depth) => {
+                        *depth -= 1;
+                        Some(TokenKind::RightBrace)
+                    }
+                    None => Some(TokenKind::RightBrace),
+                },
                 '-' => {
                     if self.match_take('=') {
                         Some(TokenKind::MinusEqual)
@@ -223,6 +261,10 @@ impl Lexer {
                         &self.source[start_index + 1..self.next_char_boundary - 1],
                     ))
                 }
+                '$' if self.match_take('"') => match self.construct_template_segment(start_index, true) {
+                    Some(token_type) => Some(token_type),
+                    None => return self.unterminated_template(start_index, start_index),
+                },
                 '$' => {
                     let lexeme = self.construct_hex(self.next_char_boundary);
                     if !lexeme.is_empty() {
@@ -251,8 +293,11 @@ impl Lexer {
                     } else if self.match_take_str("#region", start_index)
                         || self.match_take_str("#endregion", start_index)
                     {
-                        self.discard_rest_of_line();
-                        self.lex()
+                        let lexeme = self.consume_rest_of_line(start_index);
+                        Token::new(
+                            TokenKind::Region(lexeme),
+                            Span::new(start_index, self.next_char_boundary),
+                        )
                     } else {
                         Token::new(TokenKind::Hash, Span::new(start_index, self.next_char_boundary))
                     };
@@ -273,8 +318,10 @@ impl Lexer {
                         // Eat up the whitespace first...
                         self.consume_whitespace_on_line(start_index);
 
-                        // See if this is an lint tag...
-                        if self.match_take('#') && self.match_take('[') {
+                        // See if this is an lint tag, which applies to the whole file if written as `#![tag]`...
+                        let is_tag = self.match_take('#');
+                        let file_level = is_tag && self.match_take('!');
+                        if is_tag && self.match_take('[') {
                             let tag = self.construct_word(self.next_char_boundary);
                             let parameter = if self.match_take('(') {
                                 let parameter = self.consume_until(self.next_char_boundary, ')');
@@ -285,7 +332,13 @@ impl Lexer {
                             };
                             if self.match_take(']') {
                                 self.consume_rest_of_line(self.next_char_boundary);
-                                Some(TokenKind::Tag(tag, parameter))
+                                Some(if file_level {
+                                    TokenKind::FileTag(tag, parameter)
+                                } else {
+                                    TokenKind::Tag(tag, parameter)
+                                })
+                            } else if self.lossless {
+                                Some(TokenKind::Comment(self.consume_rest_of_line(start_index)))
                             } else {
                                 return self.lex();
                             }
@@ -405,13 +458,6 @@ impl Lexer {
         &self.source[start_pos..self.next_char_boundary]
     }
 
-    /// Discards the remainder of the line.
-    fn discard_rest_of_line(&mut self) {
-        while self.peek().is_some_and(|chr| chr != '\r' && chr != '\n') {
-            self.take();
-        }
-    }
-
     /// Checks if the the following characters are upcoming in the source. If
     /// they are, consumes the characters.
     fn match_take_str(&mut self, lexeme: &str, start_pos: usize) -> bool {
@@ -472,6 +518,55 @@ impl Lexer {
         &self.source[start_pos..self.next_char_boundary]
     }
 
+    /// Consumes a segment of a template string (ie: `$"foo {bar} buzz"`), stopping after either the
+    /// opening brace of the next interpolation or the closing quote of the string. `template_start`
+    /// is the position of the string's opening `$"`, and `head` denotes if this segment is the
+    /// first in the string. Returns None if the string is never closed.
+    fn construct_template_segment(&mut self, template_start: usize, head: bool) -> Option<TokenKind> {
+        let start_position = self.next_char_boundary;
+        let mut in_escape = false;
+        while let Some((_, chr)) = self.take() {
+            if in_escape {
+                in_escape = false;
+                continue;
+            }
+            match chr {
+                '\\' => in_escape = true,
+                '{' => {
+                    self.template_braces.push(0);
+                    self.template_starts.push(template_start);
+                    let lexeme = &self.source[start_position..self.next_char_boundary - 1];
+                    return Some(if head {
+                        TokenKind::TemplateStart(lexeme)
+                    } else {
+                        TokenKind::TemplateMiddle(lexeme)
+                    });
+                }
+                '"' => {
+                    let lexeme = &self.source[start_position..self.next_char_boundary - 1];
+                    return Some(if head {
+                        TokenKind::TemplateString(lexeme)
+                    } else {
+                        TokenKind::TemplateEnd(lexeme)
+                    });
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Returns the token for a template string that was never closed, which covers the rest of the
+    /// source from `start_index`. `template_start` is the position of the string's opening `$"`.
+    fn unterminated_template(&mut self, start_index: usize, template_start: usize) -> Token {
+        self.template_braces.clear();
+        self.template_starts.clear();
+        Token::new(
+            TokenKind::UnterminatedTemplate(template_start),
+            Span::new(start_index, self.next_char_boundary),
+        )
+    }
+
     /// Consumes all upcoming characters that are whitespace into the string,
     /// stopping at the end of the line.
     fn consume_whitespace_on_line(&mut self, start_pos: usize) -> &'static str {
@@ -559,3 +654,7 @@ pub static MISC_GML_CONSTANTS: Lazy<HashSet<&'static str>> =
 /// Various built-in variables in gml that are not specificlly tracked in duck.
 pub static MISC_GML_VARIABLES: Lazy<HashSet<&'static str>> =
     Lazy::new(|| serde_json::from_str(include_str!("../../assets/misc_gml_variables.json")).unwrap());
+
+/// The built-in functions in gml.
+pub static GML_FUNCTIONS: Lazy<HashSet<&'static str>> =
+    Lazy::new(|| serde_json::from_str(include_str!("../../assets/gml_functions.json")).unwrap());
@@ -10,11 +10,20 @@ use std::{iter::Peekable, ops::Range};
 pub struct Parser {
     lexer: Peekable<Lexer>,
     cursor: usize,
+    last_token_end: usize,
     file_id: FileId,
-    comments: Vec<Token>,
+    trivia: Vec<Token>,
+    tokens: Vec<Token>,
+    lossless: bool,
     use_default_ids: bool,
     tag_queue: Option<Tag>,
-    active_tag: Option<Tag>,
+    file_tags: Vec<(Span, Tag)>,
+    misplaced_file_tags: Vec<(Span, Tag)>,
+    open_regions: Vec<(usize, Option<Tag>)>,
+    region_tags: Vec<(Span, Tag)>,
+    errors: Vec<Diagnostic<FileId>>,
+    tokens_taken: usize,
+    brace_depth: isize,
 }
 
 // Basic features
@@ -24,11 +33,20 @@ impl Parser {
         Self {
             lexer: Lexer::new(source_code).peekable(),
             cursor: 0,
+            last_token_end: 0,
             file_id,
-            comments: vec![],
+            trivia: vec![],
+            tokens: vec![],
+            lossless: false,
             use_default_ids: false,
             tag_queue: None,
-            active_tag: None,
+            file_tags: vec![],
+            misplaced_file_tags: vec![],
+            open_regions: vec![],
+            region_tags: vec![],
+            errors: vec![],
+            tokens_taken: 0,
+            brace_depth: 0,
         }
     }
 
@@ -40,21 +58,60 @@ impl Parser {
         parser
     }
 
+    /// Creates a new parser that keeps all of the source's trivia (whitespace, comments, tags and
+    /// regions), attaching it to the statements and expressions it surrounds. The resulting [Ast]
+    /// can reproduce its source exactly with [Ast::reconstruct].
+    pub fn new_lossless(source_code: &'static str, file_id: FileId) -> Self {
+        let mut parser = Self::new(source_code, file_id);
+        parser.lexer = Lexer::new_lossless(source_code).peekable();
+        parser.lossless = true;
+        parser
+    }
+
     /// Runs the parser through the entire source, collecting everything into an
     /// Ast and returning it.
     ///
     /// ### Errors
     ///
-    /// Returns a [ParseError] if any of the source code caused an error.
-    pub fn into_ast(mut self) -> Result<Ast, Diagnostic<FileId>> {
+    /// Returns the first [Diagnostic] encountered if any of the source code caused an error. Use
+    /// [Parser::into_partial_ast] to recover from errors instead.
+    pub fn into_ast(self) -> Result<Ast, Diagnostic<FileId>> {
+        let (ast, mut errors) = self.into_partial_ast();
+        if errors.is_empty() {
+            Ok(ast)
+        } else {
+            Err(errors.remove(0))
+        }
+    }
+
+    /// Runs the parser through the entire source, recovering from any errors it encounters by
+    /// skipping ahead to the next statement boundary. Statements that failed to parse are kept in
+    /// the Ast as [StmtKind::Error], and every error is returned alongside it.
+    pub fn into_partial_ast(mut self) -> (Ast, Vec<Diagnostic<FileId>>) {
         let mut statements = vec![];
         while self.soft_peek().is_some() {
-            statements.push(self.stmt()?);
+            statements.push(self.recovering_stmt());
         }
-        Ok(Ast::new(statements))
+        let mut ast = Ast::new(statements);
+        ast.set_file_id(self.file_id);
+
+        // Regions left open run until the end of the file
+        while let Some((start, tag)) = self.open_regions.pop() {
+            if let Some(tag) = tag {
+                self.region_tags.push((Span::new(start, self.last_token_end), tag));
+            }
+        }
+        *ast.file_tags_mut() = std::mem::take(&mut self.file_tags);
+        *ast.misplaced_file_tags_mut() = std::mem::take(&mut self.misplaced_file_tags);
+        *ast.region_tags_mut() = std::mem::take(&mut self.region_tags);
+        if self.lossless {
+            *ast.tokens_mut() = std::mem::take(&mut self.tokens);
+            attach_trivia(&mut ast, std::mem::take(&mut self.trivia), self.last_token_end);
+        }
+        (ast, self.errors)
     }
 
-    /// Creates a new expression.
+    /// Creates a new expression. Tags only ever belong to statements, see [Parser::stmt].
     fn new_expr(&mut self, expr: impl IntoExpr, span: Span) -> Expr {
         expr.into_expr(
             if self.use_default_ids {
@@ -64,7 +121,7 @@ impl Parser {
             },
             span,
             self.file_id,
-            self.tag_queue.as_ref().cloned(),
+            None,
         )
     }
 
@@ -78,14 +135,14 @@ impl Parser {
             },
             self.span(start_position),
             self.file_id,
-            self.active_tag.as_ref().cloned(),
+            None,
         )
     }
 
     /// Creates a [Span] from the given position up until the pilot's current
     /// position.
     fn span(&self, start: usize) -> Span {
-        Span::new(start, self.cursor + 1)
+        Span::new(start, self.last_token_end.max(start))
     }
 }
 
@@ -98,8 +155,11 @@ impl Parser {
     /// Returns a [ParseError] if any of the source code caused an error.
     pub fn stmt(&mut self) -> Result<Stmt, Diagnostic<FileId>> {
         self.collect_upcoming_comments(); // seeks out tags
-        self.active_tag = self.tag_queue.take();
-        let result = match self.peek()?.token_type {
+
+        // The tag is taken before anything else is parsed, as looking ahead past the end of this
+        // statement may already have queued the tag of the next one
+        let tag = self.tag_queue.take();
+        let mut stmt = match self.peek()?.token_type {
             TokenKind::Macro(name, config, body) => self.macro_declaration(name, config, body),
             TokenKind::Enum => self.enum_declaration(),
             TokenKind::Try => self.try_catch(),
@@ -119,10 +179,26 @@ impl Parser {
             TokenKind::Exit => self.exit(),
             TokenKind::Globalvar => self.globalvar_declaration(),
             TokenKind::Var => self.local_variable_series(),
+            TokenKind::Static => self.static_declaration(),
             _ => self.assignment(),
-        };
-        self.active_tag.take();
-        result
+        }?;
+        stmt.set_tag(tag);
+        Ok(stmt)
+    }
+
+    /// Parses a statement, recovering from any error by recording it, skipping to the next
+    /// statement boundary, and returning a [StmtKind::Error] in its place.
+    fn recovering_stmt(&mut self) -> Stmt {
+        let start = self.next_token_boundary();
+        let (tokens_taken, brace_depth) = (self.tokens_taken, self.brace_depth);
+        match self.stmt() {
+            Ok(stmt) => stmt,
+            Err(error) => {
+                self.errors.push(error);
+                self.synchronize(tokens_taken, brace_depth);
+                self.new_stmt(StmtKind::Error, start)
+            }
+        }
     }
 
     fn macro_declaration(&mut self, name: &str, config: Option<&str>, body: &str) -> Result<Stmt, Diagnostic<FileId>> {
@@ -264,7 +340,7 @@ impl Parser {
             loop {
                 match parser.peek()?.token_type {
                     TokenKind::Case | TokenKind::Default | TokenKind::RightBrace | TokenKind::End => break,
-                    _ => body.push(parser.stmt()?),
+                    _ => body.push(parser.recovering_stmt()),
                 }
             }
             Ok(body)
@@ -315,7 +391,8 @@ impl Parser {
             if let Some(token) = self.match_take_possibilities(&[TokenKind::RightBrace, TokenKind::End]) {
                 break token;
             } else {
-                statements.push(self.stmt()?);
+                self.peek()?; // an unclosed block can't be recovered from
+                statements.push(self.recovering_stmt());
             }
         };
         self.match_take_repeating(TokenKind::SemiColon);
@@ -415,6 +492,24 @@ impl Parser {
         Ok(self.new_stmt(LocalVariables::new(declarations), start))
     }
 
+    fn static_declaration(&mut self) -> Result<Stmt, Diagnostic<FileId>> {
+        let start = self.next_token_boundary();
+        self.require(TokenKind::Static)?;
+        let body = self.assignment()?;
+        match body.kind() {
+            StmtKind::Assignment(..) => {}
+            StmtKind::Expr(expr) if expr.kind().as_function().is_some() => {}
+            _ => {
+                return Err(Diagnostic::error()
+                    .with_message("Invalid static declaration")
+                    .with_labels(vec![Label::primary(self.file_id, body.span()).with_message(
+                        "only assignments and functions can be declared static".to_string(),
+                    )]));
+            }
+        }
+        Ok(self.new_stmt(Static::new(body), start))
+    }
+
     fn assignment(&mut self) -> Result<Stmt, Diagnostic<FileId>> {
         let start = self.next_token_boundary();
         let expr = self.unary()?; // Unaries are the highest possibel assignment expressions
@@ -447,7 +542,7 @@ impl Parser {
     }
 
     fn expr_stmt(&mut self, expr: Expr) -> Result<Stmt, Diagnostic<FileId>> {
-        let start = self.next_token_boundary();
+        let start = expr.span().start();
         match expr.kind() {
             ExprKind::Function(..)
             | ExprKind::Postfix(..)
@@ -667,8 +762,6 @@ impl Parser {
 
     fn function(&mut self) -> Result<Expr, Diagnostic<FileId>> {
         let start = self.next_token_boundary();
-        // TODO: when we do static-analysis, this will be used
-        let _static_token = self.match_take(TokenKind::Static);
         if self.match_take(TokenKind::Function).is_some() {
             let name = self.match_take_identifier()?;
             self.require(TokenKind::LeftParenthesis)?;
@@ -742,6 +835,34 @@ impl Parser {
         if let Some(literal) = self.peek()?.to_literal() {
             let token = self.take()?;
             Ok(self.new_expr(literal, Span::new(start, token.span.end())))
+        } else if let TokenKind::TemplateStart(text) = self.peek()?.token_type {
+            self.take()?;
+            let mut parts = vec![];
+            if !text.is_empty() {
+                parts.push(TemplatePart::Text(text.to_string()));
+            }
+            loop {
+                parts.push(TemplatePart::Expr(self.expr()?));
+                let token = self.take()?;
+                match token.token_type {
+                    TokenKind::TemplateMiddle(text) | TokenKind::TemplateEnd(text) if !text.is_empty() => {
+                        parts.push(TemplatePart::Text(text.to_string()))
+                    }
+                    TokenKind::TemplateMiddle(_) | TokenKind::TemplateEnd(_) => {}
+                    _ => {
+                        return Err(Diagnostic::error()
+                            .with_message("Unexpected token in template string")
+                            .with_labels(vec![
+                                Label::primary(self.file_id, token.span)
+                                    .with_message("this token is not valid in this position".to_string()),
+                            ])
+                            .with_notes(vec!["Expected `}`".into()]));
+                    }
+                }
+                if let TokenKind::TemplateEnd(_) = token.token_type {
+                    break Ok(self.new_expr(Literal::Template(parts), Span::new(start, token.span.end())));
+                }
+            }
         } else if self.match_take(TokenKind::LeftSquareBracket).is_some() {
             let mut elements = vec![];
             loop {
@@ -1003,9 +1124,6 @@ impl Parser {
     }
 
     fn identifier(&mut self) -> Result<Expr, Diagnostic<FileId>> {
-        // FIXME: This is our slightly ludicrous and temporary solution to the static keyword -- we just eat
-        // it. Until we have static analysis, it means nothing to us!
-        self.match_take(TokenKind::Static);
         if let Some(identifier) = self.match_take_identifier()? {
             let span = identifier.span;
             Ok(self.new_expr(identifier, span))
@@ -1035,11 +1153,7 @@ impl Parser {
 
     /// Consumes and returns the next token if it is within the array of types.
     fn match_take_possibilities(&mut self, token_types: &[TokenKind]) -> Option<Token> {
-        if self
-            .peek()
-            .map(|token| token_types.contains(&token.token_type))
-            .unwrap_or(false)
-        {
+        if self.peek().is_ok_and(|token| token_types.contains(&token.token_type)) {
             Some(self.take().unwrap())
         } else {
             None
@@ -1147,6 +1261,9 @@ impl Parser {
         let start = self.next_token_boundary();
         let next = self.lexer.peek();
         if let Some(next) = next {
+            if let TokenKind::UnterminatedTemplate(template_start) = next.token_type {
+                return Err(unterminated_template(self.file_id, template_start));
+            }
             Ok(next)
         } else {
             Err(Diagnostic::error()
@@ -1163,6 +1280,19 @@ impl Parser {
         let start = self.next_token_boundary();
         if let Some(token) = self.lexer.next() {
             self.cursor = token.span.0;
+            self.last_token_end = token.span.1;
+            self.tokens_taken += 1;
+            if self.lossless {
+                self.tokens.push(token);
+            }
+            match token.token_type {
+                TokenKind::LeftBrace | TokenKind::Begin => self.brace_depth += 1,
+                TokenKind::RightBrace | TokenKind::End => self.brace_depth -= 1,
+                TokenKind::UnterminatedTemplate(template_start) => {
+                    return Err(unterminated_template(self.file_id, template_start));
+                }
+                _ => {}
+            }
             Ok(token)
         } else {
             Err(Diagnostic::error()
@@ -1173,20 +1303,99 @@ impl Parser {
         }
     }
 
-    /// Looks ahead at the next token and collects it if it is a comment (including lint tags).
+    /// Discards tokens until reaching the next statement boundary after an error. A semicolon is
+    /// consumed as the end of the broken statement, while a closing brace or a keyword that begins
+    /// a statement is left for the parser to continue from. Braces opened since the statement
+    /// began are skipped over in their entirety, and at least one token is always consumed so
+    /// that the parser is guaranteed to make progress.
+    fn synchronize(&mut self, tokens_taken: usize, brace_depth: isize) {
+        while let Some(token_type) = self.soft_peek().map(|token| token.token_type) {
+            let made_progress = self.tokens_taken > tokens_taken;
+            let nested = self.brace_depth > brace_depth;
+            match token_type {
+                TokenKind::SemiColon if !nested => {
+                    self.match_take_repeating(TokenKind::SemiColon);
+                    break;
+                }
+                TokenKind::RightBrace | TokenKind::End if !nested && made_progress => break,
+                TokenKind::RightBrace | TokenKind::End if !nested => {
+                    // A stray closing delimiter is the entire error
+                    let _ = self.take();
+                    break;
+                }
+                TokenKind::Macro(..)
+                | TokenKind::Enum
+                | TokenKind::Try
+                | TokenKind::For
+                | TokenKind::With
+                | TokenKind::Repeat
+                | TokenKind::Do
+                | TokenKind::While
+                | TokenKind::If
+                | TokenKind::Switch
+                | TokenKind::Case
+                | TokenKind::Default
+                | TokenKind::Return
+                | TokenKind::Throw
+                | TokenKind::Delete
+                | TokenKind::Break
+                | TokenKind::Continue
+                | TokenKind::Exit
+                | TokenKind::Globalvar
+                | TokenKind::Var
+                | TokenKind::Static
+                | TokenKind::Function
+                    if !nested && made_progress =>
+                {
+                    break;
+                }
+                _ => {
+                    let _ = self.take();
+                }
+            }
+        }
+    }
+
+    /// Looks ahead at the next token and collects it if it is trivia (including lint tags). A tag
+    /// written directly above a region applies to the entire region, and file tags apply to the
+    /// entire file. File tags are only recognized before the first statement, and any found later
+    /// are kept aside so they can be reported.
     fn collect_upcoming_comments(&mut self) {
         loop {
             match self.lexer.peek() {
                 Some(Token {
-                    token_type: TokenKind::Comment(_),
+                    token_type: TokenKind::Comment(_) | TokenKind::Whitespace(_),
                     ..
-                }) => self.comments.push(self.lexer.next().unwrap()),
+                }) => self.trivia.push(self.lexer.next().unwrap()),
+                Some(Token {
+                    token_type: TokenKind::Region(region),
+                    span,
+                }) => {
+                    if region.starts_with("#region") {
+                        self.open_regions.push((span.start(), self.tag_queue.take()));
+                    } else if let Some((start, Some(tag))) = self.open_regions.pop() {
+                        self.region_tags.push((Span::new(start, span.end()), tag));
+                    }
+                    self.trivia.push(self.lexer.next().unwrap());
+                }
                 Some(Token {
                     token_type: TokenKind::Tag(label, parameter),
                     ..
                 }) => {
                     self.tag_queue = Some(Tag(label.to_string(), parameter.map(|v| v.to_string())));
-                    self.lexer.next().unwrap();
+                    self.trivia.push(self.lexer.next().unwrap());
+                }
+                Some(Token {
+                    token_type: TokenKind::FileTag(label, parameter),
+                    span,
+                }) => {
+                    let file_tag = (*span, Tag(label.to_string(), parameter.map(|v| v.to_string())));
+                    if self.tokens_taken == 0 {
+                        self.file_tags.push(file_tag);
+                    } else {
+                        self.misplaced_file_tags.push(file_tag);
+                    }
+                    self.trivia.push(self.lexer.next().unwrap());
                 }
                 _ => break,
             }
@@ -1194,9 +1403,20 @@ impl Parser {
     }
 }
 
+/// Creates the error for a template string that is never closed, which swallows the rest of the
+/// file. The error points at the string's opening `$"`.
+fn unterminated_template(file_id: FileId, template_start: usize) -> Diagnostic<FileId> {
+    let opening_quote = template_start..template_start + 2;
+    Diagnostic::error()
+        .with_message("Unterminated template string")
+        .with_labels(vec![
+            Label::primary(file_id, opening_quote).with_message("this template string is never closed"),
+        ])
+}
+
 /// A start and end cursor measured in characters, used for expressing small sections of source
 /// code.
-#[derive(Debug, PartialEq, Default, Copy, Clone, serde::Serialize)]
+#[derive(Debug, PartialEq, Default, Copy, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Span(usize, usize);
 impl Span {
     /// Creates a new span.
@@ -1220,6 +1440,10 @@ impl Span {
     pub fn end(&self) -> usize {
         self.1
     }
+    /// Returns whether the other span lies entirely within this one.
+    pub fn contains(&self, other: Span) -> bool {
+        self.0 <= other.0 && other.1 <= self.1
+    }
 }
 impl From<Span> for Range<usize> {
     fn from(span: Span) -> Self {
@@ -1228,5 +1452,5 @@ impl From<Span> for Range<usize> {
 }
 
 /// A location for something in gml, combining a span and a file id.
-#[derive(Debug, PartialEq, Default, Copy, Clone, serde::Serialize)]
+#[derive(Debug, PartialEq, Default, Copy, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Location(pub FileId, pub Span);
@@ -1,3 +1,4 @@
 mod expr_tests;
+mod recovery_tests;
 mod stmt_tests;
 mod token_parsing;
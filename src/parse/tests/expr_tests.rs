@@ -20,12 +20,6 @@ expr_test!(
     Function::new(Identifier::lazy("foo"), vec![], Block::lazy(vec![]).into_stmt_lazy())
 );
 
-expr_test!(
-    static_function,
-    "static function foo() {}",
-    Function::new(Identifier::lazy("foo"), vec![], Block::lazy(vec![]).into_stmt_lazy())
-);
-
 expr_test!(
     function_with_parameters,
     "function foo(bar, baz) {}",
@@ -947,6 +941,42 @@ expr_test!(
     ExprKind::Literal(Literal::String("\nfoo\"\nfoo".into()))
 );
 
+expr_test!(
+    template_string,
+    "$\"foo\"",
+    ExprKind::Literal(Literal::Template(vec![TemplatePart::Text("foo".into())]))
+);
+
+expr_test!(
+    template_string_interpolation,
+    "$\"foo {bar} buzz {1 + 2}\"",
+    ExprKind::Literal(Literal::Template(vec![
+        TemplatePart::Text("foo ".into()),
+        TemplatePart::Expr(Identifier::lazy("bar").into_expr_lazy()),
+        TemplatePart::Text(" buzz ".into()),
+        TemplatePart::Expr(
+            Evaluation::new(
+                Literal::Real(1.0).into_expr_lazy(),
+                EvaluationOp::Plus(Token::lazy(TokenKind::Plus)),
+                Literal::Real(2.0).into_expr_lazy(),
+            )
+            .into_expr_lazy()
+        ),
+    ]))
+);
+
+expr_test!(
+    template_string_nested,
+    "$\"{ {a: $\"{b}\"} }\"",
+    ExprKind::Literal(Literal::Template(vec![TemplatePart::Expr(
+        Literal::Struct(vec![(
+            Identifier::lazy("a"),
+            Literal::Template(vec![TemplatePart::Expr(Identifier::lazy("b").into_expr_lazy())]).into_expr_lazy()
+        )])
+        .into_expr_lazy()
+    )]))
+);
+
 expr_test!(dollar_hex, "$a0f9a0", ExprKind::Literal(Literal::Hex("a0f9a0".into())));
 
 expr_test!(short_hex, "$20", ExprKind::Literal(Literal::Hex("20".into())));
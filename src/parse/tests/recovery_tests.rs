@@ -0,0 +1,94 @@
+use crate::parse::*;
+
+fn harness_recovery(source: &'static str, expected_errors: usize) -> Ast {
+    let (ast, errors) = Parser::new_with_default_ids(source, 0).into_partial_ast();
+    assert_eq!(
+        errors.len(),
+        expected_errors,
+        "`{}` produced the wrong number of errors: {:#?}",
+        source,
+        errors
+    );
+    ast
+}
+
+fn stmt_kinds(stmts: &[Stmt]) -> Vec<String> {
+    stmts
+        .iter()
+        .map(|stmt| match stmt.kind() {
+            StmtKind::Error => "error".into(),
+            StmtKind::Assignment(_) => "assignment".into(),
+            StmtKind::Expr(_) => "expr".into(),
+            StmtKind::LocalVariables(_) => "var".into(),
+            StmtKind::If(_) => "if".into(),
+            _ => "other".into(),
+        })
+        .collect()
+}
+
+#[test]
+fn recovers_at_semicolon() {
+    let ast = harness_recovery("a = ; b = 1;", 1);
+    assert_eq!(stmt_kinds(ast.stmts()), vec!["error", "assignment"]);
+}
+
+#[test]
+fn unterminated_template_string() {
+    let (_, errors) = Parser::new_with_default_ids("a = 1;\nb = $\"foo {a} bar;\nc = 2;", 0).into_partial_ast();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "Unterminated template string");
+    harness_recovery("a = $\"foo", 1);
+    harness_recovery("a = $\"{b", 1);
+}
+
+#[test]
+fn recovers_at_keyword() {
+    let ast = harness_recovery("a = )\nvar b = 1;\nif b {}", 1);
+    assert_eq!(stmt_kinds(ast.stmts()), vec!["error", "var", "if"]);
+}
+
+#[test]
+fn reports_every_error() {
+    let ast = harness_recovery("a = ;\nb = 1;\nc = );\nd = 2;", 2);
+    assert_eq!(
+        stmt_kinds(ast.stmts()),
+        vec!["error", "assignment", "error", "assignment"]
+    );
+}
+
+#[test]
+fn recovers_inside_block() {
+    let ast = harness_recovery("if a { b = ; c = 1; }\nd = 1;", 1);
+    assert_eq!(stmt_kinds(ast.stmts()), vec!["if", "assignment"]);
+    let StmtKind::If(If { body, .. }) = ast.stmts()[0].kind() else {
+        unreachable!()
+    };
+    assert_eq!(
+        stmt_kinds(&body.kind().as_block().unwrap().body),
+        vec!["error", "assignment"]
+    );
+}
+
+#[test]
+fn skips_nested_braces() {
+    let ast = harness_recovery("a = { b: , c: { d: 1 } };\ne = 1;", 1);
+    assert_eq!(stmt_kinds(ast.stmts()), vec!["error", "assignment"]);
+}
+
+#[test]
+fn recovers_from_stray_closing_brace() {
+    let ast = harness_recovery("}\na = 1;", 1);
+    assert_eq!(stmt_kinds(ast.stmts()), vec!["error", "assignment"]);
+}
+
+#[test]
+fn unclosed_block() {
+    let ast = harness_recovery("a = 1;\nif a {\nb = 1;", 1);
+    assert_eq!(stmt_kinds(ast.stmts()), vec!["assignment", "error"]);
+}
+
+#[test]
+fn into_ast_returns_first_error() {
+    assert!(Parser::new_with_default_ids("a = ; b = );", 0).into_ast().is_err());
+    assert!(Parser::new_with_default_ids("a = 1; b = 2;", 0).into_ast().is_ok());
+}
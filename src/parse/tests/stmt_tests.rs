@@ -526,10 +526,37 @@ stmt_test!(
 stmt_test!(
     static_assign,
     "static foo = 1",
-    Assignment::new(
-        Identifier::lazy("foo").into_expr_lazy(),
-        AssignmentOp::Identity(Token::lazy(TokenKind::Equal)),
-        Literal::Real(1.0).into_expr_lazy(),
+    Static::new(
+        Assignment::new(
+            Identifier::lazy("foo").into_expr_lazy(),
+            AssignmentOp::Identity(Token::lazy(TokenKind::Equal)),
+            Literal::Real(1.0).into_expr_lazy(),
+        )
+        .into_stmt_lazy()
+    )
+);
+
+stmt_test!(
+    static_function,
+    "static function foo() {}",
+    Static::new(
+        StmtKind::Expr(
+            Function::new(Identifier::lazy("foo"), vec![], Block::lazy(vec![]).into_stmt_lazy()).into_expr_lazy()
+        )
+        .into_stmt_lazy()
+    )
+);
+
+stmt_test!(
+    static_method,
+    "static foo = function() {}",
+    Static::new(
+        Assignment::new(
+            Identifier::lazy("foo").into_expr_lazy(),
+            AssignmentOp::Identity(Token::lazy(TokenKind::Equal)),
+            Function::new_anonymous(vec![], Block::lazy(vec![]).into_stmt_lazy()).into_expr_lazy(),
+        )
+        .into_stmt_lazy()
     )
 );
 
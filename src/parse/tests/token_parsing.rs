@@ -18,6 +18,20 @@ macro_rules! token_test {
     };
 }
 
+macro_rules! lossless_token_test {
+     ($name:ident: $src:expr => $($should_be:expr), * $(,)?) => {
+        #[cfg(test)]
+        #[test]
+        fn $name() {
+            let expected = vec![$($should_be, )*];
+            let outputed = Lexer::new_lossless($src)
+                .map(|token| token.token_type)
+                .collect::<Vec<TokenKind>>();
+            assert_eq!(*outputed, expected)
+        }
+    };
+}
+
 token_test!(whitepsace: " \n\t" =>);
 token_test!(float: "0.2" => Real(0.2));
 token_test!(float_no_prefix_digit: ".2" => Real(0.2));
@@ -28,6 +42,12 @@ token_test!(invalid_hex: "$q" => DollarSign, Identifier("q"));
 token_test!(string: "\"foo\"" => StringLiteral("foo"));
 token_test!(multiline_string: "@\"\n\nfoo\"" => StringLiteral("\n\nfoo"));
 token_test!(multiline_string_alt: "@'\n\nfoo'" => StringLiteral("\n\nfoo"));
+token_test!(template_string: "$\"foo\"" => TemplateString("foo"));
+token_test!(template_string_interpolation: "$\"a {b} c {d}\"" => TemplateStart("a "), Identifier("b"), TemplateMiddle(" c "), Identifier("d"), TemplateEnd(""));
+token_test!(template_string_braces: "$\"{ {} }\" {}" => TemplateStart(""), LeftBrace, RightBrace, TemplateEnd(""), LeftBrace, RightBrace);
+token_test!(template_string_escape: "$\"\\{\\\"\"" => TemplateString("\\{\\\""));
+token_test!(template_string_unterminated: "$\"foo\nbar = 1;" => UnterminatedTemplate(0));
+token_test!(template_string_unterminated_after_interpolation: "a = $\"a {b} c;" => Identifier("a"), Equal, TemplateStart("a "), Identifier("b"), UnterminatedTemplate(4));
 token_test!(identifier: "foo" => Identifier("foo"));
 token_test!(macro_declaration: "#macro foo 0" => Macro("foo", None, "0"));
 token_test!(macro_declaration_with_config: "#macro bar:foo 0" => Macro("foo", Some("bar"), "0"));
@@ -39,6 +59,8 @@ token_test!(tag: "// #[enum_string]" => Tag("enum_string", None));
 token_test!(tag_extra_slashes: "///// #[enum_string]" => Tag("enum_string", None));
 token_test!(tag_freeform_param: "///// #[tag(with random stuff inside!)]" => Tag("tag", Some("with random stuff inside!")));
 token_test!(parameter_tag: "// #[allow(and_keyword)]" => Tag("allow", Some("and_keyword")));
+token_test!(file_tag: "// #![allow(show_debug_message)]" => FileTag("allow", Some("show_debug_message")));
+token_test!(invalid_file_tag: "// #!allow" => Comment("// #!allow"));
 token_test!(
     keywords:
     "switch case break return enum default and or function constructor exit global
@@ -62,6 +84,10 @@ token_test!(
 token_test!(non_standard_utf8: "🦆" => Invalid("🦆"));
 token_test!(non_standard_utf8_ending_comment: "// 🦆" => Comment("// 🦆"));
 token_test!(empty_comment: "//" => Comment("//"));
+token_test!(region: "#region foo\n#endregion" => Region("#region foo"), Region("#endregion"));
+lossless_token_test!(lossless_whitespace: " \t\n\r\n  " => Whitespace(" \t"), Whitespace("\n"), Whitespace("\r\n"), Whitespace("  "));
+lossless_token_test!(lossless_region: "#region foo\n#endregion" => Region("#region foo"), Whitespace("\n"), Region("#endregion"));
+lossless_token_test!(lossless_invalid_tag: "// #[allow" => Comment("// #[allow"));
 
 #[test]
 fn constants() {
@@ -0,0 +1,163 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use hashbrown::HashMap;
+
+use super::{Ast, Expr, ParseVisitor, Span, Stmt, Token, TokenKind};
+
+/// The trivia (whitespace, comments, tags and regions) surrounding a [Stmt] or [Expr]. Only
+/// collected when parsing with [super::Parser::new_lossless].
+#[derive(Debug, PartialEq, Clone, Default, serde::Serialize)]
+pub struct Trivia {
+    /// Trivia found before the node, after the end of the line of the previous node.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub leading: Vec<Token>,
+    /// Trivia found after the node, up to and including the end of its line.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub trailing: Vec<Token>,
+    /// Trivia found within the node that no child could claim, such as a comment within an empty
+    /// set of parenthesis.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub inner: Vec<Token>,
+}
+
+/// Distributes the trivia tokens collected while parsing onto the nodes of the ast. Each run of
+/// trivia is split at its first newline: everything up to it trails the node that ends before the
+/// run, and the rest leads the node that begins after it. When several nodes share a boundary,
+/// the outermost one receives the trivia.
+pub(super) fn attach_trivia(ast: &mut Ast, trivia: Vec<Token>, significant_end: usize) {
+    let mut starts = BTreeSet::new();
+    let mut ends = BTreeSet::new();
+    for stmt in ast.stmts() {
+        collect_stmt_bounds(stmt, &mut starts, &mut ends);
+    }
+
+    let mut attachments = Attachments::default();
+    for run in trivia_runs(trivia) {
+        let prev_end = run[0].span.start();
+        let next_start = run[run.len() - 1].span.end();
+        let has_prev = prev_end > 0;
+        let split = if has_prev {
+            run.iter()
+                .position(|token| matches!(token.token_type, TokenKind::Whitespace(ws) if ws.contains('\n')))
+                .map_or(run.len(), |i| i + 1)
+        } else {
+            0
+        };
+        let mut leading = run;
+        let mut trailing: Vec<Token> = leading.drain(..split).collect();
+        if has_prev && ends.contains(&prev_end) {
+            attachments.trailing.entry(prev_end).or_default().append(&mut trailing);
+        } else {
+            trailing.append(&mut leading);
+            leading = trailing;
+        }
+        if leading.is_empty() {
+            continue;
+        }
+        if prev_end >= significant_end {
+            ast.trailing_trivia_mut().append(&mut leading);
+        } else if starts.contains(&next_start) {
+            attachments.leading.entry(next_start).or_default().append(&mut leading);
+        } else if has_prev && ends.contains(&prev_end) {
+            attachments.trailing.entry(prev_end).or_default().append(&mut leading);
+        } else {
+            attachments.inner.insert(prev_end, (next_start, leading));
+        }
+    }
+
+    for stmt in ast.stmts_mut() {
+        attach_to_stmt(stmt, &mut attachments);
+    }
+    // Anything still unclaimed lies outside of every statement, so we keep it on the ast itself
+    for (_, (_, mut tokens)) in std::mem::take(&mut attachments.inner) {
+        ast.trailing_trivia_mut().append(&mut tokens);
+    }
+}
+
+/// Trivia waiting to be attached to nodes, keyed by the positions they border.
+#[derive(Default)]
+struct Attachments {
+    leading: HashMap<usize, Vec<Token>>,
+    trailing: HashMap<usize, Vec<Token>>,
+    inner: BTreeMap<usize, (usize, Vec<Token>)>,
+}
+impl Attachments {
+    /// Returns the trivia that should be attached to a node with the given span.
+    fn claim(&mut self, span: Span) -> Option<Trivia> {
+        let leading = self.leading.remove(&span.start()).unwrap_or_default();
+        let trailing = self.trailing.remove(&span.end()).unwrap_or_default();
+        if leading.is_empty() && trailing.is_empty() {
+            None
+        } else {
+            Some(Trivia {
+                leading,
+                trailing,
+                inner: vec![],
+            })
+        }
+    }
+
+    /// Returns all inner trivia that falls within the given span.
+    fn claim_inner(&mut self, span: Span) -> Vec<Token> {
+        let contained: Vec<usize> = self
+            .inner
+            .range(span.start()..=span.end())
+            .filter(|(_, (end, _))| *end <= span.end())
+            .map(|(start, _)| *start)
+            .collect();
+        contained
+            .into_iter()
+            .flat_map(|start| self.inner.remove(&start).unwrap().1)
+            .collect()
+    }
+}
+
+/// Splits the trivia into runs that are not interrupted by any significant tokens.
+fn trivia_runs(trivia: Vec<Token>) -> Vec<Vec<Token>> {
+    let mut runs: Vec<Vec<Token>> = vec![];
+    for token in trivia {
+        match runs.last_mut() {
+            Some(run) if run[run.len() - 1].span.end() == token.span.start() => run.push(token),
+            _ => runs.push(vec![token]),
+        }
+    }
+    runs
+}
+
+fn collect_stmt_bounds(stmt: &Stmt, starts: &mut BTreeSet<usize>, ends: &mut BTreeSet<usize>) {
+    starts.insert(stmt.span().start());
+    ends.insert(stmt.span().end());
+    stmt.visit_child_stmts(|stmt| collect_stmt_bounds(stmt, starts, ends));
+    stmt.visit_child_exprs(|expr| collect_expr_bounds(expr, starts, ends));
+}
+
+fn collect_expr_bounds(expr: &Expr, starts: &mut BTreeSet<usize>, ends: &mut BTreeSet<usize>) {
+    starts.insert(expr.span().start());
+    ends.insert(expr.span().end());
+    expr.visit_child_stmts(|stmt| collect_stmt_bounds(stmt, starts, ends));
+    expr.visit_child_exprs(|expr| collect_expr_bounds(expr, starts, ends));
+}
+
+fn attach_to_stmt(stmt: &mut Stmt, attachments: &mut Attachments) {
+    if let Some(trivia) = attachments.claim(stmt.span()) {
+        *stmt.trivia_mut() = trivia;
+    }
+    stmt.visit_child_stmts_mut(|stmt| attach_to_stmt(stmt, attachments));
+    stmt.visit_child_exprs_mut(|expr| attach_to_expr(expr, attachments));
+    let mut inner = attachments.claim_inner(stmt.span());
+    if !inner.is_empty() {
+        stmt.trivia_mut().inner.append(&mut inner);
+    }
+}
+
+fn attach_to_expr(expr: &mut Expr, attachments: &mut Attachments) {
+    if let Some(trivia) = attachments.claim(expr.span()) {
+        *expr.trivia_mut() = trivia;
+    }
+    expr.visit_child_stmts_mut(|stmt| attach_to_stmt(stmt, attachments));
+    expr.visit_child_exprs_mut(|expr| attach_to_expr(expr, attachments));
+    let mut inner = attachments.claim_inner(expr.span());
+    if !inner.is_empty() {
+        expr.trivia_mut().inner.append(&mut inner);
+    }
+}
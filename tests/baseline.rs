@@ -0,0 +1,72 @@
+mod common;
+
+use common::{Project, duck_run, run};
+use serde_json::Value;
+
+/// Creates a project on disk containing a single script with the given source.
+fn project(name: &str, gml: &str) -> Project {
+    let project = Project::new(name, &[("scripts/test.gml", gml)]);
+    project.create_dir("objects");
+    project.create_dir("rooms");
+    project
+}
+
+#[test]
+fn write_baseline_records_diagnostics() {
+    let directory = project("write", "bar = ds_list_find_value(list, 0);\n");
+    let baseline = directory.join("baseline.json");
+    let (records, status) = run(&directory, &["--write-baseline", baseline.to_str().unwrap()]);
+    assert!(records.is_empty());
+    assert_eq!(status, 0);
+    let entries: Value = serde_json::from_str(&std::fs::read_to_string(&baseline).unwrap()).unwrap();
+    let entries = entries["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["lint"], "accessor_alternative");
+    assert_eq!(entries[0]["file"], "scripts/test.gml");
+    assert_eq!(entries[0]["count"], 1);
+}
+
+#[test]
+fn baseline_survives_moved_lines() {
+    let directory = project("moved", "bar = ds_list_find_value(list, 0);\n");
+    let baseline = directory.join("baseline.json");
+    run(&directory, &["--write-baseline", baseline.to_str().unwrap()]);
+    directory.write(
+        "scripts/test.gml",
+        "foo = 0;\n\n    bar = ds_list_find_value(list, 0);\n",
+    );
+    let (records, status) = run(&directory, &["--baseline", baseline.to_str().unwrap()]);
+    assert!(records.is_empty());
+    assert_eq!(status, 0);
+}
+
+#[test]
+fn baseline_reports_new_diagnostics() {
+    let directory = project("new", "bar = ds_list_find_value(list, 0);\n");
+    let baseline = directory.join("baseline.json");
+    run(&directory, &["--write-baseline", baseline.to_str().unwrap()]);
+    directory.write(
+        "scripts/test.gml",
+        "bar = ds_list_find_value(list, 0);\nbaz = ds_map_find_value(map, 0);\nbar = ds_list_find_value(list, 0);\n",
+    );
+    let (records, status) = run(&directory, &["--baseline", baseline.to_str().unwrap()]);
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0]["start"]["line"], 2);
+    assert_eq!(records[1]["start"]["line"], 3);
+    assert_eq!(status, 1);
+}
+
+#[test]
+fn missing_baseline() {
+    let directory = project("missing", "foo = 0;\n");
+    let output = duck_run(&directory, "human")
+        .args(["--baseline", "does_not_exist.json"])
+        .output()
+        .expect("failed to run duck");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("Failed to read the baseline")
+    );
+}
@@ -0,0 +1,219 @@
+mod common;
+
+use codespan_reporting::files::Files;
+use common::Project;
+use duck::{Config, Duck, RunSummary, cache::Cache, lint::LintLevel};
+use std::path::Path;
+
+/// Creates a project on disk containing the given scripts.
+fn project(name: &str, scripts: &[(&str, &str)]) -> Project {
+    let project = Project::new(name, &[]);
+    for (name, source) in scripts {
+        project.write(&format!("scripts/{name}"), source);
+    }
+    project
+}
+
+/// Runs duck on the project with caching enabled.
+async fn run(directory: &Path, config: Config) -> RunSummary {
+    let mut duck = Duck::new(config);
+    duck.set_caching(true);
+    duck.run(directory).await.unwrap()
+}
+
+/// Returns the lint, file name, message and position of every diagnostic in the run, sorted.
+fn diagnostics(summary: &RunSummary) -> Vec<(String, String, String, usize)> {
+    let mut diagnostics: Vec<_> = summary
+        .diagnostics()
+        .iter()
+        .map(|report| {
+            let label = &report.diagnostic.labels[0];
+            let name = summary.files().name(label.file_id).unwrap();
+            let name = Path::new(&name).file_name().unwrap().to_string_lossy().to_string();
+            (
+                report.diagnostic.code.clone().unwrap_or_default(),
+                name,
+                report.diagnostic.message.clone(),
+                label.range.start,
+            )
+        })
+        .collect();
+    diagnostics.sort();
+    diagnostics
+}
+
+/// Replaces text within the cache file, as a way to tell whether a run reused its contents.
+fn tamper_with_cache(directory: &Path, from: &str, to: &str) {
+    let path = Cache::path(directory);
+    let cache = std::fs::read_to_string(&path).unwrap();
+    assert!(cache.contains(from));
+    std::fs::write(path, cache.replace(from, to)).unwrap();
+}
+
+#[tokio::test]
+async fn cached_runs_match_uncached_runs() {
+    let directory = project(
+        "match",
+        &[
+            ("a.gml", "bar = ds_list_find_value(list, 0);\nfoo = 0 && 1;"),
+            (
+                "b.gml",
+                "function foo(a) {\n    // #[allow(and_preference)]\n    return a;\n}",
+            ),
+            ("c.gml", "var = ;"),
+        ],
+    );
+    let uncached = Duck::new(Config::default()).run(&directory).await.unwrap();
+    let first = run(&directory, Config::default()).await;
+    let second = run(&directory, Config::default()).await;
+    assert!(Cache::path(&directory).exists());
+    assert!(directory.join(".duck/.gitignore").exists());
+    assert!(!diagnostics(&uncached).is_empty());
+    assert_eq!(diagnostics(&first), diagnostics(&uncached));
+    assert_eq!(diagnostics(&second), diagnostics(&uncached));
+    assert_eq!(second.warning_count(), uncached.warning_count());
+    assert_eq!(second.denial_count(), uncached.denial_count());
+}
+
+#[tokio::test]
+async fn unchanged_files_are_reused() {
+    let directory = project(
+        "reused",
+        &[
+            ("a.gml", "bar = ds_list_find_value(list, 0);"),
+            ("b.gml", "baz = ds_map_find_value(map, 0);"),
+        ],
+    );
+    run(&directory, Config::default()).await;
+    tamper_with_cache(&directory, "Use of `ds_list_find_value`", "Cached");
+    tamper_with_cache(&directory, "Use of `ds_map_find_value`", "Cached");
+    std::fs::write(directory.join("scripts/b.gml"), "baz = ds_map_find_value(map, 1);").unwrap();
+    let summary = run(&directory, Config::default()).await;
+    let messages: Vec<_> = diagnostics(&summary)
+        .into_iter()
+        .map(|(_, file, message, _)| (file, message))
+        .collect();
+    assert_eq!(
+        messages,
+        vec![
+            ("a.gml".into(), "Cached".into()),
+            ("b.gml".into(), "Use of `ds_map_find_value`".into())
+        ]
+    );
+}
+
+#[tokio::test]
+async fn new_declarations_relint_unchanged_files() {
+    let directory = project(
+        "declarations",
+        &[("a.gml", "function foo(bar) {\n    return bar;\n}"), ("b.gml", "")],
+    );
+    let summary = run(&directory, Config::default()).await;
+    assert!(diagnostics(&summary).is_empty());
+    std::fs::write(directory.join("scripts/b.gml"), "function bar() {}").unwrap();
+    let summary = run(&directory, Config::default()).await;
+    let lints: Vec<_> = diagnostics(&summary)
+        .into_iter()
+        .map(|(lint, file, ..)| (lint, file))
+        .collect();
+    assert_eq!(lints, vec![("fucntion_name_as_parameter".into(), "a.gml".into())]);
+}
+
+#[tokio::test]
+async fn changing_the_config_invalidates_the_cache() {
+    let directory = project("config", &[("a.gml", "bar = ds_list_find_value(list, 0);")]);
+    let summary = run(&directory, Config::default()).await;
+    assert_eq!(summary.warning_count(), 1);
+    let mut config = Config::default();
+    config
+        .lint_levels
+        .insert("accessor_alternative".into(), LintLevel::Allow);
+    let summary = run(&directory, config).await;
+    assert!(summary.diagnostics().is_empty());
+}
+
+#[tokio::test]
+async fn reused_diagnostics_point_at_declarations_in_other_files() {
+    let directory = project(
+        "labels",
+        &[
+            ("a.gml", "switch foo {\n    case Foo.Bar: break;\n}"),
+            ("b.gml", "enum Foo {\n    Bar,\n    Buzz,\n}"),
+            ("c.gml", "foo = 0;"),
+        ],
+    );
+    let labels = |summary: &RunSummary| -> Vec<(String, usize)> {
+        summary
+            .diagnostics()
+            .iter()
+            .filter(|report| report.diagnostic.code.as_deref() == Some("missing_case_member"))
+            .flat_map(|report| report.diagnostic.labels.iter())
+            .map(|label| {
+                let name = summary.files().name(label.file_id).unwrap();
+                let name = Path::new(&name).file_name().unwrap().to_string_lossy().to_string();
+                (name, label.range.start)
+            })
+            .collect()
+    };
+    let expected = vec![("a.gml".to_string(), 0), ("b.gml".to_string(), 24)];
+    assert_eq!(labels(&run(&directory, Config::default()).await), expected);
+    std::fs::write(directory.join("scripts/c.gml"), "foo = 1;").unwrap();
+    let summary = run(&directory, Config::default()).await;
+    assert_eq!(labels(&summary), expected);
+}
+
+#[tokio::test]
+async fn moving_declarations_keeps_the_late_pass_of_other_files() {
+    let directory = project(
+        "moved",
+        &[
+            ("a.gml", "function foo(bar) {\n    return bar;\n}"),
+            ("b.gml", "function bar() {}"),
+        ],
+    );
+    run(&directory, Config::default()).await;
+    tamper_with_cache(&directory, "Function name as parameter", "Cached");
+    std::fs::write(directory.join("scripts/b.gml"), "// bar\nfunction bar() {}").unwrap();
+    let summary = run(&directory, Config::default()).await;
+    let messages: Vec<_> = diagnostics(&summary)
+        .into_iter()
+        .map(|(_, file, message, _)| (file, message))
+        .collect();
+    assert_eq!(messages, vec![("a.gml".into(), "Cached".into())]);
+}
+
+#[tokio::test]
+async fn diagnostics_pointing_into_changed_files_are_found_again() {
+    let directory = project(
+        "moved_labels",
+        &[
+            ("a.gml", "switch foo {\n    case Foo.Bar: break;\n}"),
+            ("b.gml", "enum Foo {\n    Bar,\n    Buzz,\n}"),
+        ],
+    );
+    run(&directory, Config::default()).await;
+    std::fs::write(
+        directory.join("scripts/b.gml"),
+        "// Foo\nenum Foo {\n    Bar,\n    Buzz,\n}",
+    )
+    .unwrap();
+    let summary = run(&directory, Config::default()).await;
+    let label = summary
+        .diagnostics()
+        .iter()
+        .find(|report| report.diagnostic.code.as_deref() == Some("missing_case_member"))
+        .map(|report| report.diagnostic.labels[1].range.start);
+    assert_eq!(label, Some(31));
+}
+
+#[tokio::test]
+async fn early_pass_tags_stay_used_when_the_late_pass_runs_again() {
+    let directory = project(
+        "tags",
+        &[("a.gml", "// #[allow(and_preference)]\nfoo = 0 and 1;"), ("b.gml", "")],
+    );
+    run(&directory, Config::default()).await;
+    std::fs::write(directory.join("scripts/b.gml"), "function bar() {}").unwrap();
+    let summary = run(&directory, Config::default()).await;
+    assert!(diagnostics(&summary).iter().all(|(lint, ..)| lint != "unused_allow"));
+}
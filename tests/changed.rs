@@ -0,0 +1,130 @@
+mod common;
+
+use common::{Project, duck_run, records};
+use std::{
+    io::Write,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+/// Creates a project on disk containing a warning in each of `a.gml` and `b.gml`, with `b.gml`
+/// declaring a function that `a.gml` uses as a parameter name.
+fn project(name: &str) -> Project {
+    Project::new(
+        name,
+        &[
+            (
+                "scripts/a.gml",
+                "bar = ds_list_find_value(list, 0);\nfunction foo(baz) {\n    return baz;\n}",
+            ),
+            ("scripts/b.gml", "bar = ds_map_find_value(map, 0);\nfunction baz() {}"),
+        ],
+    )
+}
+
+/// Runs `duck run` on the project with the given extra arguments and stdin, returning the lint and
+/// file name of every diagnostic it reported, sorted.
+fn run(directory: &Path, args: &[&str], stdin: &str) -> Vec<(String, String)> {
+    run_from(directory, directory, args, stdin).0
+}
+
+/// Runs `duck run` on the project from within another directory, returning the diagnostics it
+/// reported alongside what it printed to stderr.
+fn run_from(directory: &Path, current_directory: &Path, args: &[&str], stdin: &str) -> (Vec<(String, String)>, String) {
+    let mut child = duck_run(directory, "json")
+        .args(args)
+        .current_dir(current_directory)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to run duck");
+    child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    let mut diagnostics: Vec<_> = records(&output.stdout)
+        .iter()
+        .map(|record| {
+            (
+                record["lint"].as_str().unwrap().to_string(),
+                record["file"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    diagnostics.sort();
+    (diagnostics, String::from_utf8_lossy(&output.stderr).into_owned())
+}
+
+/// Runs git within the directory, panicking if it fails.
+fn git(directory: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(["-c", "user.name=duck", "-c", "user.email=duck@example.com"])
+        .args(args)
+        .current_dir(directory)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .expect("failed to run git");
+    assert!(status.success());
+}
+
+#[test]
+fn explicit_files() {
+    let directory = project("explicit");
+    let diagnostics = run(&directory, &["scripts/a.gml"], "");
+    assert_eq!(
+        diagnostics,
+        vec![
+            ("accessor_alternative".into(), "scripts/a.gml".into()),
+            ("fucntion_name_as_parameter".into(), "scripts/a.gml".into()),
+        ]
+    );
+}
+
+#[test]
+fn files_from_stdin() {
+    let directory = project("stdin");
+    let diagnostics = run(&directory, &["-"], "scripts/b.gml\n\n");
+    assert_eq!(
+        diagnostics,
+        vec![("accessor_alternative".into(), "scripts/b.gml".into())]
+    );
+}
+
+#[test]
+fn changed_since() {
+    let directory = project("git");
+    git(&directory, &["init", "-q"]);
+    git(&directory, &["add", "."]);
+    git(&directory, &["commit", "-q", "-m", "initial"]);
+    directory.write("scripts/b.gml", "bar = ds_map_find_value(map, 1);\nfunction baz() {}");
+    directory.write("scripts/c.gml", "qux = ds_grid_get(grid, 0, 0);");
+    let diagnostics = run(&directory, &["--changed-since", "HEAD"], "");
+    assert_eq!(
+        diagnostics,
+        vec![
+            ("accessor_alternative".into(), "scripts/b.gml".into()),
+            ("accessor_alternative".into(), "scripts/c.gml".into()),
+        ]
+    );
+}
+
+#[test]
+fn relative_paths_are_found_within_the_project() {
+    let directory = project("relative");
+    let (diagnostics, _) = run_from(&directory, &std::env::temp_dir(), &["scripts/b.gml"], "");
+    assert_eq!(
+        diagnostics,
+        vec![("accessor_alternative".into(), "scripts/b.gml".into())]
+    );
+}
+
+#[test]
+fn missing_files_are_reported() {
+    let directory = project("missing");
+    let (diagnostics, stderr) = run_from(&directory, &directory, &["-"], "scripts/b.gml\nscripts/c.gml\n");
+    assert!(stderr.contains("scripts/c.gml does not exist"), "{stderr}");
+    assert_eq!(
+        diagnostics,
+        vec![("accessor_alternative".into(), "scripts/b.gml".into())]
+    );
+}
@@ -0,0 +1,85 @@
+//! Helpers shared by the integration tests that work with projects on disk.
+
+#![allow(dead_code)]
+
+use serde_json::Value;
+use std::{
+    ops::Deref,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// A project written to a temporary directory, which is removed once it is dropped.
+pub struct Project(PathBuf);
+impl Project {
+    /// Creates a project from a list of file paths and their contents. The name must be unique
+    /// within its test file, as the tests within it run in parallel.
+    pub fn new(name: &str, files: &[(&str, &str)]) -> Self {
+        let directory = std::env::temp_dir().join(format!(
+            "duck_{}_{name}_{}",
+            env!("CARGO_CRATE_NAME"),
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+        let project = Self(directory);
+        for (path, contents) in files {
+            project.write(path, contents);
+        }
+        project
+    }
+
+    /// Writes a file within the project, creating any folders it needs.
+    pub fn write(&self, path: &str, contents: &str) {
+        let path = self.0.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    /// Creates an empty folder within the project, such as the `objects` and `rooms` folders that
+    /// discovery reports as missing otherwise.
+    pub fn create_dir(&self, path: &str) {
+        std::fs::create_dir_all(self.0.join(path)).unwrap();
+    }
+
+    /// Returns the `file://` uri of a file within the project.
+    pub fn uri(&self, path: &str) -> String {
+        format!("file://{}", self.0.join(path).display())
+    }
+}
+impl Deref for Project {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+impl Drop for Project {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Returns a command that runs `duck run` on the project, printing its diagnostics in the given
+/// format.
+pub fn duck_run(directory: &Path, format: &str) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_duck"));
+    command.args(["run", "--format", format, "--path"]).arg(directory);
+    command
+}
+
+/// Runs `duck run` on the project with the given extra arguments, returning the diagnostics it
+/// printed as json and its status.
+pub fn run(directory: &Path, args: &[&str]) -> (Vec<Value>, i32) {
+    let output = duck_run(directory, "json")
+        .args(args)
+        .output()
+        .expect("failed to run duck");
+    (records(&output.stdout), output.status.code().unwrap())
+}
+
+/// Parses the diagnostics `duck run --format json` printed.
+pub fn records(stdout: &[u8]) -> Vec<Value> {
+    let records: Value = serde_json::from_slice(stdout).unwrap();
+    records.as_array().unwrap().clone()
+}
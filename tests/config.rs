@@ -0,0 +1,219 @@
+mod common;
+
+use common::Project;
+use duck::{Casing, Config, ConfigWarning, Duck, Glob, GlobError, lint::LintLevel};
+use std::path::Path;
+
+#[test]
+fn valid_configs_have_no_warnings() {
+    let text = toml::Value::try_from(Config::full()).unwrap().to_string();
+    let (_, warnings) = Config::from_toml(&text).unwrap();
+    assert_eq!(warnings, vec![]);
+}
+
+#[test]
+fn unknown_lints_are_reported_with_suggestions() {
+    let (config, warnings) =
+        Config::from_toml("[lint_levels]\nmissing_case_membr = \"deny\"\nnot_a_real_lint = \"allow\"\ntodo = \"deny\"")
+            .unwrap();
+    assert_eq!(
+        warnings,
+        vec![
+            ConfigWarning::UnknownLint {
+                tag: "missing_case_membr".into(),
+                suggestion: Some("missing_case_member".into()),
+            },
+            ConfigWarning::UnknownLint {
+                tag: "not_a_real_lint".into(),
+                suggestion: None,
+            },
+        ]
+    );
+    assert_eq!(config.lint_levels.get("todo"), Some(&LintLevel::Deny));
+    assert_eq!(config.lint_levels.len(), 1);
+}
+
+#[test]
+fn unknown_fields_are_reported_with_suggestions() {
+    let (config, warnings) =
+        Config::from_toml("max_argument = 3\ntodo_keyword = \"fixme\"\n[formatting_rules]\nindent_size = 2").unwrap();
+    assert_eq!(
+        warnings,
+        vec![
+            ConfigWarning::UnknownField {
+                field: "formatting_rules.indent_size".into(),
+                suggestion: Some("indent_width".into()),
+            },
+            ConfigWarning::UnknownField {
+                field: "max_argument".into(),
+                suggestion: Some("max_arguments".into()),
+            },
+        ]
+    );
+    assert_eq!(config.todo_keyword, "fixme");
+}
+
+#[test]
+fn invalid_values_fall_back_to_defaults() {
+    let (config, warnings) =
+        Config::from_toml("max_arguments = \"seven\"\ntodo_keyword = \"fixme\"\n[lint_levels]\ntodo = \"dney\"")
+            .unwrap();
+    let fields: Vec<_> = warnings
+        .iter()
+        .map(|warning| match warning {
+            ConfigWarning::InvalidValue { field, .. } => field.as_str(),
+            _ => panic!("unexpected warning: {warning}"),
+        })
+        .collect();
+    assert_eq!(fields, vec!["lint_levels.todo", "max_arguments"]);
+    assert_eq!(config.max_arguments, Config::default().max_arguments);
+    assert_eq!(config.todo_keyword, "fixme");
+    assert!(config.lint_levels.is_empty());
+}
+
+#[test]
+fn invalid_toml_is_an_error() {
+    assert!(Config::from_toml("max_arguments = ").is_err());
+}
+
+#[test]
+fn globs_match_relative_paths() {
+    let glob = Glob::new("scripts/lib_*").unwrap();
+    assert!(glob.is_match(Path::new("scripts/lib_json/lib_json.gml")));
+    assert!(glob.is_match(Path::new("scripts/lib_json")));
+    assert!(!glob.is_match(Path::new("scripts/my_lib_json/my_lib_json.gml")));
+    assert!(!glob.is_match(Path::new("objects/scripts/lib_json/lib_json.gml")));
+
+    let glob = Glob::new("**/Draw_*.gml").unwrap();
+    assert!(glob.is_match(Path::new("objects/obj_player/Draw_0.gml")));
+    assert!(glob.is_match(Path::new("Draw_64.gml")));
+    assert!(!glob.is_match(Path::new("objects/obj_player/Step_0.gml")));
+
+    let glob = Glob::new("rooms/rm_[!t]?").unwrap();
+    assert!(glob.is_match(Path::new("rooms/rm_a1/RoomCreationCode.gml")));
+    assert!(!glob.is_match(Path::new("rooms/rm_t1/RoomCreationCode.gml")));
+}
+
+const OVERRIDES: &str = r#"
+todo_keyword = "fixme"
+
+[lint_levels]
+show_debug_message = "warn"
+
+[[overrides]]
+files = ["scripts/lib_*"]
+todo_keyword = "later"
+
+[overrides.lint_levels]
+show_debug_message = "allow"
+
+[overrides.casing_rules]
+constructor_rule = "any"
+function_rule = "camel"
+enum_rule = "any"
+enum_member_rule = "any"
+macro_rule = "any"
+global_rule = "any"
+local_var_rule = "any"
+struct_field = "any"
+"#;
+
+#[test]
+fn overrides_apply_to_matching_files() {
+    let (config, warnings) = Config::from_toml(OVERRIDES).unwrap();
+    assert_eq!(warnings, vec![]);
+    assert!(config.for_file(Path::new("scripts/scr_main/scr_main.gml")).is_none());
+
+    let library_config = config.for_file(Path::new("scripts/lib_json/lib_json.gml")).unwrap();
+    assert_eq!(library_config.todo_keyword, "later");
+    assert_eq!(
+        library_config.lint_levels.get("show_debug_message"),
+        Some(&LintLevel::Allow)
+    );
+    assert_eq!(library_config.casing_rules.function_rule, Casing::Camel);
+    assert!(library_config.overrides.is_empty());
+}
+
+#[test]
+fn overrides_are_validated() {
+    let (config, warnings) = Config::from_toml(
+        "[[overrides]]\nfiles = [\"scripts/lib_*\"]\nmax_argument = 3\n\n[[overrides]]\nmax_arguments = 3",
+    )
+    .unwrap();
+    assert_eq!(
+        warnings,
+        vec![
+            ConfigWarning::UnknownField {
+                field: "overrides[0].max_argument".into(),
+                suggestion: Some("max_arguments".into()),
+            },
+            ConfigWarning::InvalidValue {
+                field: "overrides[1]".into(),
+                message: "missing the `files` it applies to".into(),
+            },
+        ]
+    );
+    assert_eq!(config.overrides.len(), 1);
+}
+
+#[test]
+fn project_fields_in_overrides_are_reported() {
+    let (config, warnings) = Config::from_toml(
+        "[[overrides]]\nfiles = [\"scripts/lib_*\"]\nignored_file_paths = [\"scripts/lib_old\"]\n\n[[overrides.overrides]]\nfiles = [\"scripts/lib_json\"]",
+    )
+    .unwrap();
+    assert_eq!(
+        warnings,
+        vec![
+            ConfigWarning::ProjectFieldInOverride {
+                field: "overrides[0].overrides".into(),
+            },
+            ConfigWarning::ProjectFieldInOverride {
+                field: "overrides[0].ignored_file_paths".into(),
+            },
+        ]
+    );
+    assert!(config.overrides[0].settings.is_empty());
+}
+
+#[test]
+fn globs_with_unclosed_brackets_are_invalid() {
+    assert!(matches!(Glob::new("rooms/rm_[abc"), Err(GlobError::UnclosedBracket)));
+    assert!(matches!(Glob::new("rooms/rm_[z-a]"), Err(GlobError::InvalidClass(_))));
+    let (_, warnings) = Config::from_toml("[[overrides]]\nfiles = [\"scripts/[lib\"]").unwrap();
+    assert_eq!(
+        warnings,
+        vec![ConfigWarning::InvalidValue {
+            field: "overrides[0]".into(),
+            message: "invalid glob in `files`: unclosed `[`".into(),
+        }]
+    );
+}
+
+#[tokio::test]
+async fn overrides_are_used_when_linting() {
+    let directory = Project::new(
+        "overrides",
+        &[
+            ("scripts/scr_main/scr_main.gml", "show_debug_message(0);"),
+            ("scripts/lib_json/lib_json.gml", "show_debug_message(0);"),
+        ],
+    );
+    let (config, _) = Config::from_toml(OVERRIDES).unwrap();
+    let summary = Duck::new(config).run(&directory).await.unwrap();
+    let files: Vec<_> = summary
+        .diagnostics()
+        .iter()
+        .filter(|report| report.diagnostic.code.as_deref() == Some("show_debug_message"))
+        .map(|report| {
+            summary
+                .files()
+                .get(report.diagnostic.labels[0].file_id)
+                .unwrap()
+                .name()
+                .clone()
+        })
+        .collect();
+    assert_eq!(files.len(), 1);
+    assert!(files[0].ends_with("scr_main.gml"));
+}
@@ -0,0 +1,327 @@
+mod common;
+
+use common::Project;
+use duck::{
+    IgnoreList, driver,
+    parse::{AstOrigin, EventType, ObjectEvent},
+    project,
+};
+use std::path::Path;
+
+/// Runs discovery on a project, returning the paths it found relative to the project and the
+/// number of io errors it encountered.
+async fn discover(directory: &Path, ignored_file_paths: Vec<String>) -> (Vec<String>, usize) {
+    let (ignore_list, _) = IgnoreList::load(directory, &ignored_file_paths);
+    let (mut path_receiver, handle) = driver::start_gml_discovery(directory, ignore_list);
+    let mut paths = vec![];
+    while let Some(path) = path_receiver.recv().await {
+        paths.push(
+            path.strip_prefix(directory)
+                .unwrap()
+                .to_string_lossy()
+                .replace('\\', "/"),
+        );
+    }
+    let io_errors = handle.await.unwrap();
+    paths.sort();
+    (paths, io_errors.len())
+}
+
+const YYP: &str = r#"{
+  "resources": [
+    {"id":{"name":"scr_main","path":"scripts/scr_main/scr_main.yy",},"order":0,},
+    {"id":{"name":"obj_player","path":"objects/obj_player/obj_player.yy",},"order":1,},
+    {"id":{"name":"rm_start","path":"rooms/rm_start/rm_start.yy",},"order":2,},
+    {"id":{"name":"tl_intro","path":"timelines/tl_intro/tl_intro.yy",},"order":3,},
+    {"id":{"name":"ext_tools","path":"extensions/ext_tools/ext_tools.yy",},"order":4,},
+    {"id":{"name":"nt_readme","path":"notes/nt_readme/nt_readme.yy",},"order":5,},
+  ],
+  "resourceType": "GMProject",
+}"#;
+
+const OBJECT: &str = r#"{
+  "eventList": [
+    {"collisionObjectId":null,"eventNum":0,"eventType":0,"isDnD":false,"resourceType":"GMEvent",},
+    {"collisionObjectId":null,"eventNum":2,"eventType":3,"isDnD":false,"resourceType":"GMEvent",},
+    {"collisionObjectId":{"name":"obj_wall","path":"objects/obj_wall/obj_wall.yy",},"eventNum":0,"eventType":4,"isDnD":false,"resourceType":"GMEvent",},
+    {"collisionObjectId":null,"eventNum":64,"eventType":8,"isDnD":false,"resourceType":"GMEvent",},
+  ],
+  "name": "obj_player",
+  "resourceType": "GMObject",
+}"#;
+
+const ROOM: &str = r#"{
+  "creationCodeFile": "rooms/rm_start/RoomCreationCode.gml",
+  "layers": [
+    {"instances":[
+      {"hasCreationCode":true,"name":"inst_1A2B3C","resourceType":"GMRInstance",},
+      {"hasCreationCode":false,"name":"inst_4D5E6F","resourceType":"GMRInstance",},
+    ],"layers":[
+      {"instances":[{"hasCreationCode":true,"name":"inst_778899","resourceType":"GMRInstance",},],"layers":[],},
+    ],"resourceType":"GMRInstanceLayer",},
+  ],
+  "name": "rm_start",
+  "resourceType": "GMRoom",
+}"#;
+
+#[tokio::test]
+async fn discovers_project_resources() {
+    let directory = Project::new(
+        "resources",
+        &[
+            ("game.yyp", YYP),
+            (
+                "scripts/scr_main/scr_main.yy",
+                r#"{"isDnD":false,"resourceType":"GMScript",}"#,
+            ),
+            ("scripts/scr_main/scr_main.gml", ""),
+            ("objects/obj_player/obj_player.yy", OBJECT),
+            ("objects/obj_player/Create_0.gml", ""),
+            ("objects/obj_player/Step_2.gml", ""),
+            ("objects/obj_player/Collision_obj_wall.gml", ""),
+            ("objects/obj_player/Draw_64.gml", ""),
+            ("rooms/rm_start/rm_start.yy", ROOM),
+            ("rooms/rm_start/RoomCreationCode.gml", ""),
+            ("rooms/rm_start/InstanceCreationCode_inst_1A2B3C.gml", ""),
+            ("rooms/rm_start/InstanceCreationCode_inst_778899.gml", ""),
+            (
+                "timelines/tl_intro/tl_intro.yy",
+                r#"{"momentList":[{"moment":0,},{"moment":30,},],"resourceType":"GMTimeline",}"#,
+            ),
+            ("timelines/tl_intro/moment_0.gml", ""),
+            ("timelines/tl_intro/moment_30.gml", ""),
+            (
+                "extensions/ext_tools/ext_tools.yy",
+                r#"{"files":[{"filename":"ext_tools.gml",},{"filename":"ext_tools.dll",},],"resourceType":"GMExtension",}"#,
+            ),
+            ("extensions/ext_tools/ext_tools.gml", ""),
+            ("notes/nt_readme/nt_readme.yy", r#"{"resourceType":"GMNotes",}"#),
+            ("notes/nt_readme/nt_readme.txt", ""),
+        ],
+    );
+    let (paths, io_errors) = discover(&directory, vec![]).await;
+    assert_eq!(io_errors, 0);
+    assert_eq!(
+        paths,
+        vec![
+            "extensions/ext_tools/ext_tools.gml",
+            "objects/obj_player/Collision_obj_wall.gml",
+            "objects/obj_player/Create_0.gml",
+            "objects/obj_player/Draw_64.gml",
+            "objects/obj_player/Step_2.gml",
+            "rooms/rm_start/InstanceCreationCode_inst_1A2B3C.gml",
+            "rooms/rm_start/InstanceCreationCode_inst_778899.gml",
+            "rooms/rm_start/RoomCreationCode.gml",
+            "scripts/scr_main/scr_main.gml",
+            "timelines/tl_intro/moment_0.gml",
+            "timelines/tl_intro/moment_30.gml",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn skips_files_removed_from_the_project() {
+    let directory = Project::new(
+        "removed",
+        &[
+            (
+                "game.yyp",
+                r#"{"resources":[{"id":{"name":"scr_kept","path":"scripts/scr_kept/scr_kept.yy",},},],}"#,
+            ),
+            (
+                "scripts/scr_kept/scr_kept.yy",
+                r#"{"$GMScript":"v1","name":"scr_kept",}"#,
+            ),
+            ("scripts/scr_kept/scr_kept.gml", ""),
+            ("scripts/scr_removed/scr_removed.yy", r#"{"$GMScript":"v1",}"#),
+            ("scripts/scr_removed/scr_removed.gml", ""),
+            ("scripts/scr_kept/notes.gml.bak", ""),
+        ],
+    );
+    let (paths, io_errors) = discover(&directory, vec![]).await;
+    assert_eq!(io_errors, 0);
+    assert_eq!(paths, vec!["scripts/scr_kept/scr_kept.gml"]);
+}
+
+#[tokio::test]
+async fn reports_missing_project_files() {
+    let directory = Project::new(
+        "missing",
+        &[
+            (
+                "game.yyp",
+                r#"{"resources":[{"id":{"name":"scr_gone","path":"scripts/scr_gone/scr_gone.yy",},},{"id":{"name":"obj_empty","path":"objects/obj_empty/obj_empty.yy",},},],}"#,
+            ),
+            ("objects/obj_empty/obj_empty.yy", OBJECT),
+        ],
+    );
+    let (paths, io_errors) = discover(&directory, vec![]).await;
+    assert!(paths.is_empty());
+    assert_eq!(io_errors, 5);
+}
+
+#[tokio::test]
+async fn respects_ignored_file_paths() {
+    let directory = Project::new(
+        "ignored",
+        &[
+            ("game.yyp", YYP),
+            ("scripts/scr_main/scr_main.yy", r#"{"resourceType":"GMScript",}"#),
+            ("scripts/scr_main/scr_main.gml", ""),
+        ],
+    );
+    let (paths, _) = discover(&directory, vec!["scripts/scr_main/scr_main.gml".into()]).await;
+    assert!(paths.is_empty());
+}
+
+#[tokio::test]
+async fn ignores_folders_and_globs() {
+    let files = [
+        ("objects/obj_player/Create_0.gml", ""),
+        ("objects/obj_player/Draw_0.gml", ""),
+        ("scripts/lib_json/lib_json.gml", ""),
+        ("scripts/lib_json/helpers/lib_json_helpers.gml", ""),
+        ("scripts/scr_main/scr_main.gml", ""),
+        ("scripts/scr_generated/scr_generated.gml", ""),
+    ];
+    let directory = Project::new("globs", &files);
+    let (paths, _) = discover(
+        &directory,
+        vec!["scripts/lib_json/".into(), "Draw_*.gml".into(), "scr_gen*".into()],
+    )
+    .await;
+    assert_eq!(
+        paths,
+        vec!["objects/obj_player/Create_0.gml", "scripts/scr_main/scr_main.gml"]
+    );
+}
+
+#[tokio::test]
+async fn reads_duckignore_files() {
+    let directory = Project::new(
+        "duckignore",
+        &[
+            (".duckignore", "# Vendored code\nscripts/lib_*\n!scripts/lib_ours\n"),
+            ("scripts/lib_json/lib_json.gml", ""),
+            ("scripts/lib_ours/lib_ours.gml", ""),
+            ("scripts/scr_main/scr_main.gml", ""),
+        ],
+    );
+    let (paths, _) = discover(&directory, vec![]).await;
+    assert_eq!(
+        paths,
+        vec!["scripts/lib_ours/lib_ours.gml", "scripts/scr_main/scr_main.gml"]
+    );
+}
+
+#[test]
+fn warns_about_ignored_paths_that_do_not_exist() {
+    let directory = Project::new("missing_ignores", &[("scripts/scr_main/scr_main.gml", "")]);
+    let (ignore_list, warnings) = IgnoreList::load(
+        &directory,
+        &[
+            "scripts/scr_main/scr_main.gml".into(),
+            "scripts/scr_gone/scr_gone.gml".into(),
+            "scripts/*/scr_gone.gml".into(),
+            "scripts/[".into(),
+        ],
+    );
+    assert_eq!(warnings.len(), 2);
+    assert!(warnings[0].contains("scripts/scr_gone/scr_gone.gml"));
+    assert!(warnings[1].contains("scripts/["));
+    assert!(ignore_list.is_ignored(Path::new("scripts/scr_main/scr_main.gml")));
+}
+
+#[tokio::test]
+async fn falls_back_to_walking_directories() {
+    let directory = Project::new(
+        "fallback",
+        &[
+            ("objects/obj_player/Create_0.gml", ""),
+            ("scripts/scr_main/scr_main.gml", ""),
+            ("scripts/scr_main/scr_main.gml.bak", ""),
+            ("rooms/rm_start/RoomCreationCode.gml", ""),
+        ],
+    );
+    let (paths, io_errors) = discover(&directory, vec![]).await;
+    assert_eq!(io_errors, 0);
+    assert_eq!(
+        paths,
+        vec![
+            "objects/obj_player/Create_0.gml",
+            "rooms/rm_start/RoomCreationCode.gml",
+            "scripts/scr_main/scr_main.gml",
+        ]
+    );
+}
+
+#[test]
+fn decodes_origins_from_paths() {
+    assert_eq!(
+        project::ast_origin(Path::new("/game/objects/obj_player/Draw_64.gml")),
+        AstOrigin::ObjectEvent {
+            object: "obj_player".into(),
+            event: ObjectEvent {
+                event_type: EventType::Draw,
+                number: 64
+            },
+        }
+    );
+    assert_eq!(
+        project::ast_origin(Path::new("objects/obj_player/Collision_obj_wall.gml")),
+        AstOrigin::ObjectEvent {
+            object: "obj_player".into(),
+            event: ObjectEvent {
+                event_type: EventType::Collision("obj_wall".into()),
+                number: 0
+            },
+        }
+    );
+    assert_eq!(
+        project::ast_origin(Path::new("scripts/scr_main/scr_main.gml")),
+        AstOrigin::Script {
+            name: "scr_main".into()
+        }
+    );
+    assert_eq!(
+        project::ast_origin(Path::new("rooms/rm_start/RoomCreationCode.gml")),
+        AstOrigin::RoomCreationCode {
+            room: "rm_start".into()
+        }
+    );
+    assert_eq!(
+        project::ast_origin(Path::new("rooms/rm_start/InstanceCreationCode_inst_5A3B1C2D.gml")),
+        AstOrigin::InstanceCreationCode {
+            room: "rm_start".into(),
+            instance: "inst_5A3B1C2D".into()
+        }
+    );
+    assert_eq!(
+        project::ast_origin(Path::new("rooms/rm_start/notes.gml")),
+        AstOrigin::Unknown
+    );
+    assert_eq!(
+        project::ast_origin(Path::new("objects/obj_player/notes.gml")),
+        AstOrigin::Unknown
+    );
+    assert_eq!(project::ast_origin(Path::new("test.gml")), AstOrigin::Unknown);
+}
+
+#[tokio::test]
+async fn attaches_origins_to_loaded_files() {
+    let directory = Project::new("origins", &[("objects/obj_player/Step_0.gml", "foo = 0;")]);
+    let (path_receiver, _) = driver::start_gml_discovery(&directory, IgnoreList::default());
+    let (file_receiver, _) = driver::start_file_load(path_receiver);
+    let (mut ast_receiver, _) = driver::start_parse(file_receiver);
+    let ast = ast_receiver.recv().await.unwrap();
+    assert_eq!(
+        ast.origin(),
+        &AstOrigin::ObjectEvent {
+            object: "obj_player".into(),
+            event: ObjectEvent {
+                event_type: EventType::Step,
+                number: 0
+            },
+        }
+    );
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use common::{Project, run};
+
+#[test]
+fn duplicates_across_files() {
+    let directory = Project::new(
+        "across_files",
+        &[
+            ("scripts/a.gml", "function foo() {}\nfoo();"),
+            ("scripts/b.gml", "// #[allow(duplicate_declaration)]\nfunction foo() {}"),
+            ("scripts/c.gml", "function foo() {}"),
+        ],
+    );
+    let (records, _) = run(&directory, &[]);
+    let mut diagnostics: Vec<(String, Vec<String>)> = records
+        .iter()
+        .filter(|record| record["lint"] == "duplicate_declaration")
+        .map(|record| {
+            let mut labels: Vec<String> = record["labels"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|label| {
+                    format!(
+                        "{} {}",
+                        label["style"].as_str().unwrap(),
+                        label["file"].as_str().unwrap()
+                    )
+                })
+                .collect();
+            labels.sort();
+            (record["file"].as_str().unwrap().to_string(), labels)
+        })
+        .collect();
+    diagnostics.sort();
+
+    // Every declaration is labeled, but the allowed one is not reported itself
+    let labels = |file: &str| {
+        let mut labels: Vec<String> = ["scripts/a.gml", "scripts/b.gml", "scripts/c.gml"]
+            .into_iter()
+            .map(|other| {
+                let style = if other == file { "primary" } else { "secondary" };
+                format!("{style} {other}")
+            })
+            .collect();
+        labels.sort();
+        labels
+    };
+    assert_eq!(
+        diagnostics,
+        vec![
+            ("scripts/a.gml".into(), labels("scripts/a.gml")),
+            ("scripts/c.gml".into(), labels("scripts/c.gml")),
+        ]
+    );
+}
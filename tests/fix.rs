@@ -0,0 +1,141 @@
+use duck::{
+    Config, driver,
+    driver::Ctx,
+    lint::{LintLevel, apply_suggestions},
+    parse::Parser,
+};
+
+fn fix_with(source: &'static str, config: &Config) -> String {
+    let ast = Parser::new(source, 0)
+        .into_ast()
+        .unwrap_or_else(|_| panic!("`{source}` failed to parse"));
+    let mut reports = vec![];
+    driver::lint_ast(&ast, &mut reports, config, &Ctx::default());
+    let fixes: Vec<_> = reports.iter().map(|report| report.suggestions.clone()).collect();
+    apply_suggestions(source, &fixes).0
+}
+
+fn harness_fix(lint_tag: &str, source: &'static str, expected: &str) {
+    let mut config = Config::default();
+    config.lint_levels.insert(lint_tag.into(), LintLevel::Warn);
+    pretty_assertions::assert_eq!(fix_with(source, &config), expected);
+}
+
+#[test]
+fn and_preference() {
+    harness_fix("and_preference", "foo = a and b;", "foo = a && b;");
+}
+
+#[test]
+fn keyword_operators_are_padded() {
+    let mut config = Config {
+        prefer_and_keyword: true,
+        prefer_or_keyword: true,
+        prefer_mod_keyword: true,
+        prefer_not_keyword: true,
+        ..Default::default()
+    };
+    for tag in ["and_preference", "or_preference", "mod_preference", "not_preference"] {
+        config.lint_levels.insert(tag.into(), LintLevel::Warn);
+    }
+    pretty_assertions::assert_eq!(fix_with("foo = !a&&b||c%d;", &config), "foo = not a and b or c mod d;");
+}
+
+#[test]
+fn not_preference() {
+    harness_fix("not_preference", "foo = not   bar;", "foo = !bar;");
+}
+
+#[test]
+fn english_flavor_violation() {
+    harness_fix(
+        "english_flavor_violation",
+        "draw_set_colour(c_red);",
+        "draw_set_color(c_red);",
+    );
+}
+
+#[test]
+fn deprecated() {
+    harness_fix(
+        "deprecated",
+        "variable_struct_set(buzz, key, 0);\nfizz = arr[1, 2];",
+        "struct_set(buzz, key, 0);\nfizz = arr[1][2];",
+    );
+}
+
+#[test]
+fn renames_are_not_applied() {
+    // Renaming a declaration or moving a globalvar onto `global` would break its references
+    harness_fix(
+        "casing_rules",
+        "var FooBar = 0;\nFooBar += 1;",
+        "var FooBar = 0;\nFooBar += 1;",
+    );
+    harness_fix("deprecated", "globalvar foo;\nfoo = 1;", "globalvar foo;\nfoo = 1;");
+}
+
+#[test]
+fn bool_equality() {
+    harness_fix(
+        "bool_equality",
+        "a = b == true;\nc = d == false;\ne = f + g == false;",
+        "a = b;\nc = !d;\ne = !(f + g);",
+    );
+}
+
+#[test]
+fn accessor_alternative() {
+    harness_fix(
+        "accessor_alternative",
+        "a = ds_list_find_value(b, 0) + ds_grid_get(c, 1, 2) + ds_map_find_value(d, \"e\");\nf = array_get(g, 0) + variable_struct_get(h, \"i\") + variable_struct_get(j, k);",
+        "a = b[| 0] + c[# 1, 2] + d[? \"e\"];\nf = g[0] + h.i + j[$ k];",
+    );
+}
+
+#[test]
+fn accessor_alternative_requires_parenthesis() {
+    harness_fix(
+        "accessor_alternative",
+        "a = ds_list_find_value(b + c, 0);",
+        "a = ds_list_find_value(b + c, 0);",
+    );
+}
+
+#[test]
+fn nested_fixes_are_applied_once() {
+    let mut config = Config::default();
+    config.lint_levels.insert("and_preference".into(), LintLevel::Warn);
+    config.lint_levels.insert("bool_equality".into(), LintLevel::Warn);
+    pretty_assertions::assert_eq!(fix_with("a = (b and c) == false;", &config), "a = !(b && c);");
+}
+
+#[test]
+fn overlapping_fixes_are_skipped() {
+    let mut config = Config::default();
+    config.lint_levels.insert("deprecated".into(), LintLevel::Warn);
+    config
+        .lint_levels
+        .insert("accessor_alternative".into(), LintLevel::Warn);
+    let fixed = fix_with("a = variable_struct_get(b, \"c\");", &config);
+    assert!(
+        fixed == "a = struct_get(b, \"c\");" || fixed == "a = b.c;",
+        "unexpected fix: {fixed}"
+    );
+}
+
+#[test]
+fn suggestions_are_kept_out_of_labels() {
+    let source = "foo = a and b;";
+    let ast = Parser::new(source, 0).into_ast().unwrap();
+    let mut config = Config::default();
+    config.lint_levels.insert("and_preference".into(), LintLevel::Warn);
+    let mut reports = vec![];
+    driver::lint_ast(&ast, &mut reports, &config, &Ctx::default());
+    let report = reports
+        .iter()
+        .find(|report| report.diagnostic.code.as_deref() == Some("and_preference"))
+        .unwrap();
+    assert_eq!(report.diagnostic.labels.len(), 1);
+    assert_eq!(report.suggestions.len(), 1);
+}
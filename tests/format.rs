@@ -0,0 +1,248 @@
+use duck::{BraceStyle, Config, format::format_ast, parse::Parser};
+
+fn format_with(source: &'static str, config: &Config) -> String {
+    let ast = Parser::new_lossless(source, 0)
+        .into_ast()
+        .unwrap_or_else(|_| panic!("`{source}` failed to parse"));
+    format_ast(&ast, source, config)
+}
+
+fn harness_format_with(source: &'static str, expected: &str, config: &Config) {
+    let formatted = format_with(source, config);
+    pretty_assertions::assert_eq!(formatted, expected);
+    let reformatted = format_with(Box::leak(Box::from(formatted.as_str())), config);
+    pretty_assertions::assert_eq!(reformatted, expected, "formatting was not idempotent");
+}
+
+fn harness_format(source: &'static str, expected: &str) {
+    harness_format_with(source, expected, &Config::default());
+}
+
+#[test]
+fn assignments() {
+    harness_format("foo=1\nbar  +=  2;", "foo = 1;\nbar += 2;\n");
+}
+
+#[test]
+fn local_variables() {
+    harness_format("var a=1,b,c=2", "var a = 1, b, c = 2;\n");
+}
+
+#[test]
+fn blocks() {
+    harness_format("if foo {bar();\nbuzz();}", "if (foo) {\n    bar();\n    buzz();\n}\n");
+}
+
+#[test]
+fn empty_block() {
+    harness_format("while foo {   }", "while (foo) {}\n");
+}
+
+#[test]
+fn begin_end_and_then() {
+    harness_format("if foo then begin bar(); end", "if (foo) {\n    bar();\n}\n");
+}
+
+#[test]
+fn else_if_chains() {
+    harness_format(
+        "if a { b(); } else if c { d(); } else { e(); }",
+        "if (a) {\n    b();\n} else if (c) {\n    d();\n} else {\n    e();\n}\n",
+    );
+}
+
+#[test]
+fn unbraced_bodies() {
+    harness_format("if a b(); else c();", "if (a)\n    b();\nelse\n    c();\n");
+}
+
+#[test]
+fn for_loops() {
+    harness_format(
+        "for(var i=0;i<10;i++){foo(i);}",
+        "for (var i = 0; i < 10; i++) {\n    foo(i);\n}\n",
+    );
+}
+
+#[test]
+fn do_until() {
+    harness_format("do { foo(); } until bar", "do {\n    foo();\n} until (bar);\n");
+}
+
+#[test]
+fn try_catch() {
+    harness_format(
+        "try { foo(); } catch (e) { bar(); } finally { buzz(); }",
+        "try {\n    foo();\n} catch (e) {\n    bar();\n} finally {\n    buzz();\n}\n",
+    );
+}
+
+#[test]
+fn switches() {
+    harness_format(
+        "switch foo { case 0: bar(); break; case 1: default: buzz(); }",
+        "switch (foo) {\n    case 0:\n        bar();\n        break;\n    case 1:\n    default:\n        buzz();\n}\n",
+    );
+}
+
+#[test]
+fn enums() {
+    harness_format("enum Foo { Bar, Buzz = 1 }", "enum Foo {\n    Bar,\n    Buzz = 1,\n}\n");
+}
+
+#[test]
+fn macros_are_verbatim() {
+    harness_format("#macro foo   1+  2", "#macro foo 1+  2\n");
+}
+
+#[test]
+fn functions() {
+    harness_format(
+        "function foo(a,b=1):bar(a) constructor{static c=function(){return a;};}",
+        "function foo(a, b = 1) : bar(a) constructor {\n    static c = function() {\n        return a;\n    };\n}\n",
+    );
+}
+
+#[test]
+fn expressions() {
+    harness_format(
+        "foo = [1,2,3]; bar = {a:1,b:[]}; buzz = a ? b : -c; fizz = new Foo(1)",
+        "foo = [1, 2, 3];\nbar = { a: 1, b: [] };\nbuzz = (a) ? b : -c;\nfizz = new Foo(1);\n",
+    );
+}
+
+#[test]
+fn accessors() {
+    harness_format(
+        "a[@ 0] = b[? \"c\"] + d[# 1,2] + e[| 3] + f[$ \"g\"] + global.h + self.i + other.j + k.l[1, 2]",
+        "a[@ 0] = b[? \"c\"] + d[# 1, 2] + e[| 3] + f[$ \"g\"] + global.h + self.i + other.j + k.l[1, 2];\n",
+    );
+}
+
+#[test]
+fn literals_are_preserved() {
+    harness_format(
+        "a = @'verbatim'; b = 0.50; c = 0xFF; d = $\"{e} and {f}\";",
+        "a = @'verbatim';\nb = 0.50;\nc = 0xFF;\nd = $\"{e} and {f}\";\n",
+    );
+}
+
+#[test]
+fn unary_spacing() {
+    harness_format("a = - -b; c = -(-d);", "a = - -b;\nc = -(-d);\n");
+}
+
+#[test]
+fn blank_lines_are_collapsed() {
+    harness_format(
+        "\n\nfoo = 1;\n\n\n\nbar = 2;\nbuzz = 3;\n\n",
+        "foo = 1;\n\nbar = 2;\nbuzz = 3;\n",
+    );
+}
+
+#[test]
+fn comments_are_preserved() {
+    harness_format(
+        "// leading\nfoo = 1; // trailing\n/* block */ bar = /* inner */ 2;\n\n// end of file\n",
+        "// leading\nfoo = 1; // trailing\n/* block */\nbar = /* inner */ 2;\n\n// end of file\n",
+    );
+}
+
+#[test]
+fn comments_in_blocks() {
+    harness_format(
+        "if foo { // why\n  bar(); // because\n  // more\n}\nif buzz {\n// nothing\n}",
+        "if (foo) { // why\n    bar(); // because\n    // more\n}\nif (buzz) {\n    // nothing\n}\n",
+    );
+}
+
+#[test]
+fn tags_and_regions_are_preserved() {
+    harness_format(
+        "#region Setup\n// #[allow(global)]\nglobal.foo=1;\n#endregion",
+        "#region Setup\n// #[allow(global)]\nglobal.foo = 1;\n#endregion\n",
+    );
+}
+
+#[test]
+fn keyword_preferences() {
+    let mut config = Config::default();
+    let source = "a = b and c or not d; e = f mod g;";
+    config.prefer_and_keyword = false;
+    config.prefer_or_keyword = false;
+    config.prefer_not_keyword = false;
+    config.prefer_mod_keyword = false;
+    harness_format_with(source, "a = b && c || !d;\ne = f % g;\n", &config);
+    config.prefer_and_keyword = true;
+    config.prefer_or_keyword = true;
+    config.prefer_not_keyword = true;
+    config.prefer_mod_keyword = true;
+    harness_format_with(
+        "a = b && c || !d; e = f % g;",
+        "a = b and c or not d;\ne = f mod g;\n",
+        &config,
+    );
+}
+
+#[test]
+fn without_statement_parentheticals() {
+    let config = Config {
+        statement_parentheticals: false,
+        ..Default::default()
+    };
+    harness_format_with(
+        "if (foo) { bar(); }\nwhile (a) b();\nx = (a) ? b : c;",
+        "if foo {\n    bar();\n}\nwhile (a)\n    b();\nx = a ? b : c;\n",
+        &config,
+    );
+}
+
+#[test]
+fn next_line_braces() {
+    let mut config = Config::default();
+    config.formatting_rules.brace_style = BraceStyle::NextLine;
+    harness_format_with(
+        "if foo { bar(); } else { buzz(); }",
+        "if (foo)\n{\n    bar();\n}\nelse\n{\n    buzz();\n}\n",
+        &config,
+    );
+}
+
+#[test]
+fn indentation() {
+    let mut config = Config::default();
+    config.formatting_rules.use_tabs = true;
+    harness_format_with("if foo { bar(); }", "if (foo) {\n\tbar();\n}\n", &config);
+    config.formatting_rules.use_tabs = false;
+    config.formatting_rules.indent_width = 2;
+    harness_format_with("if foo { bar(); }", "if (foo) {\n  bar();\n}\n", &config);
+}
+
+#[test]
+fn operator_spacing() {
+    let mut config = Config::default();
+    config.formatting_rules.spaces_around_operators = false;
+    harness_format_with("a = b + c * d and e;", "a=b+c*d&&e;\n", &config);
+    config.prefer_mod_keyword = true;
+    harness_format_with("a = b % c;", "a=b mod c;\n", &config);
+}
+
+#[test]
+fn unspaced_operators_stay_apart_from_signs() {
+    let mut config = Config::default();
+    config.formatting_rules.spaces_around_operators = false;
+    harness_format_with("a = b - -c;", "a=b- -c;\n", &config);
+    harness_format_with("d = e + +f;", "d=e+ +f;\n", &config);
+    harness_format_with("g = h - --i * j;", "g=h- --i*j;\n", &config);
+    harness_format_with("k = l - +m + -n;", "k=l-+m+-n;\n", &config);
+}
+
+#[test]
+fn unspaced_operators_are_idempotent() {
+    let mut config = Config::default();
+    config.formatting_rules.spaces_around_operators = false;
+    let once = format_with("a = b - -c;\nd = e + +f;", &config);
+    let twice = format_with(Box::leak(Box::from(once.as_str())), &config);
+    pretty_assertions::assert_eq!(once, twice);
+    pretty_assertions::assert_eq!(twice, "a=b- -c;\nd=e+ +f;\n");
+}
@@ -0,0 +1,124 @@
+use duck::parse::{Ast, ExprKind, Parser, StmtKind, TokenKind};
+
+fn parse_lossless(source: &'static str) -> Ast {
+    let (ast, errors) = Parser::new_lossless(source, 0).into_partial_ast();
+    assert!(errors.is_empty(), "`{source}` failed to parse");
+    ast
+}
+
+fn harness_roundtrip(source: &'static str) {
+    let ast = parse_lossless(source);
+    pretty_assertions::assert_eq!(ast.reconstruct(source), source);
+}
+
+#[test]
+fn roundtrip_whitespace() {
+    harness_roundtrip("  foo   =  1 ;\n\n\tbar = 2;   \n");
+}
+
+#[test]
+fn roundtrip_crlf() {
+    harness_roundtrip("foo = 1;\r\nbar = 2;\r\n");
+}
+
+#[test]
+fn roundtrip_comments() {
+    harness_roundtrip("// leading\nfoo = 1; // trailing\n/* block */ bar = /* inner */ 2;\n\n// end of file\n");
+}
+
+#[test]
+fn roundtrip_regions_and_tags() {
+    harness_roundtrip("#region Setup\n// #[allow(global)]\nglobal.foo = 1;\n#endregion\n");
+}
+
+#[test]
+fn roundtrip_nested() {
+    harness_roundtrip(
+        "function foo(a, b = 1) constructor {\n    // comment\n    static bar = function() {\n        return a +   b;\n    }\n}\n\nif foo { bar(  ); } else {\n    /* nothing */\n}\n",
+    );
+}
+
+#[test]
+fn roundtrip_declarations() {
+    harness_roundtrip(
+        "#macro FOO 1 // not a comment\nenum Bar {\n    A, // first\n    B = 2,\n}\nvar a = 1,  b;\nswitch a {\n    case Bar.A: // a\n        break;\n    default:\n}\n",
+    );
+}
+
+#[test]
+fn roundtrip_template_strings() {
+    harness_roundtrip("foo = $\"{ a /* a */ } and { b }\"; // done\n");
+}
+
+#[test]
+fn roundtrip_with_errors() {
+    let source = "foo = ;\n// still here\nbar = 1;\n";
+    let (ast, errors) = Parser::new_lossless(source, 0).into_partial_ast();
+    assert_eq!(errors.len(), 1);
+    pretty_assertions::assert_eq!(ast.reconstruct(source), source);
+}
+
+#[test]
+fn unterminated_template_string() {
+    let source = "foo = 1;\nbar = $\"{foo} and\nbuzz = 2;\n";
+    let (ast, errors) = Parser::new_lossless(source, 0).into_partial_ast();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "Unterminated template string");
+    assert_eq!(errors[0].labels[0].range, 15..17);
+    pretty_assertions::assert_eq!(ast.reconstruct(source), source);
+}
+
+#[test]
+fn roundtrip_empty() {
+    harness_roundtrip("");
+    harness_roundtrip("\n\n// just a comment\n");
+}
+
+#[test]
+fn leading_and_trailing_trivia() {
+    let ast = parse_lossless("// leading\nfoo = 1; // trailing\nbar = 2;");
+    let trivia = ast.stmts()[0].trivia().unwrap();
+    assert_eq!(
+        trivia.leading.iter().map(|t| t.token_type).collect::<Vec<_>>(),
+        vec![TokenKind::Comment("// leading"), TokenKind::Whitespace("\n")]
+    );
+    assert_eq!(
+        trivia.trailing.iter().map(|t| t.token_type).collect::<Vec<_>>(),
+        vec![
+            TokenKind::Whitespace(" "),
+            TokenKind::Comment("// trailing"),
+            TokenKind::Whitespace("\n")
+        ]
+    );
+    assert!(ast.stmts()[1].trivia().is_none());
+}
+
+#[test]
+fn expression_trivia() {
+    let ast = parse_lossless("foo = /* inner */ 2;");
+    let StmtKind::Assignment(assignment) = ast.stmts()[0].kind() else {
+        panic!("expected an assignment");
+    };
+    assert!(matches!(assignment.right.kind(), ExprKind::Literal(_)));
+    assert_eq!(
+        assignment
+            .right
+            .trivia()
+            .unwrap()
+            .leading
+            .iter()
+            .map(|t| t.token_type)
+            .collect::<Vec<_>>(),
+        vec![
+            TokenKind::Whitespace(" "),
+            TokenKind::Comment("/* inner */"),
+            TokenKind::Whitespace(" ")
+        ]
+    );
+}
+
+#[test]
+fn trivia_is_not_collected_by_default() {
+    let (ast, _) = Parser::new("// leading\nfoo = 1;", 0).into_partial_ast();
+    assert!(ast.stmts()[0].trivia().is_none());
+}
@@ -0,0 +1,267 @@
+mod common;
+
+use common::Project;
+use serde_json::{Value, json};
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    path::Path,
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+};
+
+/// A scripted client that talks to `duck lsp` over stdio.
+struct Client {
+    process: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+impl Client {
+    fn start() -> Self {
+        Self::start_in(None)
+    }
+
+    /// Starts the server within a project, which it scans for declarations.
+    fn start_in(project_directory: Option<&Path>) -> Self {
+        let mut process = Command::new(env!("CARGO_BIN_EXE_duck"))
+            .arg("lsp")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("failed to start duck lsp");
+        let stdin = process.stdin.take().unwrap();
+        let stdout = BufReader::new(process.stdout.take().unwrap());
+        let mut client = Self { process, stdin, stdout };
+        client.request(
+            1,
+            "initialize",
+            json!({
+                "capabilities": {},
+                "rootUri": project_directory.map(|directory| format!("file://{}", directory.display()))
+            }),
+        );
+        client.notify("initialized", json!({}));
+        client
+    }
+
+    fn send(&mut self, message: Value) {
+        let body = message.to_string();
+        write!(self.stdin, "Content-Length: {}\r\n\r\n{body}", body.len()).unwrap();
+        self.stdin.flush().unwrap();
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+    }
+
+    /// Sends a request and returns the result of its response.
+    fn request(&mut self, id: i64, method: &str, params: Value) -> Value {
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+        self.receive_until(|message| message["id"] == id)["result"].clone()
+    }
+
+    /// Reads messages from the server until one matches the predicate, returning it.
+    fn receive_until(&mut self, predicate: impl Fn(&Value) -> bool) -> Value {
+        loop {
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                assert_ne!(
+                    self.stdout.read_line(&mut header).unwrap(),
+                    0,
+                    "duck lsp closed unexpectedly"
+                );
+                let header = header.trim_end();
+                if header.is_empty() {
+                    break;
+                }
+                if let Some(length) = header.strip_prefix("Content-Length: ") {
+                    content_length = length.parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            self.stdout.read_exact(&mut body).unwrap();
+            let message: Value = serde_json::from_slice(&body).unwrap();
+            if predicate(&message) {
+                return message;
+            }
+        }
+    }
+
+    fn open(&mut self, uri: &str, text: &str) -> Value {
+        self.notify(
+            "textDocument/didOpen",
+            json!({ "textDocument": { "uri": uri, "languageId": "gml", "version": 1, "text": text } }),
+        );
+        self.receive_diagnostics()
+    }
+
+    fn change(&mut self, uri: &str, version: i64, text: &str) -> Value {
+        self.notify(
+            "textDocument/didChange",
+            json!({
+                "textDocument": { "uri": uri, "version": version },
+                "contentChanges": [{ "text": text }]
+            }),
+        );
+        self.receive_diagnostics()
+    }
+
+    /// Returns the lint of every diagnostic published for a document.
+    fn codes(diagnostics: &Value) -> Vec<&str> {
+        diagnostics["diagnostics"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|diagnostic| diagnostic["code"].as_str())
+            .collect()
+    }
+
+    fn receive_diagnostics(&mut self) -> Value {
+        self.receive_until(|message| message["method"] == "textDocument/publishDiagnostics")["params"].clone()
+    }
+
+    fn shutdown(mut self) {
+        self.request(99, "shutdown", Value::Null);
+        self.notify("exit", Value::Null);
+        assert!(self.process.wait().unwrap().success());
+    }
+}
+
+#[test]
+fn publishes_diagnostics() {
+    let mut client = Client::start();
+    let diagnostics = client.open("file:///test.gml", "foo = 0;\nbar = ds_list_find_value(list, 0);\n");
+    assert_eq!(diagnostics["uri"], "file:///test.gml");
+    let diagnostics = diagnostics["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0]["code"], "accessor_alternative");
+    assert_eq!(diagnostics[0]["source"], "duck");
+    assert_eq!(
+        diagnostics[0]["range"],
+        json!({ "start": { "line": 1, "character": 6 }, "end": { "line": 1, "character": 33 } })
+    );
+    client.shutdown();
+}
+
+#[test]
+fn republishes_diagnostics_on_change() {
+    let mut client = Client::start();
+    client.open("file:///test.gml", "bar = ds_list_find_value(list, 0);");
+    client.notify(
+        "textDocument/didChange",
+        json!({
+            "textDocument": { "uri": "file:///test.gml", "version": 2 },
+            "contentChanges": [{ "text": "bar = list[| 0];" }]
+        }),
+    );
+    assert_eq!(client.receive_diagnostics()["diagnostics"], json!([]));
+    client.shutdown();
+}
+
+#[test]
+fn reports_parse_errors() {
+    let mut client = Client::start();
+    let diagnostics = client.open("file:///test.gml", "foo = ;\nbar = 1;");
+    let diagnostics = diagnostics["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0]["severity"], 1);
+    client.shutdown();
+}
+
+#[test]
+fn offers_code_actions() {
+    let mut client = Client::start();
+    client.open("file:///test.gml", "bar = ds_list_find_value(list, 0);");
+    let actions = client.request(
+        2,
+        "textDocument/codeAction",
+        json!({
+            "textDocument": { "uri": "file:///test.gml" },
+            "range": { "start": { "line": 0, "character": 10 }, "end": { "line": 0, "character": 10 } },
+            "context": { "diagnostics": [] }
+        }),
+    );
+    let actions = actions.as_array().unwrap();
+    assert_eq!(actions.len(), 1);
+    assert_eq!(actions[0]["kind"], "quickfix");
+    let edits = actions[0]["edit"]["changes"]["file:///test.gml"].as_array().unwrap();
+    let new_texts: Vec<&str> = edits.iter().map(|edit| edit["newText"].as_str().unwrap()).collect();
+    assert_eq!(new_texts, vec!["", "[| ", "]"]);
+    client.shutdown();
+}
+
+#[test]
+fn documents_replace_the_declarations_of_their_files() {
+    let directory = Project::new(
+        "declarations",
+        &[
+            ("scripts/a.gml", "function foo() {}"),
+            ("scripts/b.gml", "function bar() {}"),
+        ],
+    );
+    let a = directory.uri("scripts/a.gml");
+    let b = directory.uri("scripts/b.gml");
+    let mut client = Client::start_in(Some(&directory));
+
+    // Opening b.gml must keep the declarations the scan found in a.gml
+    let diagnostics = client.open(&b, "function bar(foo) {\n    return foo;\n}");
+    assert!(Client::codes(&diagnostics).contains(&"fucntion_name_as_parameter"));
+
+    // Once a.gml no longer declares foo, nothing else does
+    client.open(&a, "");
+    let diagnostics = client.change(&b, 2, "function bar(foo) {\n    return foo;\n}");
+    client.shutdown();
+    assert!(!Client::codes(&diagnostics).contains(&"fucntion_name_as_parameter"));
+}
+
+#[test]
+fn deleting_the_last_reference_reports_unused_functions() {
+    let directory = Project::new(
+        "references",
+        &[
+            (".duck.toml", "[lint_levels]\nunused_function = \"warn\""),
+            ("scripts/a.gml", "function foo() {}"),
+            ("scripts/b.gml", "foo();"),
+        ],
+    );
+    let a = directory.uri("scripts/a.gml");
+    let b = directory.uri("scripts/b.gml");
+    let mut client = Client::start_in(Some(&directory));
+    let diagnostics = client.open(&a, "function foo() {}");
+    assert!(!Client::codes(&diagnostics).contains(&"unused_function"));
+
+    client.open(&b, "");
+    let diagnostics = client.change(&a, 2, "function foo() {}");
+    client.shutdown();
+    assert!(Client::codes(&diagnostics).contains(&"unused_function"));
+}
+
+#[test]
+fn labels_in_other_files_are_related_information() {
+    let directory = Project::new(
+        "related",
+        &[
+            ("scripts/a.gml", "\nfunction foo() {}"),
+            ("scripts/b.gml", "function foo() {}"),
+        ],
+    );
+    let a = format!(
+        "file://{}",
+        directory.join("scripts/a.gml").canonicalize().unwrap().display()
+    );
+    let b = directory.uri("scripts/b.gml");
+    let mut client = Client::start_in(Some(&directory));
+    let diagnostics = client.open(&b, "function foo() {}");
+    client.shutdown();
+    let diagnostic = diagnostics["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|diagnostic| diagnostic["code"] == "duplicate_declaration")
+        .unwrap();
+    assert_eq!(diagnostic["range"]["start"]["line"], 0);
+    let related = diagnostic["relatedInformation"].as_array().unwrap();
+    assert_eq!(related.len(), 1);
+    assert_eq!(related[0]["location"]["uri"], a);
+    assert_eq!(related[0]["location"]["range"]["start"]["line"], 1);
+    assert_eq!(related[0]["message"], "...and again here");
+}
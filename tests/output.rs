@@ -0,0 +1,94 @@
+mod common;
+
+use common::{Project, duck_run};
+use serde_json::Value;
+
+/// Creates a project on disk containing a single script with one warning in it.
+fn project(name: &str) -> Project {
+    let project = Project::new(
+        name,
+        &[("scripts/test.gml", "foo = 0;\nbar = ds_list_find_value(list, 0);\n")],
+    );
+    project.create_dir("objects");
+    project.create_dir("rooms");
+    project
+}
+
+/// Runs `duck run` with the given format on a fresh project, returning its stdout and status.
+fn run(format: &str) -> (String, i32) {
+    let directory = project(format);
+    let output = duck_run(&directory, format).output().expect("failed to run duck");
+    (String::from_utf8(output.stdout).unwrap(), output.status.code().unwrap())
+}
+
+#[test]
+fn json() {
+    let (stdout, status) = run("json");
+    assert_eq!(status, 1);
+    let records: Value = serde_json::from_str(&stdout).unwrap();
+    let records = records.as_array().unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0]["lint"], "accessor_alternative");
+    assert_eq!(records[0]["severity"], "warning");
+    assert_eq!(records[0]["file"], "scripts/test.gml");
+    assert_eq!(records[0]["start"]["line"], 2);
+    assert_eq!(records[0]["start"]["column"], 7);
+    assert_eq!(records[0]["end"]["column"], 34);
+    assert!(!records[0]["labels"].as_array().unwrap().is_empty());
+    assert!(records[0]["notes"].is_array());
+}
+
+#[test]
+fn sarif() {
+    let (stdout, _) = run("sarif");
+    let log: Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(log["version"], "2.1.0");
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "duck");
+    assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "accessor_alternative");
+    let result = &run["results"][0];
+    assert_eq!(result["ruleId"], "accessor_alternative");
+    assert_eq!(result["level"], "warning");
+    let location = &result["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "scripts/test.gml");
+    assert_eq!(location["region"]["startLine"], 2);
+    assert_eq!(location["region"]["startColumn"], 7);
+}
+
+#[test]
+fn checkstyle() {
+    let (stdout, _) = run("checkstyle");
+    assert!(stdout.starts_with("<?xml"));
+    assert!(stdout.contains("<file name=\"scripts/test.gml\">"));
+    assert!(stdout.contains("line=\"2\" column=\"7\" severity=\"warning\""));
+    assert!(stdout.contains("source=\"duck.accessor_alternative\""));
+}
+
+#[test]
+fn junit() {
+    let (stdout, _) = run("junit");
+    assert!(stdout.contains("<testsuites name=\"duck\" tests=\"1\" failures=\"1\">"));
+    assert!(stdout.contains("<testsuite name=\"scripts/test.gml\" tests=\"1\" failures=\"1\">"));
+    assert!(stdout.contains("<testcase name=\"accessor_alternative (2:7)\""));
+}
+
+#[test]
+fn github() {
+    let (stdout, _) = run("github");
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 1);
+    assert!(
+        lines[0].starts_with(
+            "::warning file=scripts/test.gml,line=2,col=7,endLine=2,endColumn=34,title=accessor_alternative::"
+        ),
+        "{}",
+        lines[0]
+    );
+}
+
+#[test]
+fn machine_formats_respect_allow_flags() {
+    let directory = project("allow");
+    let status = duck_run(&directory, "json").arg("--allow-warnings").status().unwrap();
+    assert_eq!(status.code(), Some(0));
+}
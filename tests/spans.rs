@@ -0,0 +1,54 @@
+use duck::{
+    Config,
+    driver::{self, Ctx},
+    parse::ParseVisitor,
+};
+use std::ops::Range;
+
+/// Returns the span of every top level statement parsed from the source.
+fn stmt_spans(source: &'static str) -> Vec<Range<usize>> {
+    let ast = driver::parse_gml(source, &0).unwrap();
+    ast.stmts().iter().map(|stmt| Range::from(stmt.span())).collect()
+}
+
+#[test]
+fn statements_end_with_their_last_token() {
+    assert_eq!(stmt_spans("foo = 1;"), vec![0..8]);
+    assert_eq!(stmt_spans("foo = bar_baz"), vec![0..13]);
+    assert_eq!(stmt_spans("exit"), vec![0..4]);
+    assert_eq!(stmt_spans("return foo;\nexit;"), vec![0..11, 12..17]);
+    assert_eq!(stmt_spans("if a {\n    b = 1;\n}"), vec![0..19]);
+}
+
+#[test]
+fn statements_exclude_surrounding_trivia() {
+    assert_eq!(stmt_spans("  foo = 1; // note\n\nbar()  "), vec![2..10, 20..25]);
+    assert_eq!(stmt_spans("/* a */ exit /* b */"), vec![8..12]);
+}
+
+#[test]
+fn nested_statements() {
+    let ast = driver::parse_gml("while a {\n    foo = 1\n}", &0).unwrap();
+    let mut spans = vec![];
+    ast.stmts()[0].visit_child_stmts(|block| {
+        block.visit_child_stmts(|stmt| spans.push(Range::from(stmt.span())));
+    });
+    assert_eq!(spans, vec![14..21]);
+}
+
+#[test]
+fn diagnostics_cover_entire_statements() {
+    // `exit` is reported on its whole statement, which previously ended after its first character
+    let source = "foo = 1;\nexit\n";
+    let ast = driver::parse_gml(source, &0).unwrap();
+    let mut config = Config::default();
+    config.lint_levels.insert("exit".into(), duck::lint::LintLevel::Warn);
+    let mut reports = vec![];
+    driver::lint_ast(&ast, &mut reports, &config, &Ctx::default());
+    let ranges: Vec<Range<usize>> = reports
+        .iter()
+        .filter(|report| report.diagnostic.code.as_deref() == Some("exit"))
+        .map(|report| report.diagnostic.labels[0].range.clone())
+        .collect();
+    assert_eq!(ranges, vec![9..13]);
+}
@@ -0,0 +1,110 @@
+use duck::{
+    Symbol, SymbolKind, SymbolTable,
+    driver::{self, Ctx},
+    parse::{AstOrigin, EventType, Location, ObjectEvent, Span},
+};
+
+/// Collects the symbols declared within the gml, as if it were in a file with the given id.
+fn symbols(source: &'static str, file_id: usize, origin: AstOrigin) -> SymbolTable {
+    let mut ast = driver::parse_gml(source, &file_id).unwrap();
+    ast.set_origin(origin);
+    let mut ctx = Ctx::default();
+    ctx.collect_ast(&ast);
+    ctx.symbols
+}
+
+#[test]
+fn every_kind_of_declaration() {
+    let source = "function foo() {
+    enum Dir { Left, Right }
+    global.bar = 0;
+}
+function Vec2() constructor {}
+#macro SPEED 4
+globalvar baz;
+global.bar = 1;";
+    let table = symbols(source, 3, AstOrigin::Unknown);
+    let found: Vec<(&str, SymbolKind, Location)> = table
+        .iter()
+        .map(|symbol| (symbol.name.as_str(), symbol.kind.clone(), symbol.location))
+        .collect();
+    let member = || SymbolKind::EnumMember {
+        enum_name: "Dir".into(),
+    };
+    assert_eq!(
+        found,
+        vec![
+            ("foo", SymbolKind::Function, Location(3, Span::new(9, 12))),
+            ("Dir", SymbolKind::Enum, Location(3, Span::new(26, 29))),
+            ("Left", member(), Location(3, Span::new(32, 36))),
+            ("Right", member(), Location(3, Span::new(38, 43))),
+            ("bar", SymbolKind::GlobalVariable, Location(3, Span::new(57, 60))),
+            ("Vec2", SymbolKind::Constructor, Location(3, Span::new(77, 81))),
+            (
+                "SPEED",
+                SymbolKind::Macro { config: None },
+                Location(3, Span::new(106, 111))
+            ),
+            ("baz", SymbolKind::GlobalVariable, Location(3, Span::new(124, 127))),
+        ]
+    );
+    assert!(table.is_function("Vec2"));
+    assert_eq!(table.find_enum("Dir").map(|symbol| symbol.location.0), Some(3));
+    assert_eq!(
+        table.find("Dir.Right").map(|symbol| symbol.name.as_str()),
+        Some("Right")
+    );
+    assert!(!table.contains("Right"));
+}
+
+#[test]
+fn object_events() {
+    let origin = AstOrigin::ObjectEvent {
+        object: "obj_player".into(),
+        event: ObjectEvent {
+            event_type: EventType::Create,
+            number: 0,
+        },
+    };
+    let mut table = symbols("function jump() {}", 1, origin.clone());
+    table.extend(symbols("", 2, origin));
+
+    // Functions in events belong to the instance, and the object is only recorded once
+    assert!(!table.contains("jump"));
+    assert_eq!(
+        table.iter().collect::<Vec<_>>(),
+        vec![&Symbol::new(
+            "obj_player",
+            SymbolKind::Object,
+            Location(1, Span::default())
+        )]
+    );
+}
+
+#[test]
+fn replacing_a_file() {
+    let mut table = symbols("enum Dir { Left, Right }\nfunction foo() {}", 0, AstOrigin::Unknown);
+    table.extend(symbols("function foo() {}\nfunction bar() {}", 1, AstOrigin::Unknown));
+    table.replace_file(0, symbols("enum Dir { Up }\nfunction bar() {}", 0, AstOrigin::Unknown));
+    let members: Vec<&str> = table.enum_members("Dir").map(|member| member.name.as_str()).collect();
+    assert_eq!(members, vec!["Up"]);
+    assert_eq!(table.get("Dir").count(), 1);
+
+    // Declarations made in other files are left alone, even when they share a name
+    let files = |name| table.get(name).map(|symbol| symbol.location.0).collect::<Vec<_>>();
+    assert_eq!(files("foo"), vec![1]);
+    assert_eq!(files("bar"), vec![1, 0]);
+}
+
+#[test]
+fn serialization() {
+    let table = symbols("enum Dir { Left }\n#macro SPEED 4", 0, AstOrigin::Unknown);
+    let json = serde_json::to_string(&table).unwrap();
+    let table: SymbolTable = serde_json::from_str(&json).unwrap();
+    assert_eq!(table.len(), 3);
+    assert_eq!(
+        table.find("SPEED").map(|symbol| symbol.kind.clone()),
+        Some(SymbolKind::Macro { config: None })
+    );
+    assert!(table.find("Dir.Left").is_some());
+}
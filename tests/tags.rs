@@ -0,0 +1,335 @@
+mod common;
+
+use codespan_reporting::diagnostic::Severity;
+use common::Project;
+use duck::{
+    Config, Duck,
+    driver::{self, Ctx},
+    lint::LintLevel,
+    parse::Parser,
+};
+
+/// Lints the source with the given config, returning the tag and severity of every diagnostic.
+fn lint(source: &'static str, config: &Config) -> Vec<(String, Severity)> {
+    let ast = Parser::new(source, 0)
+        .into_ast()
+        .unwrap_or_else(|_| panic!("`{source}` failed to parse"));
+    let mut reports = vec![];
+    driver::lint_ast(&ast, &mut reports, config, &Ctx::default());
+    reports
+        .into_iter()
+        .map(|report| (report.diagnostic.code.unwrap(), report.diagnostic.severity))
+        .collect()
+}
+
+fn config_with(lint_tag: &str, level: LintLevel) -> Config {
+    let mut config = Config::default();
+    config.lint_levels.insert(lint_tag.into(), level);
+    config
+}
+
+#[test]
+fn allow_tags_silence_lints() {
+    let config = config_with("deprecated", LintLevel::Deny);
+    assert_eq!(lint("// #[allow(deprecated)]\nglobalvar foo;", &config), vec![]);
+}
+
+#[test]
+fn warn_tags_lower_denied_lints() {
+    let config = config_with("deprecated", LintLevel::Deny);
+    assert_eq!(
+        lint("// #[warn(deprecated)]\nglobalvar foo;", &config),
+        vec![("deprecated".into(), Severity::Warning)]
+    );
+}
+
+#[test]
+fn warn_tags_enable_allowed_lints() {
+    let config = config_with("deprecated", LintLevel::Allow);
+    assert_eq!(
+        lint("globalvar foo;\n// #[warn(deprecated)]\nglobalvar bar;", &config),
+        vec![("deprecated".into(), Severity::Warning)]
+    );
+}
+
+#[test]
+fn deny_tags_raise_lints() {
+    let config = config_with("deprecated", LintLevel::Allow);
+    assert_eq!(
+        lint("// #[deny(deprecated)]\nglobalvar foo;", &config),
+        vec![("deprecated".into(), Severity::Error)]
+    );
+}
+
+#[test]
+fn tags_only_apply_to_their_lint() {
+    let config = config_with("deprecated", LintLevel::Warn);
+    assert_eq!(
+        lint("// #[deny(todo)]\nglobalvar foo;", &config),
+        vec![("deprecated".into(), Severity::Warning)]
+    );
+}
+
+#[test]
+fn ast_pass_lints_respect_allow_tags() {
+    let config = config_with("unused_local_variable", LintLevel::Deny);
+    assert_eq!(
+        lint("// #[allow(unused_local_variable)]\nvar foo = 0;", &config),
+        vec![]
+    );
+    assert_eq!(
+        lint("var foo = 0;", &config),
+        vec![("unused_local_variable".into(), Severity::Error)]
+    );
+}
+
+#[test]
+fn ast_pass_lints_respect_raising_tags() {
+    let config = config_with("unused_local_variable", LintLevel::Allow);
+    assert_eq!(
+        lint("var foo = 0;\n// #[warn(unused_local_variable)]\nvar bar = 0;", &config),
+        vec![("unused_local_variable".into(), Severity::Warning)]
+    );
+    assert_eq!(
+        lint("// #[deny(unused_local_variable)]\nvar foo = 0;", &config),
+        vec![("unused_local_variable".into(), Severity::Error)]
+    );
+}
+
+#[test]
+fn ast_pass_lints_use_the_innermost_tag() {
+    let config = config_with("unused_local_variable", LintLevel::Warn);
+    assert_eq!(
+        lint(
+            "// #[deny(unused_local_variable)]\nfunction foo() {\n    // #[allow(unused_local_variable)]\n    var bar = 0;\n    var buzz = 0;\n}",
+            &config
+        ),
+        vec![("unused_local_variable".into(), Severity::Error)]
+    );
+}
+
+#[test]
+fn function_tags_cascade_to_their_bodies() {
+    let config = config_with("show_debug_message", LintLevel::Warn);
+    assert_eq!(
+        lint(
+            "// #[allow(show_debug_message)]\nfunction foo() {\n    if true {\n        show_debug_message(0);\n    }\n}\nshow_debug_message(1);",
+            &config
+        ),
+        vec![("show_debug_message".into(), Severity::Warning)]
+    );
+}
+
+#[test]
+fn tags_do_not_reach_back_into_the_previous_statement() {
+    let config = config_with("show_debug_message", LintLevel::Warn);
+    assert_eq!(
+        lint(
+            "function a() {\n    show_debug_message(0);\n}\n// #[allow(show_debug_message)]\nfunction b() {\n    show_debug_message(1);\n}",
+            &config
+        ),
+        vec![("show_debug_message".into(), Severity::Warning)]
+    );
+}
+
+#[test]
+fn block_tags_cascade_to_their_contents() {
+    let config = config_with("show_debug_message", LintLevel::Allow);
+    assert_eq!(
+        lint(
+            "// #[deny(show_debug_message)]\n{\n    show_debug_message(0);\n    // #[allow(show_debug_message)]\n    show_debug_message(1);\n}",
+            &config
+        ),
+        vec![("show_debug_message".into(), Severity::Error)]
+    );
+}
+
+#[test]
+fn region_tags_cover_their_region() {
+    let config = config_with("show_debug_message", LintLevel::Warn);
+    assert_eq!(
+        lint(
+            "// #[allow(show_debug_message)]\n#region debug\nshow_debug_message(0);\nshow_debug_message(1);\n#endregion\nshow_debug_message(2);",
+            &config
+        ),
+        vec![("show_debug_message".into(), Severity::Warning)]
+    );
+}
+
+#[test]
+fn unclosed_region_tags_cover_the_rest_of_the_file() {
+    let config = config_with("show_debug_message", LintLevel::Warn);
+    assert_eq!(
+        lint(
+            "show_debug_message(0);\n// #[deny(show_debug_message)]\n#region\nshow_debug_message(1);",
+            &config
+        ),
+        vec![
+            ("show_debug_message".into(), Severity::Warning),
+            ("show_debug_message".into(), Severity::Error)
+        ]
+    );
+}
+
+#[test]
+fn file_tags_cover_the_whole_file() {
+    let config = config_with("show_debug_message", LintLevel::Warn);
+    assert_eq!(
+        lint(
+            "// #![allow(show_debug_message)]\nshow_debug_message(0);\nfunction foo() {\n    show_debug_message(1);\n}",
+            &config
+        ),
+        vec![]
+    );
+}
+
+#[test]
+fn inner_tags_override_file_tags() {
+    let config = config_with("show_debug_message", LintLevel::Warn);
+    assert_eq!(
+        lint(
+            "// #![allow(show_debug_message)]\nshow_debug_message(0);\n// #[deny(show_debug_message)]\nshow_debug_message(1);",
+            &config
+        ),
+        vec![("show_debug_message".into(), Severity::Error)]
+    );
+}
+
+#[test]
+fn file_tags_after_the_first_statement_are_reported() {
+    let config = config_with("show_debug_message", LintLevel::Warn);
+    assert_eq!(
+        lint(
+            "show_debug_message(0);\n// #![allow(show_debug_message)]\nshow_debug_message(1);",
+            &config
+        ),
+        vec![
+            ("show_debug_message".into(), Severity::Warning),
+            ("show_debug_message".into(), Severity::Warning),
+            ("unused_allow".into(), Severity::Warning),
+        ]
+    );
+    assert_eq!(
+        lint(
+            "// #![allow(unused_allow)]\nfoo = 0;\n// #![allow(show_debug_message)]",
+            &config
+        ),
+        vec![]
+    );
+}
+
+#[test]
+fn unused_allow_tags_are_reported() {
+    let config = config_with("show_debug_message", LintLevel::Warn);
+    assert_eq!(
+        lint("// #[allow(show_debug_message)]\nfoo = 0;", &config),
+        vec![("unused_allow".into(), Severity::Warning)]
+    );
+    assert_eq!(
+        lint("// #![allow(show_debug_message)]\nfoo = 0;", &config),
+        vec![("unused_allow".into(), Severity::Warning)]
+    );
+    assert_eq!(
+        lint(
+            "// #[allow(show_debug_message)]\n#region\nfoo = 0;\n#endregion",
+            &config
+        ),
+        vec![("unused_allow".into(), Severity::Warning)]
+    );
+}
+
+#[test]
+fn allow_tags_that_suppress_lints_are_used() {
+    let config = config_with("show_debug_message", LintLevel::Allow);
+    assert_eq!(
+        lint("// #[allow(show_debug_message)]\nshow_debug_message(0);", &config),
+        vec![]
+    );
+    assert_eq!(
+        lint(
+            "// #![allow(unused_local_variable)]\nfunction foo() {\n    var bar = 0;\n}",
+            &config
+        ),
+        vec![]
+    );
+}
+
+#[test]
+fn unknown_lints_in_tags_are_reported() {
+    let config = Config::default();
+    assert_eq!(
+        lint("// #[allow(show_debug_mesage)]\nfoo = 0;", &config),
+        vec![("unused_allow".into(), Severity::Warning)]
+    );
+    assert_eq!(
+        lint("// #[deny(not_a_lint)]\nfoo = 0;", &config),
+        vec![("unused_allow".into(), Severity::Warning)]
+    );
+}
+
+#[test]
+fn nested_tags_are_reported_separately() {
+    let config = config_with("show_debug_message", LintLevel::Warn);
+    assert_eq!(
+        lint(
+            "// #[allow(show_debug_message)]\nfunction foo() {\n    show_debug_message(0);\n    // #[allow(show_debug_message)]\n    var bar = 1;\n    return bar;\n}",
+            &config
+        ),
+        vec![("unused_allow".into(), Severity::Warning)]
+    );
+    assert_eq!(
+        lint(
+            "// #[allow(show_debug_message)]\nfunction foo() {\n    // #[allow(show_debug_message)]\n    show_debug_message(0);\n}",
+            &config
+        ),
+        vec![("unused_allow".into(), Severity::Warning)]
+    );
+}
+
+#[test]
+fn tags_are_only_reported_on_their_own_statement() {
+    let config = Config::default();
+    assert_eq!(
+        lint("function a() {}\n// #[allow(not_a_lint)]\nfunction b() {}", &config),
+        vec![("unused_allow".into(), Severity::Warning)]
+    );
+}
+
+#[test]
+fn unused_allow_respects_tags() {
+    let config = Config::default();
+    assert_eq!(
+        lint(
+            "// #![allow(unused_allow)]\n// #[allow(show_debug_message)]\nfoo = 0;",
+            &config
+        ),
+        vec![]
+    );
+}
+
+#[tokio::test]
+async fn unused_allow_tags_are_reported_in_projects() {
+    let directory = Project::new(
+        "project",
+        &[
+            (
+                "scripts/used.gml",
+                "// #[allow(show_debug_message)]\nshow_debug_message(0);",
+            ),
+            (
+                "scripts/unused.gml",
+                "// #[allow(show_debug_message)]\nfoo = 0;\nbar = 0;",
+            ),
+        ],
+    );
+    let summary = Duck::new(config_with("show_debug_message", LintLevel::Warn))
+        .run(&directory)
+        .await
+        .unwrap();
+    let lints: Vec<_> = summary
+        .diagnostics()
+        .iter()
+        .filter_map(|report| report.diagnostic.code.as_deref())
+        .collect();
+    assert_eq!(lints, vec!["unused_allow"]);
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use common::{Project, run};
+use std::path::Path;
+
+/// Creates a project on disk with an object and a sprite, and a script that uses both. The lint is
+/// allowed by default, so the project's config enables it.
+fn project(name: &str, gml: &str) -> Project {
+    let project = Project::new(
+        name,
+        &[
+            (".duck.toml", "[lint_levels]\nundefined_identifier = \"warn\""),
+            ("objects/obj_player/Create_0.gml", "hp = 3;"),
+            ("scripts/player/player.gml", gml),
+        ],
+    );
+    project.create_dir("sprites/spr_player");
+    project
+}
+
+/// Runs `duck run` on the project, returning the primary label and notes of every
+/// `undefined_identifier` diagnostic.
+fn undefined_identifiers(directory: &Path) -> Vec<(String, Vec<String>)> {
+    let (records, _) = run(directory, &[]);
+    records
+        .iter()
+        .filter(|record| record["lint"] == "undefined_identifier")
+        .map(|record| {
+            (
+                record["labels"][0]["message"].as_str().unwrap().to_string(),
+                record["notes"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .filter_map(|note| note.as_str().filter(|note| note.starts_with("help")))
+                    .map(String::from)
+                    .collect(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn assets_are_defined() {
+    let directory = project(
+        "assets",
+        "function player_hurt() {\n    obj_player.hp -= 1;\n    sprite_index = spr_player;\n}",
+    );
+    let diagnostics = undefined_identifiers(&directory);
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn typos_are_reported() {
+    let directory = project(
+        "typos",
+        "function player_hurt() {\n    obj_player.hp -= 1;\n}\nplayer_hrut();\nhp = spr_palyer;",
+    );
+    let diagnostics = undefined_identifiers(&directory);
+    assert_eq!(
+        diagnostics,
+        vec![
+            (
+                "`player_hrut` is not a known function".into(),
+                vec!["help: did you mean `player_hurt`?".into()]
+            ),
+            (
+                "`spr_palyer` is not declared anywhere".into(),
+                vec!["help: did you mean `spr_player`?".into()]
+            ),
+        ]
+    );
+}
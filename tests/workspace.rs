@@ -0,0 +1,60 @@
+mod common;
+
+use common::Project;
+use duck::{Config, Workspace};
+
+/// Creates a project on disk containing the given scripts.
+fn project(name: &str, scripts: &[(&str, &str)]) -> Project {
+    let project = Project::new(name, &[]);
+    for (name, source) in scripts {
+        project.write(&format!("scripts/{name}"), source);
+    }
+    project
+}
+
+#[tokio::test]
+async fn only_changed_paths_are_reloaded() {
+    let directory = project("reload", &[("a.gml", "foo = 0;")]);
+    let mut workspace = Workspace::new(&directory, Config::default(), false);
+    assert!(workspace.run(&[]).await.unwrap().diagnostics().is_empty());
+
+    // Until the workspace is told the file changed, it keeps using what it loaded before
+    let path = directory.join("scripts/a.gml");
+    std::fs::write(&path, "bar = ds_list_find_value(list, 0);").unwrap();
+    assert!(workspace.run(&[]).await.unwrap().diagnostics().is_empty());
+    let summary = workspace.run(&[path]).await.unwrap();
+    assert!(!directory.join(".duck").exists());
+    assert_eq!(summary.warning_count(), 1);
+}
+
+#[tokio::test]
+async fn new_and_removed_files_are_noticed() {
+    let directory = project("files", &[("a.gml", "bar = ds_list_find_value(list, 0);")]);
+    let mut workspace = Workspace::new(&directory, Config::default(), false);
+    assert_eq!(workspace.run(&[]).await.unwrap().warning_count(), 1);
+    std::fs::write(directory.join("scripts/b.gml"), "baz = ds_map_find_value(map, 0);").unwrap();
+    assert_eq!(workspace.run(&[]).await.unwrap().warning_count(), 2);
+    std::fs::remove_file(directory.join("scripts/a.gml")).unwrap();
+    let summary = workspace.run(&[]).await.unwrap();
+    assert_eq!(summary.warning_count(), 1);
+    assert_eq!(summary.lines_parsed(), 1);
+}
+
+#[tokio::test]
+async fn unchanged_files_see_new_declarations() {
+    let directory = project(
+        "declarations",
+        &[("a.gml", "function foo(bar) {\n    return bar;\n}"), ("b.gml", "")],
+    );
+    let mut workspace = Workspace::new(&directory, Config::default(), false);
+    assert!(workspace.run(&[]).await.unwrap().diagnostics().is_empty());
+    let path = directory.join("scripts/b.gml");
+    std::fs::write(&path, "function bar() {}").unwrap();
+    let summary = workspace.run(&[path]).await.unwrap();
+    let lints: Vec<_> = summary
+        .diagnostics()
+        .iter()
+        .map(|report| report.diagnostic.code.clone().unwrap_or_default())
+        .collect();
+    assert_eq!(lints, vec!["fucntion_name_as_parameter".to_string()]);
+}
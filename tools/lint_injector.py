@@ -29,6 +29,7 @@ for root, dirs, files in os.walk('src/lint/collection/'):
             'visits_expr_late': 'impl LateExprPass' in lint_file,
             'visits_stmt_late': 'impl LateStmtPass' in lint_file,
             'visits_ast': 'impl AstPass' in lint_file,
+            'visits_ast_late': 'impl LateAstPass' in lint_file,
         })
 
 # Sort them alphabetically
@@ -85,31 +86,37 @@ opreations = [
         'name': 'early expr',
         'tag': 'visits_expr_early',
         'function_name': 'run_early_lint_on_expr',
-        'args': 'expr, config, reports'
+        'args': 'expr, config, scopes, reports'
     },
     {
         'name': 'early stmt',
         'tag': 'visits_stmt_early',
         'function_name': 'run_early_lint_on_stmt',
-        'args': 'stmt, config, reports'
+        'args': 'stmt, config, scopes, reports'
     },
     {
         'name': 'late expr',
         'tag': 'visits_expr_late',
         'function_name': 'run_late_lint_on_expr',
-        'args': 'expr, config, ctx, reports'
+        'args': 'expr, config, ctx, scopes, reports'
     },
     {
         'name': 'late stmt',
         'tag': 'visits_stmt_late',
         'function_name': 'run_late_lint_on_stmt',
-        'args': 'stmt, config, ctx, reports'
+        'args': 'stmt, config, ctx, scopes, reports'
     },
     {
         'name': 'ast',
         'tag': 'visits_ast',
         'function_name': 'run_lint_on_ast',
-        'args': 'ast, config, reports'
+        'args': 'ast, config, scopes, reports'
+    },
+    {
+        'name': 'late ast',
+        'tag': 'visits_ast_late',
+        'function_name': 'run_late_lint_on_ast',
+        'args': 'ast, config, ctx, scopes, reports'
     }
 ]
 